@@ -30,7 +30,7 @@ bench_utils::generate_bench!(roact, {
 
             rules.into_iter().fold(
                 Configuration::empty()
-                    .with_generator(darklua_core::GeneratorParameters::Dense { column_span: 80 }),
+                    .with_generator(darklua_core::GeneratorParameters::dense(80)),
                 |config, rule| config.with_rule(rule)
             )
         }),
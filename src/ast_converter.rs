@@ -907,6 +907,12 @@ impl<'a> AstConverter<'a> {
                         self.pop_expressions(statement.expressions().len())?,
                     );
 
+                    for (index, attribute) in statement.attributes().enumerate() {
+                        if let Some(attribute) = attribute {
+                            local_assign.set_attribute(index, self.convert_attribute(attribute)?);
+                        }
+                    }
+
                     if self.hold_token_data {
                         local_assign.set_tokens(LocalAssignTokens {
                             local: self.convert_token(statement.local_token())?,
@@ -1526,6 +1532,27 @@ impl<'a> AstConverter<'a> {
                     self.push_work(expression);
                 }
             }
+            ast::Stmt::Goto(goto) => {
+                let mut goto_statement =
+                    GotoStatement::new(self.convert_token_to_identifier(goto.label_name())?);
+                if self.hold_token_data {
+                    goto_statement.set_tokens(GotoTokens {
+                        goto: self.convert_token(goto.goto_token())?,
+                    });
+                }
+                self.statements.push(goto_statement.into());
+            }
+            ast::Stmt::Label(label) => {
+                let mut label_statement =
+                    LabelStatement::new(self.convert_token_to_identifier(label.name())?);
+                if self.hold_token_data {
+                    label_statement.set_tokens(LabelTokens {
+                        left_colons: self.convert_token(label.left_colons())?,
+                        right_colons: self.convert_token(label.right_colons())?,
+                    });
+                }
+                self.statements.push(label_statement.into());
+            }
             ast::Stmt::If(if_statement) => {
                 self.work_stack.push(ConvertWork::MakeIfStatement {
                     statement: if_statement,
@@ -2637,6 +2664,11 @@ impl<'a> AstConverter<'a> {
             ast::BinOp::TildeEqual(_) => BinaryOperator::NotEqual,
             ast::BinOp::TwoDots(_) => BinaryOperator::Concat,
             ast::BinOp::TwoEqual(_) => BinaryOperator::Equal,
+            ast::BinOp::Ampersand(_) => BinaryOperator::BitwiseAnd,
+            ast::BinOp::Pipe(_) => BinaryOperator::BitwiseOr,
+            ast::BinOp::Tilde(_) => BinaryOperator::BitwiseXor,
+            ast::BinOp::DoubleLessThan(_) => BinaryOperator::LeftShift,
+            ast::BinOp::DoubleGreaterThan(_) => BinaryOperator::RightShift,
             _ => {
                 return Err(ConvertError::BinaryOperator {
                     operator: operator.to_string(),
@@ -2651,6 +2683,7 @@ impl<'a> AstConverter<'a> {
             ast::UnOp::Minus(_) => UnaryOperator::Minus,
             ast::UnOp::Not(_) => UnaryOperator::Not,
             ast::UnOp::Hash(_) => UnaryOperator::Length,
+            ast::UnOp::Tilde(_) => UnaryOperator::BitwiseNot,
             _ => {
                 return Err(ConvertError::UnaryOperator {
                     operator: operator.to_string(),
@@ -2659,6 +2692,33 @@ impl<'a> AstConverter<'a> {
         })
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn convert_attribute(
+        &self,
+        attribute: &ast::lua54::Attribute,
+    ) -> Result<LocalAttribute, ConvertError> {
+        let kind = match attribute.name().token().to_string().as_str() {
+            "const" => LocalAttributeKind::Const,
+            "close" => LocalAttributeKind::Close,
+            _ => {
+                return Err(ConvertError::LocalAttribute {
+                    attribute: attribute.to_string(),
+                })
+            }
+        };
+        let mut local_attribute = LocalAttribute::new(kind);
+        if self.hold_token_data {
+            let (opening_bracket, closing_bracket) =
+                self.extract_contained_span_tokens(attribute.brackets())?;
+            local_attribute.set_tokens(LocalAttributeTokens {
+                opening_bracket,
+                name: self.convert_token(attribute.name())?,
+                closing_bracket,
+            });
+        }
+        Ok(local_attribute)
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn convert_compound_op(
         &self,
@@ -3082,6 +3142,9 @@ pub(crate) enum ConvertError {
     UnaryOperator {
         operator: String,
     },
+    LocalAttribute {
+        attribute: String,
+    },
     InterpolatedString {
         string: String,
     },
@@ -3136,6 +3199,7 @@ impl fmt::Display for ConvertError {
             ConvertError::BinaryOperator { operator } => ("binary operator", operator),
             ConvertError::CompoundOperator { operator } => ("compound operator", operator),
             ConvertError::UnaryOperator { operator } => ("unary operator", operator),
+            ConvertError::LocalAttribute { attribute } => ("local attribute", attribute),
             ConvertError::String { string } => ("string", string),
             ConvertError::TypeInfo { type_info } => ("type", type_info),
             ConvertError::TableTypeProperty { property } => ("table type property", property),
@@ -3190,7 +3254,12 @@ fn get_binary_operator_token(
         | BinOp::Star(token)
         | BinOp::TildeEqual(token)
         | BinOp::TwoDots(token)
-        | BinOp::TwoEqual(token) => Ok(token),
+        | BinOp::TwoEqual(token)
+        | BinOp::Ampersand(token)
+        | BinOp::Pipe(token)
+        | BinOp::Tilde(token)
+        | BinOp::DoubleLessThan(token)
+        | BinOp::DoubleGreaterThan(token) => Ok(token),
         _ => Err(ConvertError::BinaryOperator {
             operator: operator.to_string(),
         }),
@@ -3203,7 +3272,7 @@ fn get_unary_operator_token(
     use ast::UnOp;
 
     match operator {
-        UnOp::Minus(token) | UnOp::Not(token) | UnOp::Hash(token) => Ok(token),
+        UnOp::Minus(token) | UnOp::Not(token) | UnOp::Hash(token) | UnOp::Tilde(token) => Ok(token),
         _ => Err(ConvertError::UnaryOperator {
             operator: operator.to_string(),
         }),
@@ -0,0 +1,152 @@
+use crate::cli::process::LuaFormat;
+use crate::cli::{CommandResult, GlobalOptions};
+
+use anstyle::Style;
+use clap::{Args, Subcommand};
+use darklua_core::generator::{
+    DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, TokenBasedLuaGenerator,
+};
+use darklua_core::{DarkluaError, Parser, Resources};
+use std::{path::Path, path::PathBuf, time::Instant};
+
+use super::error::CliError;
+
+#[derive(Debug, Args)]
+pub struct Options {
+    #[command(subcommand)]
+    command: AstCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum AstCommand {
+    /// Parse a Lua file and write its syntax tree as JSON
+    Export(ExportOptions),
+    /// Generate a Lua file from a syntax tree in JSON
+    Import(ImportOptions),
+}
+
+#[derive(Debug, Args)]
+struct ExportOptions {
+    /// Lua file to parse
+    input: PathBuf,
+    /// Path where to write the JSON file
+    output: Option<PathBuf>,
+    /// Include the tokens (with their line, column and comments) in the syntax tree
+    #[arg(long)]
+    tokens: bool,
+}
+
+#[derive(Debug, Args)]
+struct ImportOptions {
+    /// JSON file containing the syntax tree
+    input: PathBuf,
+    /// Path where to write the Lua file
+    output: Option<PathBuf>,
+    /// Choose how Lua code is formatted ('dense', 'readable' or 'retain_lines')
+    #[arg(long, default_value = "readable")]
+    format: LuaFormat,
+}
+
+pub fn run(options: &Options, _: &GlobalOptions) -> CommandResult {
+    log::debug!("running `ast`: {:?}", options);
+
+    let result = match &options.command {
+        AstCommand::Export(options) => export(options),
+        AstCommand::Import(options) => import(options),
+    };
+
+    result.map_err(|err| {
+        eprintln!("an error happened: {}", err);
+        CliError::new(1)
+    })
+}
+
+fn export(options: &ExportOptions) -> Result<(), DarkluaError> {
+    let resources = Resources::from_file_system();
+
+    let code = resources.get(&options.input).map_err(DarkluaError::from)?;
+
+    let start_time = Instant::now();
+
+    let parser = if options.tokens {
+        Parser::default().preserve_tokens()
+    } else {
+        Parser::default()
+    };
+
+    let block = parser.parse(&code).map_err(|error| {
+        DarkluaError::custom(format!(
+            "unable to parse `{}`: {}",
+            options.input.display(),
+            error
+        ))
+    })?;
+
+    let json = darklua_core::ast_to_json(&block, &code)?;
+
+    report_success("exported", &options.input, start_time);
+
+    write_output(&resources, options.output.as_deref(), &json)
+}
+
+fn import(options: &ImportOptions) -> Result<(), DarkluaError> {
+    let resources = Resources::from_file_system();
+
+    let json = resources.get(&options.input).map_err(DarkluaError::from)?;
+
+    let start_time = Instant::now();
+
+    let block = darklua_core::ast_from_json(&json)?;
+
+    let lua_code = match options.format {
+        LuaFormat::Dense => {
+            let mut generator = DenseLuaGenerator::default();
+            generator.write_block(&block);
+            generator.into_string()
+        }
+        LuaFormat::Readable => {
+            let mut generator = ReadableLuaGenerator::default();
+            generator.write_block(&block);
+            generator.into_string()
+        }
+        LuaFormat::RetainLines => {
+            // imported tokens hold their own content, so no original code is needed
+            let mut generator = TokenBasedLuaGenerator::new("");
+            generator.write_block(&block);
+            generator.into_string()
+        }
+    };
+
+    report_success("imported", &options.input, start_time);
+
+    write_output(&resources, options.output.as_deref(), &lua_code)
+}
+
+fn report_success(action: &str, input: &Path, start_time: Instant) {
+    let duration = durationfmt::to_string(start_time.elapsed());
+
+    let success_style = Style::new()
+        .fg_color(Some(anstyle::Color::Ansi(anstyle::AnsiColor::Green)))
+        .dimmed();
+    let dim_style = Style::new().dimmed();
+
+    eprintln!(
+        "{success_style}successfully {} {}{success_style:#} {dim_style}(in {}){dim_style:#}",
+        action,
+        input.display(),
+        duration
+    );
+}
+
+fn write_output(
+    resources: &Resources,
+    output: Option<&Path>,
+    content: &str,
+) -> Result<(), DarkluaError> {
+    if let Some(output) = output {
+        resources.write(output, content).map_err(DarkluaError::from)
+    } else {
+        println!("{}", content);
+        Ok(())
+    }
+}
@@ -0,0 +1,290 @@
+use crate::cli::error::CliError;
+use crate::cli::utils::{maybe_plural, report_process};
+use crate::cli::{CommandResult, GlobalOptions};
+
+use clap::Args;
+use darklua_core::generator::ReadableStyle;
+use darklua_core::{Configuration, GeneratorParameters, Resources};
+use full_moon::tokenizer::{Lexer, LexerResult, TokenType};
+use full_moon::LuaVersion;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const DEFAULT_COLUMN_SPAN: usize = 80;
+const DEFAULT_CONFIG_PATHS: [&str; 2] = [".darklua.json", ".darklua.json5"];
+
+#[derive(Debug, Args)]
+pub struct Options {
+    /// Path to the lua file or directory to format.
+    input_path: PathBuf,
+    /// List the files that are not formatted instead of writing them.
+    /// Exits with an error if any file would change.
+    #[arg(long)]
+    check: bool,
+    /// Choose a specific configuration file to read the `readable` generator options from.
+    #[arg(long, short)]
+    config: Option<PathBuf>,
+    /// The maximum number of characters that should be written on a line.
+    /// This will override the column span given by the configuration file.
+    #[arg(long)]
+    column_span: Option<usize>,
+}
+
+impl Options {
+    fn get_process_options(&self) -> Result<darklua_core::Options, CliError> {
+        let (column_span, style) = match self
+            .read_configuration()?
+            .map(|config| config.generator().clone())
+        {
+            Some(GeneratorParameters::Readable {
+                column_span, style, ..
+            }) => (column_span, style),
+            Some(_) => {
+                log::info!(
+                    "the configuration does not use the `readable` generator, formatting with the default style"
+                );
+                (DEFAULT_COLUMN_SPAN, ReadableStyle::default())
+            }
+            None => (DEFAULT_COLUMN_SPAN, ReadableStyle::default()),
+        };
+
+        let generator = GeneratorParameters::Readable {
+            column_span: self.column_span.unwrap_or(column_span),
+            source_map: false,
+            preserve_comments: true,
+            style,
+        };
+
+        Ok(darklua_core::Options::new(&self.input_path)
+            .with_configuration(Configuration::empty().with_generator(generator)))
+    }
+
+    fn read_configuration(&self) -> Result<Option<Configuration>, CliError> {
+        let config_path = if let Some(config) = self.config.as_ref() {
+            Some(config.as_path())
+        } else {
+            let mut found = DEFAULT_CONFIG_PATHS
+                .iter()
+                .map(Path::new)
+                .filter(|path| path.exists());
+
+            match (found.next(), found.next()) {
+                (Some(_), Some(_)) => {
+                    log::error!(
+                        "multiple default configuration files found ({}), use `--config` to pick one",
+                        DEFAULT_CONFIG_PATHS.join(", ")
+                    );
+                    return Err(CliError::new(1));
+                }
+                (path, _) => path,
+            }
+        };
+
+        let config_path = match config_path {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        let content = Resources::from_file_system()
+            .get(config_path)
+            .map_err(|err| {
+                log::error!(
+                    "unable to read configuration `{}`: {:?}",
+                    config_path.display(),
+                    err
+                );
+                CliError::new(1)
+            })?;
+
+        json5::from_str(&content).map(Some).map_err(|err| {
+            log::error!(
+                "invalid configuration file `{}`: {}",
+                config_path.display(),
+                err
+            );
+            CliError::new(1)
+        })
+    }
+}
+
+pub fn run(options: &Options, _global: &GlobalOptions) -> CommandResult {
+    log::debug!("running `format`: {:?}", options);
+
+    let file_system = Resources::from_file_system();
+    let resources = Resources::from_memory();
+
+    let process_start_time = Instant::now();
+
+    // the files are formatted in memory first, so that nothing is written when a
+    // comment would be lost
+    let files = copy_files(options, &file_system, &resources)?;
+
+    let result =
+        darklua_core::process(&resources, options.get_process_options()?).map_err(|err| {
+            log::error!("{}", err);
+            CliError::new(1)
+        })?;
+
+    let errors = result.collect_errors();
+
+    if !errors.is_empty() {
+        let error_count = errors.len();
+        eprintln!(
+            "{} error{} happened:",
+            error_count,
+            maybe_plural(error_count)
+        );
+
+        for error in errors {
+            eprintln!("-> {}", error);
+        }
+
+        return Err(CliError::new(1));
+    }
+
+    let success_count = result.success_count();
+    if success_count != files.len() {
+        log::error!(
+            "formatted {} file{} out of {} found in `{}`",
+            success_count,
+            maybe_plural(success_count),
+            files.len(),
+            options.input_path.display()
+        );
+        return Err(CliError::new(1));
+    }
+
+    let formatted_files: Vec<_> = files
+        .into_iter()
+        .map(|(path, content)| {
+            let formatted = resources.get(&path).ok();
+            (path, content, formatted)
+        })
+        .collect();
+
+    verify_comments(&formatted_files)?;
+
+    let unformatted: Vec<_> = formatted_files
+        .into_iter()
+        .filter_map(|(path, content, formatted)| match formatted {
+            Some(formatted) if formatted == content => None,
+            formatted => Some((path, formatted)),
+        })
+        .collect();
+
+    if options.check {
+        return check(unformatted);
+    }
+
+    for (path, formatted) in unformatted {
+        if let Some(formatted) = formatted {
+            file_system.write(&path, &formatted).map_err(|err| {
+                log::error!("unable to write `{}`: {:?}", path.display(), err);
+                CliError::new(1)
+            })?;
+        }
+    }
+
+    report_process("formatted", &result, process_start_time.elapsed()).map_err(|_| CliError::new(1))
+}
+
+fn copy_files(
+    options: &Options,
+    file_system: &Resources,
+    resources: &Resources,
+) -> Result<Vec<(PathBuf, String)>, CliError> {
+    let mut files = Vec::new();
+
+    for path in file_system.collect_work(&options.input_path) {
+        let content = file_system.get(&path).map_err(|err| {
+            log::error!("unable to read `{}`: {:?}", path.display(), err);
+            CliError::new(1)
+        })?;
+        resources.write(&path, &content).map_err(|err| {
+            log::error!("unable to copy `{}`: {:?}", path.display(), err);
+            CliError::new(1)
+        })?;
+        files.push((path, content));
+    }
+
+    Ok(files)
+}
+
+/// Fails if the formatted code of a file does not contain all the comments of the
+/// original code, so that no file is written when a comment would be lost.
+fn verify_comments(files: &[(PathBuf, String, Option<String>)]) -> CommandResult {
+    let mut lost_comments = false;
+
+    for (path, content, formatted) in files {
+        let mut formatted_comments = read_comments(formatted.as_deref().unwrap_or_default());
+
+        let missing: Vec<_> = read_comments(content)
+            .into_iter()
+            .filter(|comment| {
+                match formatted_comments
+                    .iter()
+                    .position(|formatted| formatted == comment)
+                {
+                    Some(index) => {
+                        formatted_comments.swap_remove(index);
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect();
+
+        if !missing.is_empty() {
+            lost_comments = true;
+            log::error!(
+                "unable to format `{}` without removing {} comment{}: {}",
+                path.display(),
+                missing.len(),
+                maybe_plural(missing.len()),
+                missing
+                    .iter()
+                    .map(|comment| format!("`{}`", comment))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    if lost_comments {
+        Err(CliError::new(1))
+    } else {
+        Ok(())
+    }
+}
+
+fn read_comments(code: &str) -> Vec<String> {
+    let tokens = match Lexer::new(code, LuaVersion::new()).collect() {
+        LexerResult::Ok(tokens) | LexerResult::Recovered(tokens, _) => tokens,
+        LexerResult::Fatal(_) => Vec::new(),
+    };
+
+    tokens
+        .into_iter()
+        .filter(|token| {
+            matches!(
+                token.token_type(),
+                TokenType::SingleLineComment { .. } | TokenType::MultiLineComment { .. }
+            )
+        })
+        .map(|token| token.to_string().trim_end().to_owned())
+        .collect()
+}
+
+fn check(unformatted: Vec<(PathBuf, Option<String>)>) -> CommandResult {
+    if unformatted.is_empty() {
+        println!("all files are formatted");
+        Ok(())
+    } else {
+        let count = unformatted.len();
+        println!("{} file{} would be formatted:", count, maybe_plural(count));
+        for (path, _) in unformatted {
+            println!("-> {}", path.display());
+        }
+        Err(CliError::new(1))
+    }
+}
@@ -28,7 +28,7 @@ pub fn run(options: &Options, _global: &GlobalOptions) -> CommandResult {
             Configuration::empty().with_generator(
                 options
                     .column_span
-                    .map(|column_span| GeneratorParameters::Dense { column_span })
+                    .map(GeneratorParameters::dense)
                     .unwrap_or_else(GeneratorParameters::default_dense),
             ),
         );
@@ -1,5 +1,7 @@
+pub mod ast;
 pub mod convert;
 pub mod error;
+pub mod format;
 pub mod minify;
 pub mod process;
 pub mod utils;
@@ -41,6 +43,13 @@ pub enum Command {
     Process(process::Options),
     /// Convert a data file [json, json5, yaml, toml] into a Lua file
     Convert(convert::Options),
+    /// Format lua files in place without applying any transformation
+    ///
+    /// Files are written using the readable generator. With `--check`,
+    /// darklua lists the files that would change instead of writing them.
+    Format(format::Options),
+    /// Export the syntax tree of a Lua file to JSON, or generate Lua code from it
+    Ast(ast::Options),
 }
 
 impl Command {
@@ -49,6 +58,8 @@ impl Command {
             Command::Minify(options) => minify::run(options, global_options),
             Command::Process(options) => process::run(options, global_options),
             Command::Convert(options) => convert::run(options, global_options),
+            Command::Format(options) => format::run(options, global_options),
+            Command::Ast(options) => ast::run(options, global_options),
         }
     }
 }
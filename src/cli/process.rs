@@ -29,7 +29,7 @@ pub struct Options {
 }
 
 #[derive(Debug, Copy, Clone)]
-enum LuaFormat {
+pub(crate) enum LuaFormat {
     Dense,
     Readable,
     RetainLines,
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{nodes::Block, rules::replace_referenced_tokens};
+
+use super::{DarkluaError, DarkluaResult};
+
+/// The version of the JSON schema, incremented when the serialized nodes change in a
+/// way that older documents can not be read anymore.
+const AST_JSON_VERSION: u32 = 1;
+
+/// The maximum nesting of JSON objects and arrays accepted when importing a block. Deeper
+/// blocks would overflow the stack when generating code from them or dropping them.
+const MAX_AST_JSON_DEPTH: usize = 512;
+
+#[derive(Serialize)]
+struct AstDocumentRef<'a> {
+    version: u32,
+    block: &'a Block,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AstDocument {
+    version: u32,
+    block: Block,
+}
+
+/// Serializes a block to JSON. The tokens of the block are included when the block has
+/// some (for example, when it was parsed by a parser that preserves tokens).
+/// `original_code` is the code the block was parsed from: it is used to read the content
+/// of the tokens that refer to it.
+pub fn ast_to_json(block: &Block, original_code: &str) -> DarkluaResult<String> {
+    let mut block = block.clone();
+    replace_referenced_tokens(&mut block, original_code);
+
+    serde_json::to_string(&AstDocumentRef {
+        version: AST_JSON_VERSION,
+        block: &block,
+    })
+    .map_err(|err| DarkluaError::serialization(err.to_string(), "json"))
+}
+
+/// Deserializes a block from JSON produced by [`ast_to_json`](crate::ast_to_json).
+pub fn ast_from_json(json: &str) -> DarkluaResult<Block> {
+    verify_json_depth(json)?;
+
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    // the default limit of serde_json is too low for nested expressions, so the depth is
+    // verified beforehand and the stack grows on the heap while deserializing
+    deserializer.disable_recursion_limit();
+
+    let document = AstDocument::deserialize(serde_stacker::Deserializer::new(&mut deserializer))?;
+    deserializer.end()?;
+
+    if document.version != AST_JSON_VERSION {
+        return Err(DarkluaError::deserialization(
+            format!(
+                "unsupported AST version `{}` (expected `{}`)",
+                document.version, AST_JSON_VERSION
+            ),
+            "json",
+        ));
+    }
+
+    Ok(document.block)
+}
+
+fn verify_json_depth(json: &str) -> DarkluaResult<()> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, byte) in json.bytes().enumerate() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > MAX_AST_JSON_DEPTH {
+                    return Err(DarkluaError::deserialization(
+                        format!(
+                            "AST is nested too deeply (more than {} levels at byte {})",
+                            MAX_AST_JSON_DEPTH, index
+                        ),
+                        "json",
+                    ));
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{nodes::ReturnStatement, Parser};
+
+    #[test]
+    fn round_trip_block_without_tokens() {
+        let code = "local a = 1 return a + 2";
+        let block = Parser::default().parse(code).unwrap();
+
+        let json = ast_to_json(&block, code).unwrap();
+
+        pretty_assertions::assert_eq!(ast_from_json(&json).unwrap(), block);
+    }
+
+    #[test]
+    fn block_without_tokens_does_not_serialize_tokens() {
+        let block = Block::default().with_last_statement(ReturnStatement::default());
+
+        pretty_assertions::assert_eq!(
+            ast_to_json(&block, "").unwrap(),
+            r#"{"version":1,"block":{"statements":[],"last_statement":{"return":{"expressions":[]}}}}"#
+        );
+    }
+
+    #[test]
+    fn round_trip_block_with_tokens() {
+        let code = "-- comment\nlocal a = 1\nreturn a";
+        let block = Parser::default().preserve_tokens().parse(code).unwrap();
+
+        let json = ast_to_json(&block, code).unwrap();
+        let mut expected = block.clone();
+        replace_referenced_tokens(&mut expected, code);
+
+        pretty_assertions::assert_eq!(ast_from_json(&json).unwrap(), expected);
+    }
+
+    #[test]
+    fn error_on_unsupported_version() {
+        let error = ast_from_json(r#"{"version":0,"block":{"statements":[]}}"#).unwrap_err();
+
+        pretty_assertions::assert_eq!(
+            error.to_string(),
+            "unable to read json data: unsupported AST version `0` (expected `1`)"
+        );
+    }
+
+    fn nested_parentheses_document(depth: usize) -> String {
+        format!(
+            r#"{{"version":1,"block":{{"statements":[],"last_statement":{{"return":{{"expressions":[{}{}{}]}}}}}}}}"#,
+            r#"{"parenthese":{"expression":"#.repeat(depth),
+            r#"{"true":null}"#,
+            "}}".repeat(depth)
+        )
+    }
+
+    #[test]
+    fn deserialize_deeply_nested_expressions() {
+        let block = ast_from_json(&nested_parentheses_document(200)).unwrap();
+
+        assert!(block.get_last_statement().is_some());
+    }
+
+    #[test]
+    fn error_on_too_deeply_nested_expressions() {
+        let error = ast_from_json(&nested_parentheses_document(200_000)).unwrap_err();
+
+        pretty_assertions::assert_eq!(
+            error.to_string(),
+            "unable to read json data: AST is nested too deeply (more than 512 levels at byte 7179)"
+        );
+    }
+
+    #[test]
+    fn nesting_inside_strings_is_ignored() {
+        let json = format!(
+            r#"{{"version":1,"block":{{"statements":[],"last_statement":{{"return":{{"expressions":[{{"string":{{"value":"{}\""}}}}]}}}}}}}}"#,
+            "{[".repeat(1000)
+        );
+
+        assert!(ast_from_json(&json).is_ok());
+    }
+
+    #[test]
+    fn error_on_unknown_statement() {
+        let error =
+            ast_from_json(r#"{"version":1,"block":{"statements":[{"oops":{}}]}}"#).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("unable to read json data: unknown variant `oops`"));
+    }
+}
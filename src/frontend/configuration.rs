@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -7,13 +7,16 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    generator::{DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, TokenBasedLuaGenerator},
+    generator::{
+        DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, ReadableStyle,
+        TokenBasedLuaGenerator, TokenMapping,
+    },
     nodes::Block,
     rules::{
-        bundle::{BundleRequireMode, Bundler},
+        bundle::{BundleRequireMode, Bundler, ModuleInitialization, ResourceLoader, SharedChunk},
         get_default_rules, Rule,
     },
-    Parser,
+    LuaVersion, Parser,
 };
 
 const DEFAULT_COLUMN_SPAN: usize = 80;
@@ -31,6 +34,10 @@ pub struct Configuration {
     generator: GeneratorParameters,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     bundle: Option<BundleConfiguration>,
+    #[serde(default)]
+    lua_version: LuaVersion,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target: Option<LuaVersion>,
     #[serde(default, skip)]
     location: Option<PathBuf>,
 }
@@ -43,6 +50,8 @@ impl Configuration {
             rules: Vec::new(),
             generator: GeneratorParameters::default(),
             bundle: None,
+            lua_version: LuaVersion::default(),
+            target: None,
             location: None,
         }
     }
@@ -58,6 +67,33 @@ impl Configuration {
         self.generator = generator;
     }
 
+    #[inline]
+    pub fn generator(&self) -> &GeneratorParameters {
+        &self.generator
+    }
+
+    #[inline]
+    pub fn with_lua_version(mut self, lua_version: LuaVersion) -> Self {
+        self.lua_version = lua_version;
+        self
+    }
+
+    #[inline]
+    pub fn set_lua_version(&mut self, lua_version: LuaVersion) {
+        self.lua_version = lua_version;
+    }
+
+    #[inline]
+    pub fn with_target(mut self, target: LuaVersion) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    #[inline]
+    pub fn set_target(&mut self, target: LuaVersion) {
+        self.target = Some(target);
+    }
+
     #[inline]
     pub fn with_rule(mut self, rule: impl Into<Box<dyn Rule>>) -> Self {
         self.push_rule(rule);
@@ -88,7 +124,23 @@ impl Configuration {
 
     #[inline]
     pub(crate) fn build_parser(&self) -> Parser {
-        self.generator.build_parser()
+        let parser = self
+            .generator
+            .build_parser()
+            .with_lua_version(self.lua_version);
+
+        if self.target.is_some() || self.rules().any(Rule::require_tokens) {
+            // tokens are needed to report the line of constructs that
+            // are not supported by the target and by rules that read comments
+            parser.preserve_tokens()
+        } else {
+            parser
+        }
+    }
+
+    #[inline]
+    pub(crate) fn target(&self) -> Option<LuaVersion> {
+        self.target
     }
 
     #[inline]
@@ -96,6 +148,17 @@ impl Configuration {
         self.generator.generate_lua(block, code)
     }
 
+    /// Generates the code and the token mappings needed to write a source map, when the
+    /// generator has the `source_map` option enabled.
+    #[inline]
+    pub(crate) fn generate_lua_with_token_mappings(
+        &self,
+        block: &Block,
+        code: &str,
+    ) -> (String, Option<Vec<TokenMapping>>) {
+        self.generator.generate_lua_with_token_mappings(block, code)
+    }
+
     pub(crate) fn bundle(&self) -> Option<Bundler> {
         if let Some(bundle_config) = self.bundle.as_ref() {
             let bundler = Bundler::new(
@@ -103,7 +166,24 @@ impl Configuration {
                 bundle_config.require_mode().clone(),
                 bundle_config.excludes(),
             )
-            .with_modules_identifier(bundle_config.modules_identifier());
+            .with_modules_identifier(bundle_config.modules_identifier())
+            .with_tree_shake(bundle_config.tree_shake())
+            .with_initialization(bundle_config.initialization())
+            .with_allow_cycles(bundle_config.allow_cycles())
+            .with_manifest(bundle_config.manifest().map(Path::to_path_buf))
+            .with_loaders(bundle_config.loaders())
+            .with_deduplicate(bundle_config.deduplicate())
+            .with_shared_chunk(bundle_config.shared_chunk().map(|shared_chunk| {
+                shared_chunk.resolve(self.location().unwrap_or_else(|| Path::new("")))
+            }))
+            .with_file_rules(
+                self.rules
+                    .iter()
+                    .map(AsRef::as_ref)
+                    .filter(|rule| rule.applies_before_bundling())
+                    .filter_map(copy_rule)
+                    .collect(),
+            );
             Some(bundler)
         } else {
             None
@@ -121,12 +201,29 @@ impl Configuration {
     }
 }
 
+/// Copies a rule from its serialized properties, so that the bundler can own the rules it
+/// applies to the inlined files.
+fn copy_rule(rule: &(dyn Rule + 'static)) -> Option<Box<dyn Rule>> {
+    serde_json::to_value(rule)
+        .and_then(serde_json::from_value)
+        .map_err(|err| {
+            log::warn!(
+                "unable to apply rule `{}` to the bundled files: {}",
+                rule.get_name(),
+                err
+            );
+        })
+        .ok()
+}
+
 impl Default for Configuration {
     fn default() -> Self {
         Self {
             rules: get_default_rules(),
             generator: Default::default(),
             bundle: None,
+            lua_version: LuaVersion::default(),
+            target: None,
             location: None,
         }
     }
@@ -136,6 +233,8 @@ impl std::fmt::Debug for Configuration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Config")
             .field("generator", &self.generator)
+            .field("lua_version", &self.lua_version)
+            .field("target", &self.target)
             .field(
                 "rules",
                 &self
@@ -161,10 +260,18 @@ pub enum GeneratorParameters {
     Dense {
         #[serde(default = "get_default_column_span")]
         column_span: usize,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        source_map: bool,
     },
     Readable {
         #[serde(default = "get_default_column_span")]
         column_span: usize,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        source_map: bool,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        preserve_comments: bool,
+        #[serde(default, skip_serializing_if = "ReadableStyle::is_default")]
+        style: ReadableStyle,
     },
 }
 
@@ -176,14 +283,54 @@ impl Default for GeneratorParameters {
 
 impl GeneratorParameters {
     pub fn default_dense() -> Self {
+        Self::dense(DEFAULT_COLUMN_SPAN)
+    }
+
+    /// Creates the parameters of the `dense` generator with the given column span.
+    pub fn dense(column_span: usize) -> Self {
         Self::Dense {
-            column_span: DEFAULT_COLUMN_SPAN,
+            column_span,
+            source_map: false,
         }
     }
 
     pub fn default_readable() -> Self {
+        Self::readable(DEFAULT_COLUMN_SPAN)
+    }
+
+    /// Creates the parameters of the `readable` generator with the given column span.
+    pub fn readable(column_span: usize) -> Self {
         Self::Readable {
-            column_span: DEFAULT_COLUMN_SPAN,
+            column_span,
+            source_map: false,
+            preserve_comments: false,
+            style: ReadableStyle::default(),
+        }
+    }
+
+    /// Writes a source map next to the generated code. The `retain_lines` generator
+    /// does not write source maps, so it is not changed.
+    pub fn with_source_map(mut self, enabled: bool) -> Self {
+        match &mut self {
+            Self::RetainLines => {}
+            Self::Dense { source_map, .. } | Self::Readable { source_map, .. } => {
+                *source_map = enabled;
+            }
+        }
+        self
+    }
+
+    fn readable_generator(
+        column_span: usize,
+        preserve_comments: bool,
+        style: ReadableStyle,
+        code: &str,
+    ) -> ReadableLuaGenerator {
+        let generator = ReadableLuaGenerator::new(column_span).with_style(style);
+        if preserve_comments {
+            generator.with_comments(code)
+        } else {
+            generator
         }
     }
 
@@ -194,22 +341,72 @@ impl GeneratorParameters {
                 generator.write_block(block);
                 generator.into_string()
             }
-            Self::Dense { column_span } => {
+            Self::Dense { column_span, .. } => {
                 let mut generator = DenseLuaGenerator::new(*column_span);
                 generator.write_block(block);
                 generator.into_string()
             }
-            Self::Readable { column_span } => {
-                let mut generator = ReadableLuaGenerator::new(*column_span);
+            Self::Readable {
+                column_span,
+                preserve_comments,
+                style,
+                ..
+            } => {
+                let mut generator =
+                    Self::readable_generator(*column_span, *preserve_comments, *style, code);
                 generator.write_block(block);
                 generator.into_string()
             }
         }
     }
 
+    fn generate_lua_with_token_mappings(
+        &self,
+        block: &Block,
+        code: &str,
+    ) -> (String, Option<Vec<TokenMapping>>) {
+        match self {
+            Self::Dense {
+                column_span,
+                source_map: true,
+            } => {
+                let mut generator = DenseLuaGenerator::new(*column_span).with_token_mappings();
+                generator.write_block(block);
+                let (lua_code, token_mappings) = generator.into_string_and_token_mappings(code);
+                (lua_code, Some(token_mappings))
+            }
+            Self::Readable {
+                column_span,
+                source_map: true,
+                preserve_comments,
+                style,
+            } => {
+                let mut generator =
+                    Self::readable_generator(*column_span, *preserve_comments, *style, code)
+                        .with_token_mappings();
+                generator.write_block(block);
+                let (lua_code, token_mappings) = generator.into_string_and_token_mappings(code);
+                (lua_code, Some(token_mappings))
+            }
+            _ => (self.generate_lua(block, code), None),
+        }
+    }
+
     fn build_parser(&self) -> Parser {
         match self {
             Self::RetainLines => Parser::default().preserve_tokens(),
+            // source maps are built from the line and column of each token
+            Self::Dense {
+                source_map: true, ..
+            }
+            | Self::Readable {
+                source_map: true, ..
+            } => Parser::default().preserve_tokens(),
+            // comments are read from the tokens
+            Self::Readable {
+                preserve_comments: true,
+                ..
+            } => Parser::default().preserve_tokens(),
             Self::Dense { .. } | Self::Readable { .. } => Parser::default(),
         }
     }
@@ -222,12 +419,8 @@ impl FromStr for GeneratorParameters {
         Ok(match s {
             // keep "retain-lines" for back-compatibility
             "retain_lines" | "retain-lines" => Self::RetainLines,
-            "dense" => Self::Dense {
-                column_span: DEFAULT_COLUMN_SPAN,
-            },
-            "readable" => Self::Readable {
-                column_span: DEFAULT_COLUMN_SPAN,
-            },
+            "dense" => Self::default_dense(),
+            "readable" => Self::default_readable(),
             _ => return Err(format!("invalid generator name `{}`", s)),
         })
     }
@@ -242,6 +435,20 @@ pub struct BundleConfiguration {
     modules_identifier: Option<String>,
     #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     excludes: HashSet<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    tree_shake: bool,
+    #[serde(default, skip_serializing_if = "ModuleInitialization::is_default")]
+    initialization: ModuleInitialization,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    allow_cycles: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manifest: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    loaders: BTreeMap<String, ResourceLoader>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    deduplicate: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shared_chunk: Option<SharedChunk>,
 }
 
 impl BundleConfiguration {
@@ -250,6 +457,13 @@ impl BundleConfiguration {
             require_mode: require_mode.into(),
             modules_identifier: None,
             excludes: Default::default(),
+            tree_shake: false,
+            initialization: ModuleInitialization::default(),
+            allow_cycles: false,
+            manifest: None,
+            loaders: Default::default(),
+            deduplicate: false,
+            shared_chunk: None,
         }
     }
 
@@ -263,6 +477,41 @@ impl BundleConfiguration {
         self
     }
 
+    pub fn with_tree_shake(mut self, tree_shake: bool) -> Self {
+        self.tree_shake = tree_shake;
+        self
+    }
+
+    pub fn with_initialization(mut self, initialization: ModuleInitialization) -> Self {
+        self.initialization = initialization;
+        self
+    }
+
+    pub fn with_allow_cycles(mut self, allow_cycles: bool) -> Self {
+        self.allow_cycles = allow_cycles;
+        self
+    }
+
+    pub fn with_manifest(mut self, manifest: impl Into<PathBuf>) -> Self {
+        self.manifest = Some(manifest.into());
+        self
+    }
+
+    pub fn with_loader(mut self, extension: impl Into<String>, loader: ResourceLoader) -> Self {
+        self.loaders.insert(extension.into(), loader);
+        self
+    }
+
+    pub fn with_deduplicate(mut self, deduplicate: bool) -> Self {
+        self.deduplicate = deduplicate;
+        self
+    }
+
+    pub fn with_shared_chunk(mut self, shared_chunk: SharedChunk) -> Self {
+        self.shared_chunk = Some(shared_chunk);
+        self
+    }
+
     pub(crate) fn require_mode(&self) -> &BundleRequireMode {
         &self.require_mode
     }
@@ -277,6 +526,34 @@ impl BundleConfiguration {
     pub(crate) fn excludes(&self) -> impl Iterator<Item = &str> {
         self.excludes.iter().map(AsRef::as_ref)
     }
+
+    pub(crate) fn tree_shake(&self) -> bool {
+        self.tree_shake
+    }
+
+    pub(crate) fn initialization(&self) -> ModuleInitialization {
+        self.initialization
+    }
+
+    pub(crate) fn allow_cycles(&self) -> bool {
+        self.allow_cycles
+    }
+
+    pub(crate) fn manifest(&self) -> Option<&Path> {
+        self.manifest.as_deref()
+    }
+
+    pub(crate) fn loaders(&self) -> &BTreeMap<String, ResourceLoader> {
+        &self.loaders
+    }
+
+    pub(crate) fn deduplicate(&self) -> bool {
+        self.deduplicate
+    }
+
+    pub(crate) fn shared_chunk(&self) -> Option<&SharedChunk> {
+        self.shared_chunk.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -285,6 +562,7 @@ mod test {
 
     mod generator_parameters {
         use super::*;
+        use crate::generator::{CallParentheses, IndentStyle, QuoteStyle};
 
         #[test]
         fn deserialize_retain_lines_params() {
@@ -309,7 +587,8 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Dense {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: false,
                 }
             );
         }
@@ -321,7 +600,10 @@ mod test {
 
             pretty_assertions::assert_eq!(
                 config.generator,
-                GeneratorParameters::Dense { column_span: 110 }
+                GeneratorParameters::Dense {
+                    column_span: 110,
+                    source_map: false,
+                }
             );
         }
 
@@ -333,7 +615,10 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Readable {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: false,
+                    preserve_comments: false,
+                    style: ReadableStyle::default(),
                 }
             );
         }
@@ -345,10 +630,125 @@ mod test {
 
             pretty_assertions::assert_eq!(
                 config.generator,
-                GeneratorParameters::Readable { column_span: 110 }
+                GeneratorParameters::Readable {
+                    column_span: 110,
+                    source_map: false,
+                    preserve_comments: false,
+                    style: ReadableStyle::default(),
+                }
             );
         }
 
+        #[test]
+        fn deserialize_dense_params_with_source_map() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', source_map: true } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Dense {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: true,
+                }
+            );
+        }
+
+        #[test]
+        fn dense_params_with_source_map() {
+            pretty_assertions::assert_eq!(
+                GeneratorParameters::dense(60).with_source_map(true),
+                GeneratorParameters::Dense {
+                    column_span: 60,
+                    source_map: true,
+                }
+            );
+        }
+
+        #[test]
+        fn retain_lines_params_ignore_source_map() {
+            pretty_assertions::assert_eq!(
+                GeneratorParameters::RetainLines.with_source_map(true),
+                GeneratorParameters::RetainLines
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_source_map() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'readable', source_map: true } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: true,
+                    preserve_comments: false,
+                    style: ReadableStyle::default(),
+                }
+            );
+        }
+
+        #[test]
+        fn readable_params_with_source_map() {
+            pretty_assertions::assert_eq!(
+                GeneratorParameters::readable(100).with_source_map(true),
+                GeneratorParameters::Readable {
+                    column_span: 100,
+                    source_map: true,
+                    preserve_comments: false,
+                    style: ReadableStyle::default(),
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_preserve_comments() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'readable', preserve_comments: true } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: false,
+                    preserve_comments: true,
+                    style: ReadableStyle::default(),
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_style() {
+            let config: Configuration = json5::from_str(
+                "{ generator: { name: 'readable', style: { indent_style: 'tabs', quote_style: 'double', trailing_commas: false, call_parentheses: 'always', function_blank_lines: 2 } } }",
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: false,
+                    preserve_comments: false,
+                    style: ReadableStyle::default()
+                        .with_indent_style(IndentStyle::Tabs)
+                        .with_quote_style(QuoteStyle::Double)
+                        .with_trailing_commas(false)
+                        .with_call_parentheses(CallParentheses::Always)
+                        .with_function_blank_lines(2),
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_unknown_style_field() {
+            let result: Result<Configuration, _> =
+                json5::from_str("{ generator: { name: 'readable', style: { indent: 2 } } }");
+
+            assert!(result.is_err());
+        }
+
         #[test]
         fn deserialize_retain_lines_params_as_string() {
             let config: Configuration = json5::from_str("{generator: 'retain_lines'}").unwrap();
@@ -363,7 +763,8 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Dense {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: false,
                 }
             );
         }
@@ -375,7 +776,10 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Readable {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    source_map: false,
+                    preserve_comments: false,
+                    style: ReadableStyle::default(),
                 }
             );
         }
@@ -392,7 +796,10 @@ mod test {
     }
 
     mod bundle_configuration {
-        use crate::rules::require::PathRequireMode;
+        use crate::rules::{
+            require::{ModuleRequireMode, PathRequireMode},
+            RobloxRequireMode,
+        };
 
         use super::*;
 
@@ -472,6 +879,157 @@ mod test {
             );
         }
 
+        #[test]
+        fn deserialize_roblox_require_mode_as_string() {
+            let config: Configuration =
+                json5::from_str("{ bundle: { require_mode: 'roblox' } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(RobloxRequireMode::default())
+            );
+        }
+
+        #[test]
+        fn deserialize_roblox_require_mode_with_sourcemap() {
+            let config: Configuration = json5::from_str(
+                "{ bundle: { require_mode: { name: 'roblox', rojo_sourcemap: './sourcemap.json' } } }",
+            )
+            .unwrap();
+
+            assert!(matches!(
+                config.bundle.unwrap().require_mode(),
+                BundleRequireMode::Roblox(_)
+            ));
+        }
+
+        #[test]
+        fn deserialize_module_require_mode_as_string() {
+            let config: Configuration =
+                json5::from_str("{ bundle: { require_mode: 'module' } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(ModuleRequireMode::default())
+            );
+        }
+
+        #[test]
+        fn deserialize_module_require_mode_with_package_path() {
+            let config: Configuration = json5::from_str(
+                "{ bundle: { require_mode: { name: 'module', package_path: ['./lib/?.lua'] } } }",
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(ModuleRequireMode::new(vec!["./lib/?.lua"]))
+            );
+        }
+
+        #[test]
+        fn deserialize_tree_shake() {
+            let config: Configuration =
+                json5::from_str("{ bundle: { require_mode: 'path', tree_shake: true } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(PathRequireMode::default()).with_tree_shake(true)
+            );
+        }
+
+        #[test]
+        fn deserialize_eager_initialization() {
+            let config: Configuration =
+                json5::from_str("{ bundle: { require_mode: 'path', initialization: 'eager' } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(PathRequireMode::default())
+                    .with_initialization(ModuleInitialization::Eager)
+            );
+        }
+
+        #[test]
+        fn deserialize_allow_cycles() {
+            let config: Configuration =
+                json5::from_str("{ bundle: { require_mode: 'path', allow_cycles: true } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(PathRequireMode::default()).with_allow_cycles(true)
+            );
+        }
+
+        #[test]
+        fn deserialize_manifest() {
+            let config: Configuration =
+                json5::from_str("{ bundle: { require_mode: 'path', manifest: 'bundle.json' } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(PathRequireMode::default()).with_manifest("bundle.json")
+            );
+        }
+
+        #[test]
+        fn deserialize_loaders() {
+            let config: Configuration = json5::from_str(
+                "{ bundle: { require_mode: 'path', loaders: { csv: 'csv', 'luau.json': 'text' } } }",
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(PathRequireMode::default())
+                    .with_loader("csv", ResourceLoader::Csv)
+                    .with_loader("luau.json", ResourceLoader::Text)
+            );
+        }
+
+        #[test]
+        fn deserialize_unknown_loader() {
+            let result: Result<Configuration, _> =
+                json5::from_str("{ bundle: { require_mode: 'path', loaders: { png: 'image' } } }");
+
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn deserialize_deduplicate() {
+            let config: Configuration =
+                json5::from_str("{ bundle: { require_mode: 'path', deduplicate: true } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(PathRequireMode::default()).with_deduplicate(true)
+            );
+        }
+
+        #[test]
+        fn deserialize_shared_chunk() {
+            let config: Configuration = json5::from_str(
+                r#"{ bundle: { require_mode: 'path', shared_chunk: {
+                    entries: ['src/client.lua', 'src/server.lua'],
+                    output: 'build/shared.lua',
+                    require: 'require("./shared")',
+                } } }"#,
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.bundle.unwrap(),
+                BundleConfiguration::new(PathRequireMode::default()).with_shared_chunk(
+                    SharedChunk::new("build/shared.lua", "require(\"./shared\")")
+                        .with_entry("src/client.lua")
+                        .with_entry("src/server.lua")
+                )
+            );
+        }
+
         #[test]
         fn deserialize_unknown_require_mode_name() {
             let result: Result<Configuration, _> =
@@ -7,7 +7,11 @@ use std::{
     path::PathBuf,
 };
 
-use crate::{process::LuaSerializerError, rules::Rule, ParserError};
+use crate::{
+    process::{LuaSerializerError, TargetViolation},
+    rules::Rule,
+    LuaVersion, ParserError,
+};
 
 use super::{
     resources::ResourceError,
@@ -45,6 +49,11 @@ enum ErrorKind {
     CyclicWork {
         work: Vec<(WorkData, Vec<PathBuf>)>,
     },
+    UnsupportedTarget {
+        path: PathBuf,
+        target: LuaVersion,
+        violations: Vec<TargetViolation>,
+    },
     Deserialization {
         message: String,
         data_type: &'static str,
@@ -192,6 +201,18 @@ impl DarkluaError {
         })
     }
 
+    pub(crate) fn unsupported_target(
+        path: impl Into<PathBuf>,
+        target: LuaVersion,
+        violations: Vec<TargetViolation>,
+    ) -> Self {
+        Self::new(ErrorKind::UnsupportedTarget {
+            path: path.into(),
+            target,
+            violations,
+        })
+    }
+
     pub(crate) fn invalid_resource_path(
         path: impl Into<String>,
         message: impl Into<String>,
@@ -208,6 +229,20 @@ impl DarkluaError {
         })
     }
 
+    pub(crate) fn deserialization(message: impl Into<String>, data_type: &'static str) -> Self {
+        Self::new(ErrorKind::Deserialization {
+            message: message.into(),
+            data_type,
+        })
+    }
+
+    pub(crate) fn serialization(message: impl Into<String>, data_type: &'static str) -> Self {
+        Self::new(ErrorKind::Serialization {
+            message: message.into(),
+            data_type,
+        })
+    }
+
     pub(crate) fn os_string_conversion(os_string: impl Into<OsString>) -> Self {
         Self::new(ErrorKind::OsStringConversion {
             os_string: os_string.into(),
@@ -374,6 +409,24 @@ impl Display for DarkluaError {
                     }
                 )?;
             }
+            ErrorKind::UnsupportedTarget {
+                path,
+                target,
+                violations,
+            } => {
+                let list: Vec<_> = violations
+                    .iter()
+                    .map(|violation| format!("    {}", violation))
+                    .collect();
+
+                write!(
+                    f,
+                    "unable to generate `{}` for target `{}`:\n{}",
+                    path.display(),
+                    target,
+                    list.join("\n")
+                )?;
+            }
             ErrorKind::Deserialization { message, data_type } => {
                 write!(f, "unable to read {} data: {}", data_type, message)?;
             }
@@ -1,18 +1,22 @@
+mod ast_json;
 mod configuration;
 mod error;
 mod options;
 mod resources;
+mod source_map;
 mod utils;
 mod work_cache;
 mod work_item;
 mod worker;
 mod worker_tree;
 
+pub use ast_json::{ast_from_json, ast_to_json};
 pub use configuration::{BundleConfiguration, Configuration, GeneratorParameters};
 pub use error::{DarkluaError, DarkluaResult};
 pub use options::Options;
 pub use resources::Resources;
 use serde::Serialize;
+pub(crate) use source_map::SourceRange;
 use work_item::WorkItem;
 use worker::Worker;
 pub use worker_tree::WorkerTree;
@@ -32,7 +32,7 @@ impl Source {
                 let location = normalize_path(location);
 
                 data.iter()
-                    .any(|(path, _content)| path != &location && path.starts_with(&location))
+                    .any(|(path, _content)| path != &location && is_within(path, &location))
             }
         };
         Ok(is_directory)
@@ -99,7 +99,7 @@ impl Source {
                 let data = data.lock().unwrap();
                 let location = normalize_path(location);
                 let mut paths: Vec<_> = data.keys().map(normalize_path).collect();
-                paths.retain(|path| path.starts_with(&location));
+                paths.retain(|path| is_within(path, &location));
 
                 Box::new(paths.into_iter())
             }
@@ -127,7 +127,7 @@ impl Source {
                 } else if self.is_directory(location)? {
                     let mut data = data.lock().unwrap();
                     let location = normalize_path(location);
-                    data.retain(|path, _| !path.starts_with(&location));
+                    data.retain(|path, _| !is_within(path, &location));
                 }
 
                 Ok(())
@@ -136,6 +136,16 @@ impl Source {
     }
 }
 
+/// Returns true if a normalized path is located under the given normalized location.
+/// The current directory (`.`) contains every relative path.
+fn is_within(path: &Path, location: &Path) -> bool {
+    if location == Path::new(".") {
+        path.is_relative() && !path.starts_with("..")
+    } else {
+        path.starts_with(location)
+    }
+}
+
 fn walk_file_system(location: PathBuf) -> impl Iterator<Item = PathBuf> {
     let mut unknown_paths = vec![location];
     let mut file_paths = Vec::new();
@@ -356,5 +366,28 @@ mod test {
                 vec![PathBuf::from("src/test.lua")]
             );
         }
+
+        #[test]
+        fn collect_work_from_current_directory_contains_created_files() {
+            let resources = new();
+            resources.write("./test.lua", ANY_CONTENT).unwrap();
+            resources.write("src/test.lua", ANY_CONTENT).unwrap();
+
+            let mut work = Vec::from_iter(resources.collect_work("."));
+            work.sort();
+
+            assert_eq!(
+                work,
+                vec![PathBuf::from("src/test.lua"), PathBuf::from("test.lua")]
+            );
+        }
+
+        #[test]
+        fn current_directory_is_a_directory() {
+            let resources = new();
+            resources.write("test.lua", ANY_CONTENT).unwrap();
+
+            assert_eq!(resources.is_directory("."), Ok(true));
+        }
     }
 }
@@ -0,0 +1,286 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::{generator::TokenMapping, utils};
+
+const BASE64_CHARACTERS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Associates a range of token lines of a bundled block to the file the tokens were
+/// parsed from. The `line_offset` is the amount of lines the tokens were shifted by
+/// when the file was inserted into the bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SourceRange {
+    path: PathBuf,
+    first_line: usize,
+    last_line: Option<usize>,
+    line_offset: usize,
+}
+
+impl SourceRange {
+    pub(crate) fn new(
+        path: impl Into<PathBuf>,
+        first_line: usize,
+        last_line: Option<usize>,
+        line_offset: usize,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            first_line,
+            last_line,
+            line_offset,
+        }
+    }
+
+    fn original_line(&self, line: usize) -> Option<usize> {
+        if line < self.first_line || self.last_line.is_some_and(|last| line > last) {
+            return None;
+        }
+        line.checked_sub(self.line_offset).filter(|line| *line > 0)
+    }
+}
+
+/// A Source Map (revision 3) of a generated file.
+#[derive(Debug, Serialize)]
+pub(crate) struct SourceMap {
+    version: u8,
+    file: String,
+    sources: Vec<String>,
+    names: Vec<String>,
+    mappings: String,
+}
+
+impl SourceMap {
+    /// Builds the source map of `output` from the token mappings of the generator. When
+    /// `source_ranges` is empty, every token is mapped to `source`.
+    pub(crate) fn new(
+        output: &Path,
+        source: &Path,
+        token_mappings: &[TokenMapping],
+        source_ranges: &[SourceRange],
+    ) -> Self {
+        let map_location = output.parent().unwrap_or_else(|| Path::new(""));
+
+        let mut sources: Vec<PathBuf> = Vec::new();
+        let mut encoder = MappingsEncoder::default();
+
+        for token_mapping in token_mappings {
+            let (path, original_line) = if source_ranges.is_empty() {
+                (source, token_mapping.original_line())
+            } else {
+                match source_ranges.iter().find_map(|range| {
+                    range
+                        .original_line(token_mapping.original_line())
+                        .map(|line| (range.path.as_path(), line))
+                }) {
+                    Some(found) => found,
+                    None => continue,
+                }
+            };
+
+            let source_index = sources
+                .iter()
+                .position(|known_path| known_path == path)
+                .unwrap_or_else(|| {
+                    sources.push(path.to_path_buf());
+                    sources.len() - 1
+                });
+
+            encoder.push_segment(
+                token_mapping.generated_line() - 1,
+                token_mapping.generated_column(),
+                source_index,
+                original_line - 1,
+                token_mapping.original_column().unwrap_or(0),
+            );
+        }
+
+        Self {
+            version: 3,
+            file: output
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            sources: sources
+                .iter()
+                .map(|path| relative_source_path(path, map_location))
+                .collect(),
+            names: Vec::new(),
+            mappings: encoder.into_string(),
+        }
+    }
+
+    /// Returns the path where the source map of the given output is written.
+    pub(crate) fn path_for(output: &Path) -> PathBuf {
+        let mut file_name = output.as_os_str().to_os_string();
+        file_name.push(".map");
+        PathBuf::from(file_name)
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("source map should serialize to json")
+    }
+}
+
+// displays the source path relative to the source map with `/` separators
+fn relative_source_path(path: &Path, map_location: &Path) -> String {
+    let path = utils::normalize_path(path);
+    let map_location = utils::normalize_path(map_location);
+    let relative_path = pathdiff::diff_paths(&path, &map_location).unwrap_or(path);
+
+    relative_path
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Debug, Default)]
+struct MappingsEncoder {
+    mappings: String,
+    current_line: usize,
+    previous_generated_column: usize,
+    previous_source: usize,
+    previous_original_line: usize,
+    previous_original_column: usize,
+    line_has_segment: bool,
+}
+
+impl MappingsEncoder {
+    fn push_segment(
+        &mut self,
+        generated_line: usize,
+        generated_column: usize,
+        source: usize,
+        original_line: usize,
+        original_column: usize,
+    ) {
+        while self.current_line < generated_line {
+            self.mappings.push(';');
+            self.current_line += 1;
+            self.previous_generated_column = 0;
+            self.line_has_segment = false;
+        }
+
+        if self.line_has_segment {
+            self.mappings.push(',');
+        }
+
+        encode_vlq(
+            &mut self.mappings,
+            generated_column as i64 - self.previous_generated_column as i64,
+        );
+        encode_vlq(
+            &mut self.mappings,
+            source as i64 - self.previous_source as i64,
+        );
+        encode_vlq(
+            &mut self.mappings,
+            original_line as i64 - self.previous_original_line as i64,
+        );
+        encode_vlq(
+            &mut self.mappings,
+            original_column as i64 - self.previous_original_column as i64,
+        );
+
+        self.previous_generated_column = generated_column;
+        self.previous_source = source;
+        self.previous_original_line = original_line;
+        self.previous_original_column = original_column;
+        self.line_has_segment = true;
+    }
+
+    fn into_string(self) -> String {
+        self.mappings
+    }
+}
+
+fn encode_vlq(output: &mut String, value: i64) {
+    let mut value = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+
+    loop {
+        let mut digit = value & 0b11111;
+        value >>= 5;
+        if value > 0 {
+            digit |= 0b100000;
+        }
+        output.push(BASE64_CHARACTERS[digit as usize] as char);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode(value: i64) -> String {
+        let mut output = String::new();
+        encode_vlq(&mut output, value);
+        output
+    }
+
+    #[test]
+    fn encode_vlq_zero() {
+        assert_eq!(encode(0), "A");
+    }
+
+    #[test]
+    fn encode_vlq_one() {
+        assert_eq!(encode(1), "C");
+    }
+
+    #[test]
+    fn encode_vlq_negative_one() {
+        assert_eq!(encode(-1), "D");
+    }
+
+    #[test]
+    fn encode_vlq_fifteen() {
+        assert_eq!(encode(15), "e");
+    }
+
+    #[test]
+    fn encode_vlq_sixteen() {
+        assert_eq!(encode(16), "gB");
+    }
+
+    #[test]
+    fn encode_vlq_large_negative() {
+        assert_eq!(encode(-1000), "x+B");
+    }
+
+    #[test]
+    fn encoder_separates_lines_and_segments() {
+        let mut encoder = MappingsEncoder::default();
+        encoder.push_segment(0, 0, 0, 0, 0);
+        encoder.push_segment(0, 6, 0, 0, 6);
+        encoder.push_segment(2, 2, 1, 3, 4);
+
+        assert_eq!(encoder.into_string(), "AAAA,MAAM;;ECGF");
+    }
+
+    #[test]
+    fn source_range_maps_line_within_range() {
+        let range = SourceRange::new("module.lua", 3, Some(5), 2);
+
+        assert_eq!(range.original_line(2), None);
+        assert_eq!(range.original_line(3), Some(1));
+        assert_eq!(range.original_line(5), Some(3));
+        assert_eq!(range.original_line(6), None);
+    }
+
+    #[test]
+    fn source_range_without_last_line() {
+        let range = SourceRange::new("main.lua", 10, None, 9);
+
+        assert_eq!(range.original_line(100), Some(91));
+    }
+}
@@ -3,9 +3,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{nodes::Block, utils::Timer};
+use crate::{nodes::Block, rules::ignore_directives::IgnoreDirectives, utils::Timer};
 
-use super::{DarkluaError, DarkluaResult};
+use super::{DarkluaError, DarkluaResult, SourceRange};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct Progress {
@@ -54,6 +54,8 @@ impl Progress {
 pub(crate) struct WorkProgress {
     pub(crate) content: String,
     pub(crate) progress: Progress,
+    pub(crate) ignore_directives: IgnoreDirectives,
+    pub(crate) source_ranges: Vec<SourceRange>,
 }
 
 impl WorkProgress {
@@ -61,9 +63,21 @@ impl WorkProgress {
         Self {
             content,
             progress: Progress::new(block),
+            ignore_directives: IgnoreDirectives::default(),
+            source_ranges: Vec::new(),
         }
     }
 
+    pub(crate) fn with_ignore_directives(mut self, ignore_directives: IgnoreDirectives) -> Self {
+        self.ignore_directives = ignore_directives;
+        self
+    }
+
+    pub(crate) fn with_source_ranges(mut self, source_ranges: Vec<SourceRange>) -> Self {
+        self.source_ranges = source_ranges;
+        self
+    }
+
     pub(crate) fn required_content(&self) -> impl Iterator<Item = &Path> {
         self.progress.required.iter().map(AsRef::as_ref)
     }
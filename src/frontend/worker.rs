@@ -3,6 +3,7 @@ use std::path::Path;
 use super::{
     configuration::Configuration,
     resources::Resources,
+    source_map::{SourceMap, SourceRange},
     utils::maybe_plural,
     work_cache::WorkCache,
     work_item::{WorkItem, WorkProgress, WorkStatus},
@@ -11,7 +12,13 @@ use super::{
 
 use crate::{
     nodes::Block,
-    rules::{bundle::Bundler, ContextBuilder, Rule, RuleConfiguration},
+    process::TargetValidator,
+    rules::{
+        bundle::Bundler,
+        get_all_rule_names,
+        ignore_directives::{may_contain_ignore_directives, DetachedStatements, IgnoreDirectives},
+        ContextBuilder, Rule, RuleConfiguration,
+    },
     utils::{normalize_path, Timer},
     GeneratorParameters,
 };
@@ -93,9 +100,9 @@ impl<'a> Worker<'a> {
                 "override with {} generator",
                 match generator {
                     GeneratorParameters::RetainLines => "`retain_lines`".to_owned(),
-                    GeneratorParameters::Dense { column_span } =>
+                    GeneratorParameters::Dense { column_span, .. } =>
                         format!("dense ({})", column_span),
-                    GeneratorParameters::Readable { column_span } =>
+                    GeneratorParameters::Readable { column_span, .. } =>
                         format!("readable ({})", column_span),
                 }
             );
@@ -127,7 +134,14 @@ impl<'a> Worker<'a> {
 
                 let content = self.resources.get(work_item.source())?;
 
-                let parser = self.configuration.build_parser();
+                let has_ignore_directives = may_contain_ignore_directives(&content);
+
+                let parser = if has_ignore_directives {
+                    // ignore directives are read from the comments
+                    self.configuration.build_parser().preserve_tokens()
+                } else {
+                    self.configuration.build_parser()
+                };
 
                 log::debug!("beginning work on `{}`", source_display);
 
@@ -140,9 +154,18 @@ impl<'a> Worker<'a> {
                 let parser_time = parser_timer.duration_label();
                 log::debug!("parsed `{}` in {}", source_display, parser_time);
 
-                self.bundle(work_item, &mut block, &content)?;
+                let ignore_directives = if has_ignore_directives {
+                    self.read_ignore_directives(work_item.source(), &mut block, &content)
+                } else {
+                    IgnoreDirectives::default()
+                };
 
-                work_item.status = WorkProgress::new(content, block).into();
+                let source_ranges = self.bundle(work_item, &mut block, &content)?;
+
+                work_item.status = WorkProgress::new(content, block)
+                    .with_ignore_directives(ignore_directives)
+                    .with_source_ranges(source_ranges)
+                    .into();
 
                 self.apply_rules(work_item)
             }
@@ -170,6 +193,32 @@ impl<'a> Worker<'a> {
             })
     }
 
+    fn read_ignore_directives(
+        &self,
+        source: &Path,
+        block: &mut Block,
+        content: &str,
+    ) -> IgnoreDirectives {
+        let ignore_directives = IgnoreDirectives::from_block(block, content);
+
+        if !ignore_directives.is_empty() {
+            log::trace!("found ignore directives in `{}`", source.display());
+        }
+
+        let rule_names = get_all_rule_names();
+        for rule_name in ignore_directives.iter_rule_names() {
+            if !rule_names.contains(&rule_name) {
+                log::warn!(
+                    "unknown rule `{}` in ignore directive of `{}`",
+                    rule_name,
+                    source.display()
+                );
+            }
+        }
+
+        ignore_directives
+    }
+
     fn apply_rules(&mut self, work_item: &mut WorkItem) -> DarkluaResult<()> {
         let work_progress = match &mut work_item.status {
             WorkStatus::InProgress(progress) => progress.as_mut(),
@@ -181,6 +230,14 @@ impl<'a> Worker<'a> {
         let source_display = work_item.data.source().display();
         let normalized_source = normalize_path(work_item.data.source());
 
+        let relative_source = self
+            .configuration
+            .location()
+            .map(normalize_path)
+            .and_then(|location| normalized_source.strip_prefix(location).ok())
+            .unwrap_or(&normalized_source)
+            .to_path_buf();
+
         progress.duration().start();
 
         for (index, rule) in self
@@ -189,8 +246,21 @@ impl<'a> Worker<'a> {
             .enumerate()
             .skip(progress.next_rule())
         {
-            let mut context_builder =
-                self.create_rule_context(work_item.data.source(), &work_progress.content);
+            if !rule.applies_to(&relative_source) {
+                log::trace!(
+                    "[{}] skip rule `{}` (path is filtered out)",
+                    source_display,
+                    rule.get_name(),
+                );
+                continue;
+            }
+
+            let excluded_lines = work_progress
+                .ignore_directives
+                .excluded_lines(rule.get_name());
+            let mut context_builder = self
+                .create_rule_context(work_item.data.source(), &work_progress.content)
+                .with_excluded_lines(excluded_lines.clone());
             log::trace!(
                 "[{}] apply rule `{}`{}",
                 source_display,
@@ -260,6 +330,14 @@ impl<'a> Worker<'a> {
 
             let source = work_item.data.source();
 
+            let detached_statements = if excluded_lines.is_empty() || rule.handles_excluded_lines()
+            {
+                None
+            } else {
+                Some(DetachedStatements::detach(block, &excluded_lines))
+                    .filter(|detached| !detached.is_empty())
+            };
+
             let rule_result = rule.process(block, &context).map_err(|rule_error| {
                 let error = DarkluaError::rule_error(source, rule, index, rule_error);
 
@@ -273,6 +351,10 @@ impl<'a> Worker<'a> {
                 error
             });
 
+            if let Some(detached_statements) = detached_statements {
+                detached_statements.restore(block);
+            }
+
             work_item
                 .external_file_dependencies
                 .extend(context.into_dependencies());
@@ -309,11 +391,23 @@ impl<'a> Worker<'a> {
                 .write(work_item.data.output(), &format!("{:#?}", progress.block()))?;
         }
 
+        if let Some(target) = self.configuration.target() {
+            let violations = TargetValidator::new(target).validate(progress.mutate_block());
+
+            if !violations.is_empty() {
+                return Err(DarkluaError::unsupported_target(
+                    work_item.data.source(),
+                    target,
+                    violations,
+                ));
+            }
+        }
+
         let generator_timer = Timer::now();
 
-        let lua_code = self
+        let (lua_code, token_mappings) = self
             .configuration
-            .generate_lua(progress.block(), &work_progress.content);
+            .generate_lua_with_token_mappings(progress.block(), &work_progress.content);
 
         let generator_time = generator_timer.duration_label();
         log::debug!(
@@ -324,6 +418,22 @@ impl<'a> Worker<'a> {
 
         self.resources.write(work_item.data.output(), &lua_code)?;
 
+        if let Some(token_mappings) = token_mappings {
+            let output = work_item.data.output();
+            let source_map = SourceMap::new(
+                output,
+                work_item.data.source(),
+                &token_mappings,
+                &work_progress.source_ranges,
+            );
+            let source_map_path = SourceMap::path_for(output);
+
+            log::debug!("write source map to `{}`", source_map_path.display());
+
+            self.resources
+                .write(&source_map_path, &source_map.to_json())?;
+        }
+
         self.cache
             .link_source_to_output(normalized_source, work_item.data.output());
 
@@ -349,15 +459,24 @@ impl<'a> Worker<'a> {
         work_item: &mut WorkItem,
         block: &mut Block,
         original_code: &str,
-    ) -> DarkluaResult<()> {
+    ) -> DarkluaResult<Vec<SourceRange>> {
         if self.cached_bundler.is_none() {
             if let Some(bundler) = self.configuration.bundle() {
                 self.cached_bundler = Some(bundler);
             }
         }
+        if self
+            .cached_bundler
+            .as_ref()
+            .map(|bundler| bundler.needs_shared_chunk(work_item.source()))
+            .unwrap_or(false)
+        {
+            self.build_shared_chunk(work_item.source(), original_code)?;
+        }
+
         let bundler = match self.cached_bundler.as_ref() {
             Some(bundler) => bundler,
-            None => return Ok(()),
+            None => return Ok(Vec::new()),
         };
 
         log::debug!("beginning bundling from `{}`", work_item.source().display());
@@ -381,6 +500,8 @@ impl<'a> Worker<'a> {
             error
         });
 
+        let source_ranges = context.take_source_ranges();
+
         work_item
             .external_file_dependencies
             .extend(context.into_dependencies());
@@ -394,6 +515,71 @@ impl<'a> Worker<'a> {
             bundle_time
         );
 
+        Ok(source_ranges)
+    }
+
+    fn build_shared_chunk(&mut self, entry: &Path, original_code: &str) -> DarkluaResult<()> {
+        let mut bundler = match self.cached_bundler.take() {
+            Some(bundler) => bundler,
+            None => return Ok(()),
+        };
+
+        log::debug!("build shared chunk from entry `{}`", entry.display());
+
+        let shared_chunk_timer = Timer::now();
+
+        let context = self.create_rule_context(entry, original_code).build();
+        let result = bundler.build_shared_chunk(&context);
+        let output = bundler
+            .shared_chunk()
+            .map(|shared_chunk| shared_chunk.output().to_path_buf());
+
+        self.cached_bundler = Some(bundler);
+
+        let mut block = result.map_err(|err| err.context("unable to build shared chunk"))?;
+        let output = match output {
+            Some(output) => output,
+            None => return Ok(()),
+        };
+
+        let relative_output = self
+            .configuration
+            .location()
+            .map(normalize_path)
+            .and_then(|location| output.strip_prefix(location).ok())
+            .unwrap_or(&output)
+            .to_path_buf();
+
+        for (index, rule) in self.configuration.rules().enumerate() {
+            if !rule.applies_to(&relative_output) {
+                continue;
+            }
+
+            let context = self.create_rule_context(&output, "").build();
+            rule.process(&mut block, &context)
+                .map_err(|rule_error| DarkluaError::rule_error(&output, rule, index, rule_error))?;
+        }
+
+        if let Some(target) = self.configuration.target() {
+            let violations = TargetValidator::new(target).validate(&mut block);
+
+            if !violations.is_empty() {
+                return Err(DarkluaError::unsupported_target(
+                    &output, target, violations,
+                ));
+            }
+        }
+
+        let lua_code = self.configuration.generate_lua(&block, "");
+
+        self.resources.write(&output, &lua_code)?;
+
+        log::info!(
+            "built shared chunk `{}` in {}",
+            output.display(),
+            shared_chunk_timer.duration_label()
+        );
+
         Ok(())
     }
 }
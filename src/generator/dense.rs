@@ -1,5 +1,7 @@
+use crate::generator::token_mappings::{TokenMapping, TokenMappingRecorder};
 use crate::generator::{utils, LuaGenerator};
 use crate::nodes;
+use crate::utils::lines;
 
 /// This implementation of [LuaGenerator](trait.LuaGenerator.html) attempts to produce Lua code as
 /// small as possible. It is not meant to be read by humans.
@@ -9,6 +11,7 @@ pub struct DenseLuaGenerator {
     current_line_length: usize,
     output: String,
     last_push_length: usize,
+    token_mappings: Option<TokenMappingRecorder>,
 }
 
 impl DenseLuaGenerator {
@@ -20,6 +23,7 @@ impl DenseLuaGenerator {
             current_line_length: 0,
             output: String::new(),
             last_push_length: 0,
+            token_mappings: None,
         }
     }
 
@@ -36,6 +40,7 @@ impl DenseLuaGenerator {
     fn push_char(&mut self, character: char) {
         self.push_space_if_needed(character, 1);
 
+        self.record_content(character.encode_utf8(&mut [0; 4]));
         self.output.push(character);
         self.current_line_length += 1;
         self.last_push_length = 1;
@@ -48,6 +53,7 @@ impl DenseLuaGenerator {
             self.raw_push_char(character);
         } else {
             let last_push_content = self.get_last_push_str().to_owned();
+            let old_start = self.output.len().saturating_sub(self.last_push_length);
             (0..self.last_push_length).for_each(|_| {
                 self.output.pop();
             });
@@ -63,6 +69,9 @@ impl DenseLuaGenerator {
             }
 
             self.output.push('\n');
+            if let Some(token_mappings) = self.token_mappings.as_mut() {
+                token_mappings.move_output(old_start, self.output.len());
+            }
             self.output.push_str(&last_push_content);
             self.output.push(character);
             self.last_push_length += 1;
@@ -132,8 +141,45 @@ impl DenseLuaGenerator {
         self.output
     }
 
+    /// Records the original position of tokens while generating code. The mappings can
+    /// be obtained with `into_string_and_token_mappings`.
+    pub fn with_token_mappings(mut self) -> Self {
+        self.token_mappings = Some(TokenMappingRecorder::default());
+        self
+    }
+
+    /// Consumes the generator and produce the code with the mappings of the tokens to
+    /// their original position. The original code is needed to find the column of tokens
+    /// that still refer to it.
+    pub fn into_string_and_token_mappings(
+        self,
+        original_code: &str,
+    ) -> (String, Vec<TokenMapping>) {
+        let output = self.output;
+        let mappings = self
+            .token_mappings
+            .map(|recorder| recorder.into_mappings(&output, original_code))
+            .unwrap_or_default();
+        (output, mappings)
+    }
+
+    #[inline]
+    fn map_token(&mut self, token: Option<&nodes::Token>) {
+        if let Some(token_mappings) = self.token_mappings.as_mut() {
+            token_mappings.map_token(token);
+        }
+    }
+
+    #[inline]
+    fn record_content(&mut self, content: &str) {
+        if let Some(token_mappings) = self.token_mappings.as_mut() {
+            token_mappings.write_content(self.output.len(), content);
+        }
+    }
+
     #[inline]
     fn raw_push_str(&mut self, content: &str) {
+        self.record_content(content);
         self.output.push_str(content);
         self.last_push_length = content.len();
         self.current_line_length += self.last_push_length;
@@ -141,6 +187,7 @@ impl DenseLuaGenerator {
 
     #[inline]
     fn raw_push_char(&mut self, character: char) {
+        self.record_content(character.encode_utf8(&mut [0; 4]));
         self.output.push(character);
         self.last_push_length = 1;
         self.current_line_length += 1;
@@ -217,6 +264,7 @@ impl DenseLuaGenerator {
     }
 
     fn write_typed_identifier(&mut self, typed_identifier: &nodes::TypedIdentifier) {
+        self.map_token(typed_identifier.get_identifier().get_token());
         self.push_str(typed_identifier.get_name());
 
         if let Some(r#type) = typed_identifier.get_type() {
@@ -225,6 +273,12 @@ impl DenseLuaGenerator {
         }
     }
 
+    fn write_local_attribute(&mut self, attribute: &nodes::LocalAttribute) {
+        self.push_char('<');
+        self.push_str(attribute.kind().to_str());
+        self.push_char('>');
+    }
+
     fn write_function_return_type(&mut self, return_type: &nodes::FunctionReturnType) {
         match return_type {
             nodes::FunctionReturnType::Type(r#type) => self.write_type(r#type),
@@ -292,6 +346,7 @@ impl LuaGenerator for DenseLuaGenerator {
         let mut statements = block.iter_statements().peekable();
 
         while let Some(statement) = statements.next() {
+            self.map_token(lines::first_statement_token(statement));
             self.write_statement(statement);
 
             if let Some(next_statement) = statements.peek() {
@@ -304,6 +359,7 @@ impl LuaGenerator for DenseLuaGenerator {
         }
 
         if let Some(last_statement) = block.get_last_statement() {
+            self.map_token(lines::first_last_statement_token(last_statement));
             self.write_last_statement(last_statement);
         }
     }
@@ -374,6 +430,17 @@ impl LuaGenerator for DenseLuaGenerator {
         self.push_str("end");
     }
 
+    fn write_goto_statement(&mut self, goto: &nodes::GotoStatement) {
+        self.push_str("goto");
+        self.push_str(goto.get_label().get_name());
+    }
+
+    fn write_label_statement(&mut self, label: &nodes::LabelStatement) {
+        self.push_str("::");
+        self.push_str(label.get_name().get_name());
+        self.push_str("::");
+    }
+
     fn write_if_statement(&mut self, if_statement: &nodes::IfStatement) {
         let branches = if_statement.get_branches();
 
@@ -468,13 +535,21 @@ impl LuaGenerator for DenseLuaGenerator {
         let variables = assign.get_variables();
         let last_variable_index = variables.len().saturating_sub(1);
 
-        variables.iter().enumerate().for_each(|(index, variable)| {
-            self.write_typed_identifier(variable);
+        variables
+            .iter()
+            .zip(assign.iter_attributes())
+            .enumerate()
+            .for_each(|(index, (variable, attribute))| {
+                self.write_typed_identifier(variable);
 
-            if index != last_variable_index {
-                self.push_char(',');
-            }
-        });
+                if let Some(attribute) = attribute {
+                    self.write_local_attribute(attribute);
+                }
+
+                if index != last_variable_index {
+                    self.push_char(',');
+                }
+            });
 
         if assign.has_values() {
             self.push_char_and_break_if('=', utils::break_equal);
@@ -699,19 +774,23 @@ impl LuaGenerator for DenseLuaGenerator {
         self.write_type(statement.get_type());
     }
 
-    fn write_false_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_false_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str("false");
     }
 
-    fn write_true_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_true_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str("true");
     }
 
-    fn write_nil_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_nil_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str("nil");
     }
 
-    fn write_variable_arguments_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_variable_arguments_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str_and_break_if("...", utils::break_variable_arguments);
     }
 
@@ -747,6 +826,7 @@ impl LuaGenerator for DenseLuaGenerator {
             Length => self.push_char('#'),
             Minus => self.push_char_and_break_if('-', utils::break_minus),
             Not => self.push_str("not"),
+            BitwiseNot => self.push_char('~'),
         }
 
         let expression = unary.get_expression();
@@ -806,6 +886,7 @@ impl LuaGenerator for DenseLuaGenerator {
         self.push_new_line_if_needed(1);
         self.raw_push_char('.');
 
+        self.map_token(field.get_field().get_token());
         self.push_str(field.get_field().get_name());
     }
 
@@ -870,6 +951,7 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_number(&mut self, number: &nodes::NumberExpression) {
+        self.map_token(number.get_token());
         use nodes::NumberExpression::*;
 
         match number {
@@ -944,6 +1026,7 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_string(&mut self, string: &nodes::StringExpression) {
+        self.map_token(string.get_token());
         let result = utils::write_string(string.get_value());
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
@@ -980,6 +1063,7 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_identifier(&mut self, identifier: &nodes::Identifier) {
+        self.map_token(identifier.get_token());
         self.push_str(identifier.get_name());
     }
 
@@ -3,12 +3,16 @@
 
 mod dense;
 mod readable;
+mod readable_style;
 mod token_based;
-mod utils;
+mod token_mappings;
+pub(crate) mod utils;
 
 pub use dense::DenseLuaGenerator;
 pub use readable::ReadableLuaGenerator;
+pub use readable_style::{CallParentheses, IndentStyle, QuoteStyle, ReadableStyle};
 pub use token_based::TokenBasedLuaGenerator;
+pub use token_mappings::TokenMapping;
 
 use crate::nodes;
 
@@ -30,7 +34,9 @@ pub trait LuaGenerator {
             CompoundAssign(statement) => self.write_compound_assign(statement),
             Function(statement) => self.write_function_statement(statement),
             GenericFor(statement) => self.write_generic_for(statement),
+            Goto(statement) => self.write_goto_statement(statement),
             If(statement) => self.write_if_statement(statement),
+            Label(statement) => self.write_label_statement(statement),
             LocalAssign(statement) => self.write_local_assign(statement),
             LocalFunction(statement) => self.write_local_function(statement),
             TypeFunction(statement) => self.write_type_function(statement),
@@ -46,7 +52,9 @@ pub trait LuaGenerator {
     fn write_do_statement(&mut self, do_statement: &nodes::DoStatement);
     fn write_compound_assign(&mut self, assign: &nodes::CompoundAssignStatement);
     fn write_generic_for(&mut self, generic_for: &nodes::GenericForStatement);
+    fn write_goto_statement(&mut self, goto: &nodes::GotoStatement);
     fn write_if_statement(&mut self, if_statement: &nodes::IfStatement);
+    fn write_label_statement(&mut self, label: &nodes::LabelStatement);
     fn write_function_statement(&mut self, function: &nodes::FunctionStatement);
     fn write_last_statement(&mut self, statement: &nodes::LastStatement);
     fn write_local_assign(&mut self, assign: &nodes::LocalAssignStatement);
@@ -313,11 +321,20 @@ mod test {
             $generator:expr => (
                 $($name:ident => $code:literal),+,
             )
+        ) => {
+            blocks_consistency!($crate::Parser::default(), $generator => (
+                $($name => $code),+,
+            ));
+        };
+        (
+            $parser:expr, $generator:expr => (
+                $($name:ident => $code:literal),+,
+            )
         ) => {
             $(
                 #[test]
                 fn $name() {
-                    let parser = $crate::Parser::default();
+                    let parser = $parser;
 
                     let expected_block = parser.parse($code)
                         .expect(&format!("unable to parse `{}`", $code));
@@ -406,6 +423,34 @@ mod $mod_name {
         ));
     }
 
+    mod lua54 {
+        use super::*;
+
+        blocks_consistency!(
+            $crate::Parser::default().with_lua_version($crate::LuaVersion::Lua54),
+            $generator => (
+                goto_statement => "goto continue",
+                label_statement => "::continue::",
+                goto_and_label_in_loop => "for i = 1, 10 do if i == 5 then goto skip end ::skip:: end",
+                local_const => "local value <const> = 10",
+                local_close => "local file <close> = open()",
+                local_with_mixed_attributes => "local a <const>, b, c <close> = 1, 2, 3",
+                bitwise_and => "return a & b",
+                bitwise_or => "return a | b",
+                bitwise_xor => "return a ~ b",
+                left_shift => "return a << 2",
+                right_shift => "return a >> 2",
+                bitwise_not => "return ~a",
+                double_bitwise_not => "return ~ ~a",
+                bitwise_not_of_negative => "return ~-a",
+                bitwise_xor_with_bitwise_not => "return a ~ ~b",
+                mixed_bitwise_precedence => "return a | b ~ c & d << 1 .. e",
+                shift_of_concat => "return (a << b) .. c",
+                comparison_with_bitwise => "return a & b == c | d",
+            )
+        );
+    }
+
     mod numbers {
         use super::*;
 
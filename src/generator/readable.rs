@@ -1,5 +1,7 @@
-use crate::generator::{utils, LuaGenerator};
+use crate::generator::token_mappings::{TokenMapping, TokenMappingRecorder};
+use crate::generator::{utils, CallParentheses, IndentStyle, LuaGenerator, ReadableStyle};
 use crate::nodes;
+use crate::utils::lines;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum StatementType {
@@ -9,7 +11,9 @@ enum StatementType {
     CompoundAssign,
     Function,
     GenericFor,
+    Goto,
     If,
+    Label,
     LocalAssign,
     LocalFunction,
     TypeFunction,
@@ -33,7 +37,9 @@ impl From<&nodes::Statement> for StatementType {
             CompoundAssign(_) => Self::CompoundAssign,
             Function(_) => Self::Function,
             GenericFor(_) => Self::GenericFor,
+            Goto(_) => Self::Goto,
             If(_) => Self::If,
+            Label(_) => Self::Label,
             LocalAssign(_) => Self::LocalAssign,
             LocalFunction(_) => Self::LocalFunction,
             TypeFunction(_) => Self::TypeFunction,
@@ -62,11 +68,14 @@ impl From<&nodes::LastStatement> for StatementType {
 #[derive(Debug, Clone)]
 pub struct ReadableLuaGenerator {
     column_span: usize,
-    indentation: usize,
+    style: ReadableStyle,
+    block_depth: usize,
     current_line_length: usize,
     current_indentation: usize,
     output: String,
     last_push_length: usize,
+    token_mappings: Option<TokenMappingRecorder>,
+    comments_code: Option<String>,
     can_add_new_line_stack: Vec<bool>,
 }
 
@@ -74,15 +83,24 @@ impl ReadableLuaGenerator {
     pub fn new(column_span: usize) -> Self {
         Self {
             column_span,
-            indentation: 4,
+            style: ReadableStyle::default(),
+            block_depth: 0,
             current_line_length: 0,
             current_indentation: 0,
             output: String::new(),
             last_push_length: 0,
+            token_mappings: None,
+            comments_code: None,
             can_add_new_line_stack: Vec::new(),
         }
     }
 
+    /// Sets the formatting choices used to write the code.
+    pub fn with_style(mut self, style: ReadableStyle) -> Self {
+        self.style = style;
+        self
+    }
+
     #[inline]
     fn can_add_new_line(&self) -> bool {
         self.can_add_new_line_stack.last().copied().unwrap_or(true)
@@ -110,8 +128,19 @@ impl ReadableLuaGenerator {
 
     #[inline]
     fn write_indentation(&mut self) {
-        let indentation = " ".repeat(self.indentation * self.current_indentation);
-        self.raw_push_str(&indentation);
+        match self.style.indent_style() {
+            IndentStyle::Spaces => {
+                let indentation = " ".repeat(self.style.indent_width() * self.current_indentation);
+                self.raw_push_str(&indentation);
+            }
+            IndentStyle::Tabs => {
+                let indentation = "\t".repeat(self.current_indentation);
+                self.raw_push_str(&indentation);
+                // each tab counts as the indentation width in the line length
+                self.current_line_length +=
+                    self.current_indentation * self.style.indent_width().saturating_sub(1);
+            }
+        }
     }
 
     #[inline]
@@ -144,13 +173,289 @@ impl ReadableLuaGenerator {
     fn push_char(&mut self, character: char) {
         self.push_space_if_needed(character, 1);
 
+        self.record_content(character.encode_utf8(&mut [0; 4]));
         self.output.push(character);
         self.current_line_length += 1;
         self.last_push_length = 1;
     }
 
+    /// Records the original position of tokens while generating code. The mappings can
+    /// be obtained with `into_string_and_token_mappings`.
+    pub fn with_token_mappings(mut self) -> Self {
+        self.token_mappings = Some(TokenMappingRecorder::default());
+        self
+    }
+
+    /// Consumes the generator and produce the code with the mappings of the tokens to
+    /// their original position. The original code is needed to find the column of tokens
+    /// that still refer to it.
+    pub fn into_string_and_token_mappings(
+        self,
+        original_code: &str,
+    ) -> (String, Vec<TokenMapping>) {
+        let output = self.output;
+        let mappings = self
+            .token_mappings
+            .map(|recorder| recorder.into_mappings(&output, original_code))
+            .unwrap_or_default();
+        (output, mappings)
+    }
+
+    /// Writes the comments around statements, block keywords, table entries, call arguments
+    /// and function parameters. The original code is used to read the comments of tokens
+    /// that refer to it.
+    pub fn with_comments(mut self, original_code: impl Into<String>) -> Self {
+        self.comments_code = Some(original_code.into());
+        self
+    }
+
+    fn read_comments<'a>(&self, trivia: impl Iterator<Item = &'a nodes::Trivia>) -> Vec<String> {
+        match self.comments_code.as_ref() {
+            Some(code) => trivia
+                .filter(|trivia| trivia.kind() == nodes::TriviaKind::Comment)
+                .map(|trivia| trivia.read(code).trim_end().to_owned())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Writes the comments found before the given token, each on their own line.
+    fn write_leading_comments(&mut self, token: Option<&nodes::Token>) {
+        let comments = match token {
+            Some(token) => self.read_comments(token.iter_leading_trivia()),
+            None => return,
+        };
+
+        for comment in comments {
+            if self.current_line_length != 0 {
+                self.push_new_line();
+            }
+            if self.current_indentation != 0 {
+                self.write_indentation();
+            }
+            self.raw_push_str(&comment);
+            self.push_new_line();
+        }
+    }
+
+    /// Writes the comments found after the given tokens at the end of the current line.
+    fn write_trailing_comments<'a>(&mut self, tokens: impl IntoIterator<Item = &'a nodes::Token>) {
+        if self.comments_code.is_none() {
+            return;
+        }
+
+        let comments: Vec<_> = tokens
+            .into_iter()
+            .flat_map(|token| self.read_comments(token.iter_trailing_trivia()))
+            .collect();
+
+        for comment in comments {
+            self.raw_push_char(' ');
+            self.raw_push_str(&comment);
+        }
+    }
+
+    /// Returns true if comments are found after the `trailing` tokens or before the
+    /// `leading` tokens.
+    fn has_token_comments<'a>(
+        &self,
+        trailing: impl IntoIterator<Item = &'a nodes::Token>,
+        leading: impl IntoIterator<Item = &'a nodes::Token>,
+    ) -> bool {
+        if self.comments_code.is_none() {
+            return false;
+        }
+
+        let is_comment = |trivia: &nodes::Trivia| trivia.kind() == nodes::TriviaKind::Comment;
+
+        trailing
+            .into_iter()
+            .flat_map(nodes::Token::iter_trailing_trivia)
+            .chain(
+                leading
+                    .into_iter()
+                    .flat_map(nodes::Token::iter_leading_trivia),
+            )
+            .any(is_comment)
+    }
+
+    /// Returns true if the block can be written on the same line as the keywords around
+    /// it, which is only possible when there is nothing to write between them.
+    fn is_empty_block(
+        &self,
+        block: &nodes::Block,
+        opening: Option<&nodes::Token>,
+        closing: Option<&nodes::Token>,
+    ) -> bool {
+        block.is_empty() && !self.has_token_comments(opening, closing)
+    }
+
+    /// Writes a block on new lines after its opening keyword (like `then` or `do`). The
+    /// comments following the opening token and the comments preceding the closing token
+    /// are written with the block.
+    fn write_enclosed_block(
+        &mut self,
+        block: &nodes::Block,
+        opening: Option<&nodes::Token>,
+        closing: Option<&nodes::Token>,
+    ) {
+        self.write_trailing_comments(opening);
+        self.push_new_line();
+        self.push_indentation();
+        self.write_block(block);
+        self.write_leading_comments(closing);
+        self.pop_indentation();
+    }
+
+    fn write_function_block(
+        &mut self,
+        block: &nodes::Block,
+        tokens: Option<&nodes::FunctionBodyTokens>,
+        has_return_type: bool,
+    ) {
+        // when a return type is written, the comments following the parentheses are not
+        // the last ones before the block
+        let opening = tokens
+            .filter(|_| !has_return_type)
+            .map(|tokens| &tokens.closing_parenthese);
+        let closing = tokens.map(|tokens| &tokens.end);
+
+        if self.is_empty_block(block, opening, closing) {
+            self.raw_push_str(" end");
+        } else {
+            self.write_enclosed_block(block, opening, closing);
+            self.push_str("end");
+        }
+    }
+
+    /// Returns the number of blank lines to write between two top-level statements when
+    /// one of them is a function declaration and the style defines it.
+    fn function_blank_lines(
+        &self,
+        current_type: StatementType,
+        next_type: StatementType,
+    ) -> Option<usize> {
+        let is_function = |statement_type| {
+            matches!(
+                statement_type,
+                StatementType::Function | StatementType::LocalFunction
+            )
+        };
+
+        self.style
+            .function_blank_lines()
+            .filter(|_| self.block_depth == 1)
+            .filter(|_| is_function(current_type) || is_function(next_type))
+    }
+
+    fn has_arguments_comments(&self, arguments: &nodes::TupleArguments) -> bool {
+        let tokens = arguments.get_tokens();
+
+        self.has_token_comments(
+            tokens
+                .map(|tokens| &tokens.opening_parenthese)
+                .into_iter()
+                .chain(tokens.into_iter().flat_map(|tokens| tokens.commas.iter()))
+                .chain(
+                    arguments
+                        .iter_values()
+                        .filter_map(lines::last_expression_token),
+                ),
+            tokens
+                .map(|tokens| &tokens.closing_parenthese)
+                .into_iter()
+                .chain(
+                    arguments
+                        .iter_values()
+                        .filter_map(lines::first_expression_token),
+                ),
+        )
+    }
+
+    fn has_parameters_comments(
+        &self,
+        parameters: &[nodes::TypedIdentifier],
+        tokens: Option<&nodes::FunctionBodyTokens>,
+    ) -> bool {
+        self.has_token_comments(
+            tokens
+                .map(|tokens| &tokens.opening_parenthese)
+                .into_iter()
+                .chain(
+                    tokens
+                        .into_iter()
+                        .flat_map(|tokens| tokens.parameter_commas.iter()),
+                )
+                .chain(tokens.and_then(|tokens| tokens.variable_arguments.as_ref()))
+                .chain(
+                    parameters
+                        .iter()
+                        .filter_map(lines::last_typed_identifier_token),
+                ),
+            tokens
+                .map(|tokens| &tokens.closing_parenthese)
+                .into_iter()
+                .chain(tokens.and_then(|tokens| tokens.variable_arguments.as_ref()))
+                .chain(
+                    parameters
+                        .iter()
+                        .filter_map(|parameter| parameter.get_token()),
+                ),
+        )
+    }
+
+    fn has_comments(&self, table: &nodes::TableExpression) -> bool {
+        if self.comments_code.is_none() {
+            return false;
+        }
+
+        let is_comment = |trivia: &nodes::Trivia| trivia.kind() == nodes::TriviaKind::Comment;
+
+        let table_tokens_have_comments = table.get_tokens().is_some_and(|tokens| {
+            tokens
+                .opening_brace
+                .iter_trailing_trivia()
+                .chain(tokens.closing_brace.iter_leading_trivia())
+                .chain(
+                    tokens
+                        .separators
+                        .iter()
+                        .flat_map(nodes::Token::iter_trailing_trivia),
+                )
+                .any(is_comment)
+        });
+
+        table_tokens_have_comments
+            || table.get_entries().iter().any(|entry| {
+                lines::first_table_entry_token(entry)
+                    .into_iter()
+                    .flat_map(nodes::Token::iter_leading_trivia)
+                    .chain(
+                        lines::last_table_entry_token(entry)
+                            .into_iter()
+                            .flat_map(nodes::Token::iter_trailing_trivia),
+                    )
+                    .any(is_comment)
+            })
+    }
+
+    #[inline]
+    fn map_token(&mut self, token: Option<&nodes::Token>) {
+        if let Some(token_mappings) = self.token_mappings.as_mut() {
+            token_mappings.map_token(token);
+        }
+    }
+
+    #[inline]
+    fn record_content(&mut self, content: &str) {
+        if let Some(token_mappings) = self.token_mappings.as_mut() {
+            token_mappings.write_content(self.output.len(), content);
+        }
+    }
+
     #[inline]
     fn raw_push_str(&mut self, content: &str) {
+        self.record_content(content);
         self.output.push_str(content);
         self.last_push_length = content.len();
         self.current_line_length += self.last_push_length;
@@ -158,6 +463,7 @@ impl ReadableLuaGenerator {
 
     #[inline]
     fn raw_push_char(&mut self, character: char) {
+        self.record_content(character.encode_utf8(&mut [0; 4]));
         self.output.push(character);
         self.last_push_length = 1;
         self.current_line_length += 1;
@@ -172,13 +478,6 @@ impl ReadableLuaGenerator {
         }
     }
 
-    #[inline]
-    fn indent_and_write_block(&mut self, block: &nodes::Block) {
-        self.push_indentation();
-        self.write_block(block);
-        self.pop_indentation();
-    }
-
     fn push_new_line_if_needed(&mut self, pushed_length: usize) {
         if self.current_line_length == 0 && self.current_indentation != 0 {
             self.write_indentation();
@@ -281,6 +580,7 @@ impl ReadableLuaGenerator {
         parameters: &[nodes::TypedIdentifier],
         is_variadic: bool,
         variadic_type: Option<&nodes::FunctionVariadicType>,
+        tokens: Option<&nodes::FunctionBodyTokens>,
     ) {
         let mut parameters_length = parameters.iter().fold(0, |acc, parameter| {
             acc + parameter.get_name().len()
@@ -305,7 +605,9 @@ impl ReadableLuaGenerator {
 
         let last_index = parameters.len().saturating_sub(1);
 
-        if self.fits_on_current_line(parameters_length) {
+        let has_comments = self.has_parameters_comments(parameters, tokens);
+
+        if !has_comments && self.fits_on_current_line(parameters_length) {
             parameters.iter().enumerate().for_each(|(index, variable)| {
                 self.write_typed_identifier(variable);
 
@@ -329,23 +631,31 @@ impl ReadableLuaGenerator {
                 }
             };
         } else {
+            self.write_trailing_comments(tokens.map(|tokens| &tokens.opening_parenthese));
             self.push_indentation();
 
             parameters.iter().enumerate().for_each(|(index, variable)| {
                 self.push_new_line();
+                self.write_leading_comments(variable.get_token());
                 self.write_indentation();
                 self.write_typed_identifier(variable);
 
-                if index != last_index {
+                if index != last_index || is_variadic {
                     self.raw_push_char(',');
                 }
+
+                self.write_trailing_comments(
+                    lines::last_typed_identifier_token(variable)
+                        .into_iter()
+                        .chain(tokens.and_then(|tokens| tokens.parameter_commas.get(index))),
+                );
             });
 
             if is_variadic {
-                if !parameters.is_empty() {
-                    self.raw_push_char(',');
-                };
                 self.push_new_line();
+                self.write_leading_comments(
+                    tokens.and_then(|tokens| tokens.variable_arguments.as_ref()),
+                );
                 self.write_indentation();
                 self.raw_push_str("...");
 
@@ -354,10 +664,15 @@ impl ReadableLuaGenerator {
                     self.raw_push_char(' ');
                     self.write_function_variadic_type(r#variadic_type);
                 }
+
+                self.write_trailing_comments(
+                    tokens.and_then(|tokens| tokens.variable_arguments.as_ref()),
+                );
             };
 
-            self.pop_indentation();
             self.push_new_line();
+            self.write_leading_comments(tokens.map(|tokens| &tokens.closing_parenthese));
+            self.pop_indentation();
             self.write_indentation();
         }
     }
@@ -365,13 +680,17 @@ impl ReadableLuaGenerator {
     fn write_variable(&mut self, variable: &nodes::Variable) {
         use nodes::Variable::*;
         match variable {
-            Identifier(identifier) => self.push_str(identifier.get_name()),
+            Identifier(identifier) => {
+                self.map_token(identifier.get_token());
+                self.push_str(identifier.get_name());
+            }
             Field(field) => self.write_field(field),
             Index(index) => self.write_index(index),
         }
     }
 
     fn write_typed_identifier(&mut self, typed_identifier: &nodes::TypedIdentifier) {
+        self.map_token(typed_identifier.get_identifier().get_token());
         self.push_str(typed_identifier.get_name());
 
         if let Some(r#type) = typed_identifier.get_type() {
@@ -381,6 +700,12 @@ impl ReadableLuaGenerator {
         }
     }
 
+    fn write_local_attribute(&mut self, attribute: &nodes::LocalAttribute) {
+        self.raw_push_char('<');
+        self.raw_push_str(attribute.kind().to_str());
+        self.raw_push_char('>');
+    }
+
     fn write_function_return_type(&mut self, return_type: &nodes::FunctionReturnType) {
         match return_type {
             nodes::FunctionReturnType::Type(r#type) => self.write_type(r#type),
@@ -452,23 +777,48 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_block(&mut self, block: &nodes::Block) {
-        let mut statements = block.iter_statements().peekable();
+        self.block_depth += 1;
+        let mut statements = block.iter_statements().enumerate().peekable();
 
-        while let Some(statement) = statements.next() {
+        while let Some((index, statement)) = statements.next() {
+            self.write_leading_comments(lines::first_statement_token(statement));
+            self.map_token(lines::first_statement_token(statement));
             let current_type: StatementType = statement.into();
 
             self.push_can_add_new_line(false);
             self.write_statement(statement);
 
-            if let Some(next_statement) = statements.peek() {
+            let next_statement = statements.peek().map(|(_, next)| *next);
+
+            if let Some(next_statement) = next_statement {
                 if utils::starts_with_parenthese(next_statement)
                     && utils::ends_with_prefix(statement)
                 {
                     self.push_char(';');
                 }
+            }
 
-                if current_type != (*next_statement).into() {
-                    self.push_new_line();
+            self.write_trailing_comments(
+                lines::last_statement_token(statement).into_iter().chain(
+                    block
+                        .get_tokens()
+                        .and_then(|tokens| tokens.semicolons.get(index))
+                        .and_then(Option::as_ref),
+                ),
+            );
+
+            if let Some(next_statement) = next_statement {
+                match self.function_blank_lines(current_type, next_statement.into()) {
+                    Some(blank_lines) => {
+                        for _ in 0..blank_lines {
+                            self.push_new_line();
+                        }
+                    }
+                    None => {
+                        if current_type != next_statement.into() {
+                            self.push_new_line();
+                        }
+                    }
                 }
             }
 
@@ -477,12 +827,37 @@ impl LuaGenerator for ReadableLuaGenerator {
         }
 
         if let Some(last_statement) = block.get_last_statement() {
-            if block.iter_statements().next().is_some() {
-                self.push_new_line();
+            if let Some(previous_statement) = block.iter_statements().last() {
+                let blank_lines = self
+                    .function_blank_lines(previous_statement.into(), last_statement.into())
+                    .unwrap_or(1);
+                for _ in 0..blank_lines {
+                    self.push_new_line();
+                }
             }
+            self.write_leading_comments(lines::first_last_statement_token(last_statement));
+            self.map_token(lines::first_last_statement_token(last_statement));
             self.write_last_statement(last_statement);
+            self.write_trailing_comments(
+                lines::last_last_statement_token(last_statement)
+                    .into_iter()
+                    .chain(
+                        block
+                            .get_tokens()
+                            .and_then(|tokens| tokens.last_semicolon.as_ref()),
+                    ),
+            );
             self.push_new_line();
         }
+
+        if let Some(final_token) = block
+            .get_tokens()
+            .and_then(|tokens| tokens.final_token.as_ref())
+        {
+            self.write_leading_comments(Some(final_token));
+        }
+
+        self.block_depth -= 1;
     }
 
     fn write_last_statement(&mut self, statement: &nodes::LastStatement) {
@@ -554,14 +929,23 @@ impl LuaGenerator for ReadableLuaGenerator {
         let variables = assign.get_variables();
         let last_variable_index = variables.len().saturating_sub(1);
 
-        variables.iter().enumerate().for_each(|(index, variable)| {
-            self.write_typed_identifier(variable);
+        variables
+            .iter()
+            .zip(assign.iter_attributes())
+            .enumerate()
+            .for_each(|(index, (variable, attribute))| {
+                self.write_typed_identifier(variable);
 
-            if index != last_variable_index {
-                self.raw_push_char(',');
-                self.raw_push_char(' ');
-            }
-        });
+                if let Some(attribute) = attribute {
+                    self.raw_push_char(' ');
+                    self.write_local_attribute(attribute);
+                }
+
+                if index != last_variable_index {
+                    self.raw_push_char(',');
+                    self.raw_push_char(' ');
+                }
+            });
 
         if assign.has_values() {
             self.raw_push_str(" = ");
@@ -610,6 +994,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             parameters,
             function.is_variadic(),
             function.get_variadic_type(),
+            function.get_tokens().map(|tokens| &tokens.function_body),
         );
         self.raw_push_char(')');
 
@@ -617,15 +1002,11 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.write_function_return_type_suffix(return_type);
         }
 
-        let block = function.get_block();
-
-        if block.is_empty() {
-            self.raw_push_str(" end");
-        } else {
-            self.push_new_line();
-            self.indent_and_write_block(block);
-            self.push_str("end");
-        }
+        self.write_function_block(
+            function.get_block(),
+            function.get_tokens().map(|tokens| &tokens.function_body),
+            function.get_return_type().is_some(),
+        );
     }
 
     fn write_type_function(&mut self, function: &nodes::TypeFunctionStatement) {
@@ -643,6 +1024,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             parameters,
             function.is_variadic(),
             function.get_variadic_type(),
+            function.get_tokens().map(|tokens| &tokens.function_body),
         );
         self.raw_push_char(')');
 
@@ -650,15 +1032,11 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.write_function_return_type_suffix(return_type);
         }
 
-        let block = function.get_block();
-
-        if block.is_empty() {
-            self.raw_push_str(" end");
-        } else {
-            self.push_new_line();
-            self.indent_and_write_block(block);
-            self.push_str("end");
-        }
+        self.write_function_block(
+            function.get_block(),
+            function.get_tokens().map(|tokens| &tokens.function_body),
+            function.get_return_type().is_some(),
+        );
     }
 
     fn write_export_type_function(&mut self, function: &nodes::ExportTypeFunctionStatement) {
@@ -676,6 +1054,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             parameters,
             function.is_variadic(),
             function.get_variadic_type(),
+            function.get_tokens().map(|tokens| &tokens.function_body),
         );
         self.raw_push_char(')');
 
@@ -683,15 +1062,11 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.write_function_return_type_suffix(return_type);
         }
 
-        let block = function.get_block();
-
-        if block.is_empty() {
-            self.raw_push_str(" end");
-        } else {
-            self.push_new_line();
-            self.indent_and_write_block(block);
-            self.push_str("end");
-        }
+        self.write_function_block(
+            function.get_block(),
+            function.get_tokens().map(|tokens| &tokens.function_body),
+            function.get_return_type().is_some(),
+        );
     }
 
     fn write_generic_for(&mut self, generic_for: &nodes::GenericForStatement) {
@@ -728,13 +1103,15 @@ impl LuaGenerator for ReadableLuaGenerator {
             });
 
         let block = generic_for.get_block();
+        let tokens = generic_for.get_tokens();
+        let opening = tokens.map(|tokens| &tokens.r#do);
+        let closing = tokens.map(|tokens| &tokens.end);
 
-        if block.is_empty() {
+        if self.is_empty_block(block, opening, closing) {
             self.raw_push_str(" do end");
         } else {
             self.push_str("do");
-            self.push_new_line();
-            self.indent_and_write_block(block);
+            self.write_enclosed_block(block, opening, closing);
             self.push_str("end");
         }
     }
@@ -758,37 +1135,62 @@ impl LuaGenerator for ReadableLuaGenerator {
         }
 
         let block = numeric_for.get_block();
+        let tokens = numeric_for.get_tokens();
+        let opening = tokens.map(|tokens| &tokens.r#do);
+        let closing = tokens.map(|tokens| &tokens.end);
 
-        if block.is_empty() {
+        if self.is_empty_block(block, opening, closing) {
             self.raw_push_str(" do end");
         } else {
             self.push_str("do");
-            self.push_new_line();
-            self.indent_and_write_block(block);
+            self.write_enclosed_block(block, opening, closing);
             self.push_str("end");
         }
     }
 
+    fn write_goto_statement(&mut self, goto: &nodes::GotoStatement) {
+        self.push_str("goto");
+        self.push_str(goto.get_label().get_name());
+    }
+
+    fn write_label_statement(&mut self, label: &nodes::LabelStatement) {
+        self.push_str("::");
+        self.raw_push_str(label.get_name().get_name());
+        self.raw_push_str("::");
+    }
+
     fn write_if_statement(&mut self, if_statement: &nodes::IfStatement) {
         let branches = if_statement.get_branches();
+        let tokens = if_statement.get_tokens();
+        let else_block = if_statement.get_else_block();
+        let end_token = tokens.map(|tokens| &tokens.end);
+        let last_branch_end = if else_block.is_some() {
+            tokens.and_then(|tokens| tokens.r#else.as_ref())
+        } else {
+            end_token
+        };
 
         branches.iter().enumerate().for_each(|(index, branch)| {
-            if index == 0 {
+            let then = if index == 0 {
                 self.push_str("if ");
+                tokens.map(|tokens| &tokens.then)
             } else {
                 self.push_str("elseif ");
-            }
+                branch.get_tokens().map(|tokens| &tokens.then)
+            };
+            let closing = match branches.get(index + 1) {
+                Some(next_branch) => next_branch.get_tokens().map(|tokens| &tokens.elseif),
+                None => last_branch_end,
+            };
 
             self.write_expression(branch.get_condition());
             self.raw_push_str(" then");
-            self.push_new_line();
-            self.indent_and_write_block(branch.get_block());
+            self.write_enclosed_block(branch.get_block(), then, closing);
         });
 
-        if let Some(else_block) = if_statement.get_else_block() {
+        if let Some(else_block) = else_block {
             self.push_str("else");
-            self.push_new_line();
-            self.indent_and_write_block(else_block);
+            self.write_enclosed_block(else_block, last_branch_end, end_token);
         }
 
         self.push_str("end");
@@ -818,6 +1220,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             function.get_parameters(),
             function.is_variadic(),
             function.get_variadic_type(),
+            function.get_tokens(),
         );
         self.raw_push_char(')');
 
@@ -825,26 +1228,24 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.write_function_return_type_suffix(return_type);
         }
 
-        let block = function.get_block();
-
-        if block.is_empty() {
-            self.raw_push_str(" end");
-        } else {
-            self.push_new_line();
-            self.indent_and_write_block(block);
-            self.push_str("end");
-        }
+        self.write_function_block(
+            function.get_block(),
+            function.get_tokens(),
+            function.get_return_type().is_some(),
+        );
     }
 
     fn write_do_statement(&mut self, do_statement: &nodes::DoStatement) {
         let block = do_statement.get_block();
+        let tokens = do_statement.get_tokens();
+        let opening = tokens.map(|tokens| &tokens.r#do);
+        let closing = tokens.map(|tokens| &tokens.end);
 
-        if block.is_empty() {
+        if self.is_empty_block(block, opening, closing) {
             self.push_str("do end");
         } else {
             self.push_str("do");
-            self.push_new_line();
-            self.indent_and_write_block(block);
+            self.write_enclosed_block(block, opening, closing);
             self.push_str("end");
         }
     }
@@ -853,12 +1254,14 @@ impl LuaGenerator for ReadableLuaGenerator {
         self.push_str("repeat");
 
         let block = repeat.get_block();
+        let tokens = repeat.get_tokens();
+        let opening = tokens.map(|tokens| &tokens.repeat);
+        let closing = tokens.map(|tokens| &tokens.until);
 
-        if block.is_empty() {
+        if self.is_empty_block(block, opening, closing) {
             self.raw_push_str(" until ");
         } else {
-            self.push_new_line();
-            self.indent_and_write_block(block);
+            self.write_enclosed_block(block, opening, closing);
             self.push_str("until ");
         }
 
@@ -872,13 +1275,15 @@ impl LuaGenerator for ReadableLuaGenerator {
         self.pop_can_add_new_line();
 
         let block = while_statement.get_block();
+        let tokens = while_statement.get_tokens();
+        let opening = tokens.map(|tokens| &tokens.r#do);
+        let closing = tokens.map(|tokens| &tokens.end);
 
-        if block.is_empty() {
+        if self.is_empty_block(block, opening, closing) {
             self.raw_push_str(" do end");
         } else {
             self.raw_push_str(" do");
-            self.push_new_line();
-            self.indent_and_write_block(block);
+            self.write_enclosed_block(block, opening, closing);
             self.push_str("end");
         }
     }
@@ -941,19 +1346,23 @@ impl LuaGenerator for ReadableLuaGenerator {
         self.write_type(statement.get_type());
     }
 
-    fn write_false_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_false_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str("false");
     }
 
-    fn write_true_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_true_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str("true");
     }
 
-    fn write_nil_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_nil_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str("nil");
     }
 
-    fn write_variable_arguments_expression(&mut self, _token: &Option<nodes::Token>) {
+    fn write_variable_arguments_expression(&mut self, token: &Option<nodes::Token>) {
+        self.map_token(token.as_ref());
         self.push_str_and_break_if("...", utils::break_variable_arguments);
     }
 
@@ -986,6 +1395,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             Length => self.push_char('#'),
             Minus => self.push_str_and_break_if("-", utils::break_minus),
             Not => self.push_str("not "),
+            BitwiseNot => self.push_char('~'),
         }
 
         let expression = unary.get_expression();
@@ -1012,6 +1422,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             parameters,
             function.is_variadic(),
             function.get_variadic_type(),
+            function.get_tokens(),
         );
         self.raw_push_char(')');
 
@@ -1019,15 +1430,11 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.write_function_return_type_suffix(return_type);
         }
 
-        let block = function.get_block();
-
-        if block.is_empty() {
-            self.raw_push_str(" end");
-        } else {
-            self.push_new_line();
-            self.indent_and_write_block(block);
-            self.push_str("end");
-        }
+        self.write_function_block(
+            function.get_block(),
+            function.get_tokens(),
+            function.get_return_type().is_some(),
+        );
     }
 
     fn write_function_call(&mut self, call: &nodes::FunctionCall) {
@@ -1044,21 +1451,85 @@ impl LuaGenerator for ReadableLuaGenerator {
         self.pop_can_add_new_line();
     }
 
+    fn write_arguments(&mut self, arguments: &nodes::Arguments) {
+        use nodes::Arguments::*;
+        match (self.style.call_parentheses(), arguments) {
+            (CallParentheses::Always, String(string)) => {
+                self.raw_push_char('(');
+                self.write_string(string);
+                self.push_char(')');
+            }
+            (CallParentheses::Always, Table(table)) => {
+                self.raw_push_char('(');
+                self.write_table(table);
+                self.push_char(')');
+            }
+            (CallParentheses::Omit, Tuple(tuple)) if tuple.len() == 1 => {
+                match tuple.iter_values().next() {
+                    Some(nodes::Expression::String(string)) => {
+                        self.raw_push_char(' ');
+                        self.write_string(string);
+                    }
+                    Some(nodes::Expression::Table(table)) => {
+                        self.raw_push_char(' ');
+                        self.write_table(table);
+                    }
+                    _ => self.write_tuple_arguments(tuple),
+                }
+            }
+            (_, String(string)) => self.write_string(string),
+            (_, Table(table)) => self.write_table(table),
+            (_, Tuple(tuple)) => self.write_tuple_arguments(tuple),
+        }
+    }
+
     fn write_tuple_arguments(&mut self, arguments: &nodes::TupleArguments) {
         self.raw_push_char('(');
 
         let last_index = arguments.len().saturating_sub(1);
-        arguments
-            .iter_values()
-            .enumerate()
-            .for_each(|(index, expression)| {
-                self.write_expression(expression);
 
-                if index != last_index {
-                    self.raw_push_char(',');
-                    self.raw_push_char(' ');
-                }
-            });
+        if self.has_arguments_comments(arguments) {
+            let tokens = arguments.get_tokens();
+
+            self.write_trailing_comments(tokens.map(|tokens| &tokens.opening_parenthese));
+            self.push_indentation();
+
+            arguments
+                .iter_values()
+                .enumerate()
+                .for_each(|(index, expression)| {
+                    self.push_new_line();
+                    self.write_leading_comments(lines::first_expression_token(expression));
+                    self.write_indentation();
+                    self.write_expression(expression);
+
+                    if index != last_index {
+                        self.raw_push_char(',');
+                    }
+
+                    self.write_trailing_comments(
+                        lines::last_expression_token(expression)
+                            .into_iter()
+                            .chain(tokens.and_then(|tokens| tokens.commas.get(index))),
+                    );
+                });
+
+            self.push_new_line();
+            self.write_leading_comments(tokens.map(|tokens| &tokens.closing_parenthese));
+            self.pop_indentation();
+        } else {
+            arguments
+                .iter_values()
+                .enumerate()
+                .for_each(|(index, expression)| {
+                    self.write_expression(expression);
+
+                    if index != last_index {
+                        self.raw_push_char(',');
+                        self.raw_push_char(' ');
+                    }
+                });
+        }
 
         self.push_char(')');
     }
@@ -1070,6 +1541,7 @@ impl LuaGenerator for ReadableLuaGenerator {
 
         self.push_new_line_if_needed(1);
         self.raw_push_char('.');
+        self.map_token(field.get_field().get_token());
         self.raw_push_str(field.get_field().get_name());
     }
 
@@ -1133,7 +1605,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.raw_push_char('}');
         } else {
             let column_space = self.column_span.saturating_sub(self.current_line_length);
-            if self.table_fits_on_line(entries, column_space) {
+            if !self.has_comments(table) && self.table_fits_on_line(entries, column_space) {
                 let last_index = table_len.saturating_sub(1);
 
                 entries.iter().enumerate().for_each(|(index, entry)| {
@@ -1145,18 +1617,33 @@ impl LuaGenerator for ReadableLuaGenerator {
                     }
                 });
             } else {
+                let tokens = table.get_tokens();
+
+                self.write_trailing_comments(tokens.map(|tokens| &tokens.opening_brace));
                 self.push_indentation();
 
-                entries.iter().for_each(|entry| {
+                let last_index = table_len.saturating_sub(1);
+
+                entries.iter().enumerate().for_each(|(index, entry)| {
                     self.push_new_line();
+                    self.write_leading_comments(lines::first_table_entry_token(entry));
                     self.write_indentation();
                     self.write_table_entry(entry);
 
-                    self.raw_push_char(',');
+                    if index != last_index || self.style.trailing_commas() {
+                        self.raw_push_char(',');
+                    }
+
+                    self.write_trailing_comments(
+                        lines::last_table_entry_token(entry)
+                            .into_iter()
+                            .chain(tokens.and_then(|tokens| tokens.separators.get(index))),
+                    );
                 });
 
-                self.pop_indentation();
                 self.push_new_line();
+                self.write_leading_comments(tokens.map(|tokens| &tokens.closing_brace));
+                self.pop_indentation();
             }
 
             self.push_char('}');
@@ -1183,11 +1670,14 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_number(&mut self, number: &nodes::NumberExpression) {
+        self.map_token(number.get_token());
         self.push_str(&utils::write_number(number));
     }
 
     fn write_string(&mut self, string: &nodes::StringExpression) {
-        let result = utils::write_string(string.get_value());
+        self.map_token(string.get_token());
+        let result =
+            utils::write_string_with_quote_style(string.get_value(), self.style.quote_style());
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -1223,6 +1713,7 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_identifier(&mut self, identifier: &nodes::Identifier) {
+        self.map_token(identifier.get_token());
         self.push_str(identifier.get_name());
     }
 
@@ -1294,7 +1785,8 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_string_type(&mut self, string_type: &nodes::StringType) {
-        let result = utils::write_string(string_type.get_value());
+        let result =
+            utils::write_string_with_quote_style(string_type.get_value(), self.style.quote_style());
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+
+/// The characters used to indent each level of code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndentStyle {
+    #[default]
+    Spaces,
+    Tabs,
+}
+
+/// Defines which quote is used to write strings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStyle {
+    /// Use single quotes, unless the string contains single quotes and no double quotes.
+    #[default]
+    PreferSingle,
+    /// Use double quotes, unless the string contains double quotes and no single quotes.
+    PreferDouble,
+    /// Always use single quotes.
+    Single,
+    /// Always use double quotes.
+    Double,
+}
+
+impl QuoteStyle {
+    pub(crate) fn quote_symbol(&self, value: &str) -> char {
+        match self {
+            Self::PreferSingle => {
+                if value.contains('\'') && !value.contains('"') {
+                    '"'
+                } else {
+                    '\''
+                }
+            }
+            Self::PreferDouble => {
+                if value.contains('"') && !value.contains('\'') {
+                    '\''
+                } else {
+                    '"'
+                }
+            }
+            Self::Single => '\'',
+            Self::Double => '"',
+        }
+    }
+}
+
+/// Defines how parentheses are written for function calls with a single string or
+/// table argument.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallParentheses {
+    /// Write the arguments as they are in the code.
+    #[default]
+    Keep,
+    /// Always wrap the argument in parentheses.
+    Always,
+    /// Remove the parentheses around a single string or table argument.
+    Omit,
+}
+
+/// The formatting choices of the [`ReadableLuaGenerator`](super::ReadableLuaGenerator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "snake_case")]
+pub struct ReadableStyle {
+    indent_style: IndentStyle,
+    indent_width: usize,
+    quote_style: QuoteStyle,
+    trailing_commas: bool,
+    call_parentheses: CallParentheses,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    function_blank_lines: Option<usize>,
+}
+
+impl Default for ReadableStyle {
+    fn default() -> Self {
+        Self {
+            indent_style: IndentStyle::default(),
+            indent_width: 4,
+            quote_style: QuoteStyle::default(),
+            trailing_commas: true,
+            call_parentheses: CallParentheses::default(),
+            function_blank_lines: None,
+        }
+    }
+}
+
+impl ReadableStyle {
+    pub fn with_indent_style(mut self, indent_style: IndentStyle) -> Self {
+        self.indent_style = indent_style;
+        self
+    }
+
+    /// Sets the number of spaces of each indentation level. When indenting with tabs,
+    /// it is used as the width of a tab to compute the length of lines.
+    pub fn with_indent_width(mut self, indent_width: usize) -> Self {
+        self.indent_width = indent_width;
+        self
+    }
+
+    pub fn with_quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Sets if a comma is written after the last entry of tables written on multiple lines.
+    pub fn with_trailing_commas(mut self, trailing_commas: bool) -> Self {
+        self.trailing_commas = trailing_commas;
+        self
+    }
+
+    pub fn with_call_parentheses(mut self, call_parentheses: CallParentheses) -> Self {
+        self.call_parentheses = call_parentheses;
+        self
+    }
+
+    /// Sets the number of blank lines written before and after top-level function
+    /// declarations. By default, blank lines are only written between statements of
+    /// different kinds.
+    pub fn with_function_blank_lines(mut self, blank_lines: usize) -> Self {
+        self.function_blank_lines = Some(blank_lines);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn indent_style(&self) -> IndentStyle {
+        self.indent_style
+    }
+
+    #[inline]
+    pub(crate) fn indent_width(&self) -> usize {
+        self.indent_width
+    }
+
+    #[inline]
+    pub(crate) fn quote_style(&self) -> QuoteStyle {
+        self.quote_style
+    }
+
+    #[inline]
+    pub(crate) fn trailing_commas(&self) -> bool {
+        self.trailing_commas
+    }
+
+    #[inline]
+    pub(crate) fn call_parentheses(&self) -> CallParentheses {
+        self.call_parentheses
+    }
+
+    #[inline]
+    pub(crate) fn function_blank_lines(&self) -> Option<usize> {
+        self.function_blank_lines
+    }
+
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prefer_single_uses_single_quotes() {
+        assert_eq!(QuoteStyle::PreferSingle.quote_symbol("abc"), '\'');
+    }
+
+    #[test]
+    fn prefer_single_uses_double_quotes_around_single_quotes() {
+        assert_eq!(QuoteStyle::PreferSingle.quote_symbol("it's"), '"');
+    }
+
+    #[test]
+    fn prefer_single_with_both_quotes() {
+        assert_eq!(QuoteStyle::PreferSingle.quote_symbol("it's \"a\""), '\'');
+    }
+
+    #[test]
+    fn prefer_double_uses_double_quotes() {
+        assert_eq!(QuoteStyle::PreferDouble.quote_symbol("abc"), '"');
+    }
+
+    #[test]
+    fn prefer_double_uses_single_quotes_around_double_quotes() {
+        assert_eq!(QuoteStyle::PreferDouble.quote_symbol("say \"a\""), '\'');
+    }
+
+    #[test]
+    fn double_always_uses_double_quotes() {
+        assert_eq!(QuoteStyle::Double.quote_symbol("say \"a\""), '"');
+    }
+}
@@ -549,9 +549,13 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         let last_variable_index = assign.variables_len().saturating_sub(1);
         assign
             .iter_variables()
+            .zip(assign.iter_attributes())
             .enumerate()
-            .for_each(|(i, identifier)| {
+            .for_each(|(i, (identifier, attribute))| {
                 self.write_typed_identifier(identifier);
+                if let Some(attribute) = attribute {
+                    self.write_local_attribute(attribute);
+                }
                 if i < last_variable_index {
                     if let Some(comma) = tokens.variable_commas.get(i) {
                         self.write_token(comma);
@@ -581,6 +585,45 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         }
     }
 
+    fn write_local_attribute(&mut self, attribute: &LocalAttribute) {
+        if let Some(tokens) = attribute.get_tokens() {
+            self.write_local_attribute_with_tokens(attribute, tokens);
+        } else {
+            self.write_local_attribute_with_tokens(
+                attribute,
+                &self.generate_local_attribute_tokens(attribute),
+            );
+        }
+    }
+
+    fn write_local_attribute_with_tokens(
+        &mut self,
+        attribute: &LocalAttribute,
+        tokens: &LocalAttributeTokens,
+    ) {
+        self.write_token(&tokens.opening_bracket);
+        let name_in_token = tokens.name.read(self.original_code);
+        if name_in_token == attribute.kind().to_str() {
+            self.write_token(&tokens.name);
+        } else {
+            let mut new_token = tokens.name.clone();
+            new_token.replace_with_content(attribute.kind().to_str());
+            self.write_token(&new_token);
+        }
+        self.write_token(&tokens.closing_bracket);
+    }
+
+    fn write_goto_with_tokens(&mut self, goto: &GotoStatement, tokens: &GotoTokens) {
+        self.write_token(&tokens.goto);
+        self.write_identifier(goto.get_label());
+    }
+
+    fn write_label_with_tokens(&mut self, label: &LabelStatement, tokens: &LabelTokens) {
+        self.write_token(&tokens.left_colons);
+        self.write_identifier(label.get_name());
+        self.write_token(&tokens.right_colons);
+    }
+
     fn write_local_function_with_tokens(
         &mut self,
         function: &LocalFunctionStatement,
@@ -1328,6 +1371,27 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         }
     }
 
+    fn generate_local_attribute_tokens(&self, attribute: &LocalAttribute) -> LocalAttributeTokens {
+        LocalAttributeTokens {
+            opening_bracket: Token::from_content("<"),
+            name: Token::from_content(attribute.kind().to_str()),
+            closing_bracket: Token::from_content(">"),
+        }
+    }
+
+    fn generate_goto_tokens(&self, _goto: &GotoStatement) -> GotoTokens {
+        GotoTokens {
+            goto: Token::from_content("goto"),
+        }
+    }
+
+    fn generate_label_tokens(&self, _label: &LabelStatement) -> LabelTokens {
+        LabelTokens {
+            left_colons: Token::from_content("::"),
+            right_colons: Token::from_content("::"),
+        }
+    }
+
     fn generate_local_function_tokens(
         &self,
         function: &LocalFunctionStatement,
@@ -1851,6 +1915,22 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
         }
     }
 
+    fn write_goto_statement(&mut self, goto: &GotoStatement) {
+        if let Some(tokens) = goto.get_tokens() {
+            self.write_goto_with_tokens(goto, tokens);
+        } else {
+            self.write_goto_with_tokens(goto, &self.generate_goto_tokens(goto));
+        }
+    }
+
+    fn write_label_statement(&mut self, label: &LabelStatement) {
+        if let Some(tokens) = label.get_tokens() {
+            self.write_label_with_tokens(label, tokens);
+        } else {
+            self.write_label_with_tokens(label, &self.generate_label_tokens(label));
+        }
+    }
+
     fn write_if_statement(&mut self, if_statement: &IfStatement) {
         if let Some(tokens) = if_statement.get_tokens() {
             self.write_if_statement_with_tokens(if_statement, tokens);
@@ -2521,6 +2601,42 @@ mod test {
         return_field_split_on_lines => "return value.\n\tproperty\n\t.name",
     );
 
+    mod lua54 {
+        use super::*;
+
+        macro_rules! test_lua54_output {
+            ($($name:ident => $code:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let parser = crate::Parser::default()
+                            .with_lua_version(crate::LuaVersion::Lua54)
+                            .preserve_tokens();
+                        let block = parser.parse($code)
+                            .expect(&format!("failed to parse `{}`", $code));
+
+                        let mut generator = TokenBasedLuaGenerator::new($code);
+
+                        generator.write_block(&block);
+
+                        let output = generator.into_string();
+
+                        assert_eq!($code, &output);
+                    }
+                )*
+            };
+        }
+
+        test_lua54_output!(
+            goto_statement => "goto  continue -- skip",
+            label_statement => ":: continue ::",
+            local_const => "local value < const > = 1",
+            local_const_and_close => "local a <const>, b <close> = 1, f()",
+            bitwise_operators => "return a & b | c ~ d << 1 >> 2",
+            bitwise_not => "return ~ a",
+        );
+    }
+
     #[test]
     fn inserts_a_new_line_after_a_comment_for_a_token() {
         let statement = RepeatStatement::new(Block::default(), true).with_tokens(RepeatTokens {
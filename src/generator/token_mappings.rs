@@ -0,0 +1,128 @@
+use crate::nodes::{Position, Token};
+
+/// Associates a position in the generated code to the position of the token it was
+/// generated from. Lines are one-based (like token line numbers) and columns are
+/// zero-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenMapping {
+    generated_line: usize,
+    generated_column: usize,
+    original_line: usize,
+    original_column: Option<usize>,
+}
+
+impl TokenMapping {
+    #[inline]
+    pub fn generated_line(&self) -> usize {
+        self.generated_line
+    }
+
+    #[inline]
+    pub fn generated_column(&self) -> usize {
+        self.generated_column
+    }
+
+    #[inline]
+    pub fn original_line(&self) -> usize {
+        self.original_line
+    }
+
+    /// Returns the column of the original token, when it is known.
+    #[inline]
+    pub fn original_column(&self) -> Option<usize> {
+        self.original_column
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RecordedToken {
+    output_offset: usize,
+    position: Position,
+}
+
+/// Records the position of tokens while a generator writes code, using the offset in
+/// the output where the token content begins.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TokenMappingRecorder {
+    pending: Option<Position>,
+    recorded: Vec<RecordedToken>,
+}
+
+impl TokenMappingRecorder {
+    /// Marks the token as the origin of the next content written by the generator.
+    pub(crate) fn map_token(&mut self, token: Option<&Token>) {
+        if let Some(token) = token {
+            if token.get_line_number().is_some() {
+                self.pending = Some(token.get_position().clone());
+            }
+        }
+    }
+
+    /// Records the pending token at the first non-whitespace character of the content
+    /// that is about to be written at the given output offset.
+    pub(crate) fn write_content(&mut self, output_offset: usize, content: &str) {
+        let trimmed = content.trim_start();
+
+        if trimmed.is_empty() {
+            return;
+        }
+
+        if let Some(position) = self.pending.take() {
+            self.recorded.push(RecordedToken {
+                output_offset: output_offset + (content.len() - trimmed.len()),
+                position,
+            });
+        }
+    }
+
+    /// Moves the recorded tokens written from `old_offset` when the generator rewrites
+    /// the end of its output.
+    pub(crate) fn move_output(&mut self, old_offset: usize, new_offset: usize) {
+        for token in self.recorded.iter_mut().rev() {
+            if token.output_offset < old_offset {
+                break;
+            }
+            token.output_offset = token.output_offset - old_offset + new_offset;
+        }
+    }
+
+    pub(crate) fn into_mappings(self, output: &str, original_code: &str) -> Vec<TokenMapping> {
+        let mut mappings = Vec::with_capacity(self.recorded.len());
+
+        let mut generated_line = 1;
+        let mut line_start = 0;
+        let mut scanned = 0;
+
+        for token in self.recorded {
+            let output_offset = token.output_offset.min(output.len());
+
+            if output_offset < scanned {
+                // offsets are only decreasing if the output was rewritten unexpectedly
+                continue;
+            }
+
+            for (index, character) in output[scanned..output_offset].char_indices() {
+                if character == '\n' {
+                    generated_line += 1;
+                    line_start = scanned + index + 1;
+                }
+            }
+            scanned = output_offset;
+
+            let original_line = match &token.position {
+                Position::LineNumberReference { line_number, .. }
+                | Position::LineNumber { line_number, .. } => *line_number,
+                Position::Any { .. } => continue,
+            };
+
+            mappings.push(TokenMapping {
+                generated_line,
+                generated_column: output[line_start..output_offset].chars().count(),
+                original_line,
+                original_column: token.position.get_column(original_code),
+            });
+        }
+
+        mappings
+    }
+}
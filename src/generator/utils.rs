@@ -3,6 +3,8 @@
 
 use std::convert::TryInto;
 
+use super::QuoteStyle;
+
 use crate::nodes::{
     Expression, FieldExpression, FunctionCall, IndexExpression, NumberExpression, Prefix,
     Statement, StringSegment, TableExpression, Variable,
@@ -299,26 +301,12 @@ pub fn count_new_lines(string: &str) -> usize {
 }
 
 pub fn write_string(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_owned();
-    }
+    write_string_with_quote_style(value, QuoteStyle::default())
+}
 
-    if value.len() == 1 {
-        let character = value
-            .chars()
-            .next()
-            .expect("string should have at least one character");
-        match character {
-            '\'' => return "\"'\"".to_owned(),
-            '"' => return "'\"'".to_owned(),
-            _ => {
-                if needs_escaping(character) {
-                    return format!("'{}'", escape(character));
-                } else {
-                    return format!("'{}'", character);
-                }
-            }
-        }
+pub fn write_string_with_quote_style(value: &str, quote_style: QuoteStyle) -> String {
+    if value.len() <= 1 {
+        return write_quoted(value, quote_style);
     }
 
     if !value.contains(needs_quoted_string)
@@ -328,7 +316,7 @@ pub fn write_string(value: &str) -> String {
     {
         write_long_bracket(value)
     } else {
-        write_quoted(value)
+        write_quoted(value, quote_style)
     }
 }
 
@@ -376,11 +364,11 @@ fn write_long_bracket(value: &str) -> String {
     format!("[{}[{}{}]{}]", equals, needs_extra_new_line, value, equals)
 }
 
-fn write_quoted(value: &str) -> String {
+fn write_quoted(value: &str, quote_style: QuoteStyle) -> String {
     let mut quoted = String::new();
     quoted.reserve(value.len() + 2);
 
-    let quote_symbol = get_quote_symbol(value);
+    let quote_symbol = quote_style.quote_symbol(value);
     quoted.push(quote_symbol);
 
     for character in value.chars() {
@@ -399,16 +387,6 @@ fn write_quoted(value: &str) -> String {
     quoted
 }
 
-fn get_quote_symbol(value: &str) -> char {
-    if value.contains('"') {
-        '\''
-    } else if value.contains('\'') {
-        '"'
-    } else {
-        '\''
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -10,7 +10,7 @@ pub mod rules;
 mod utils;
 
 pub use frontend::{
-    convert_data, process, BundleConfiguration, Configuration, DarkluaError, GeneratorParameters,
-    Options, Resources, WorkerTree,
+    ast_from_json, ast_to_json, convert_data, process, BundleConfiguration, Configuration,
+    DarkluaError, GeneratorParameters, Options, Resources, WorkerTree,
 };
-pub use parser::{Parser, ParserError};
+pub use parser::{LuaVersion, Parser, ParserError};
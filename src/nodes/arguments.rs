@@ -1,8 +1,10 @@
 use std::iter;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, StringExpression, TableExpression, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TupleArgumentsTokens {
     pub opening_parenthese: Token,
     pub closing_parenthese: Token,
@@ -16,9 +18,10 @@ impl TupleArgumentsTokens {
     );
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TupleArguments {
     values: Vec<Expression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TupleArgumentsTokens>,
 }
 
@@ -97,7 +100,8 @@ impl iter::FromIterator<Expression> for TupleArguments {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Arguments {
     Tuple(TupleArguments),
     String(StringExpression),
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{LastStatement, ReturnStatement, Statement, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlockTokens {
     pub semicolons: Vec<Option<Token>>,
     pub last_semicolon: Option<Token>,
@@ -14,10 +16,11 @@ impl BlockTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Block {
     statements: Vec<Statement>,
     last_statement: Option<LastStatement>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<Box<BlockTokens>>,
 }
 
@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, FunctionReturnType, Token, Type};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BinaryOperator {
     And,
     Or,
@@ -18,6 +21,11 @@ pub enum BinaryOperator {
     Percent,
     Caret,
     Concat,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    LeftShift,
+    RightShift,
 }
 
 #[inline]
@@ -182,6 +190,11 @@ impl BinaryOperator {
             Self::Percent => "%",
             Self::Caret => "^",
             Self::Concat => "..",
+            Self::BitwiseAnd => "&",
+            Self::BitwiseOr => "|",
+            Self::BitwiseXor => "~",
+            Self::LeftShift => "<<",
+            Self::RightShift => ">>",
         }
     }
 
@@ -195,19 +208,24 @@ impl BinaryOperator {
             | Self::LowerOrEqualThan
             | Self::GreaterThan
             | Self::GreaterOrEqualThan => 2,
-            Self::Concat => 3,
-            Self::Plus | Self::Minus => 4,
-            Self::Asterisk | Self::Slash | Self::DoubleSlash | Self::Percent => 5,
-            Self::Caret => 7,
+            Self::BitwiseOr => 3,
+            Self::BitwiseXor => 4,
+            Self::BitwiseAnd => 5,
+            Self::LeftShift | Self::RightShift => 6,
+            Self::Concat => 7,
+            Self::Plus | Self::Minus => 8,
+            Self::Asterisk | Self::Slash | Self::DoubleSlash | Self::Percent => 9,
+            Self::Caret => 11,
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BinaryExpression {
     operator: BinaryOperator,
     left: Expression,
     right: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Identifier, Prefix, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FieldExpression {
     prefix: Prefix,
     field: Identifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -1,9 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{
     Block, FunctionBodyTokens, FunctionReturnType, FunctionVariadicType, GenericParameters,
     TypedIdentifier,
 };
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionExpression {
     block: Block,
     parameters: Vec<TypedIdentifier>,
@@ -11,6 +13,7 @@ pub struct FunctionExpression {
     variadic_type: Option<FunctionVariadicType>,
     return_type: Option<FunctionReturnType>,
     generic_parameters: Option<GenericParameters>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<Box<FunctionBodyTokens>>,
 }
 
@@ -1,13 +1,16 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::Expression;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfExpression {
     condition: Expression,
     result: Expression,
     else_result: Expression,
     branches: Vec<ElseIfExpressionBranch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<IfExpressionTokens>,
 }
 
@@ -125,10 +128,11 @@ impl IfExpression {
     super::impl_token_fns!(iter = [tokens, branches]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ElseIfExpressionBranch {
     condition: Expression,
     result: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<ElseIfExpressionBranchTokens>,
 }
 
@@ -178,7 +182,7 @@ impl ElseIfExpressionBranch {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfExpressionTokens {
     pub r#if: Token,
     pub then: Token,
@@ -189,7 +193,7 @@ impl IfExpressionTokens {
     super::impl_token_fns!(target = [r#if, then, r#else]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ElseIfExpressionBranchTokens {
     pub elseif: Token,
     pub then: Token,
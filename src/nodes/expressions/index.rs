@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, Prefix, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IndexExpressionTokens {
     pub opening_bracket: Token,
     pub closing_bracket: Token,
@@ -10,10 +12,11 @@ impl IndexExpressionTokens {
     super::impl_token_fns!(target = [opening_bracket, closing_bracket]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IndexExpression {
     prefix: Prefix,
     index: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<IndexExpressionTokens>,
 }
 
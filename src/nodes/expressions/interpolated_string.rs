@@ -1,12 +1,15 @@
 use std::iter::FromIterator;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{StringError, Token, Trivia};
 
 use super::{string_utils, Expression};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StringSegment {
     value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -57,9 +60,10 @@ impl StringSegment {
     super::impl_token_fns!(iter = [token]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValueSegment {
     value: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<ValueSegmentTokens>,
 }
 
@@ -95,7 +99,7 @@ impl ValueSegment {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValueSegmentTokens {
     pub opening_brace: Token,
     pub closing_brace: Token,
@@ -105,7 +109,8 @@ impl ValueSegmentTokens {
     super::impl_token_fns!(target = [opening_brace, closing_brace]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InterpolationSegment {
     String(StringSegment),
     Value(ValueSegment),
@@ -184,9 +189,10 @@ impl From<String> for InterpolationSegment {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InterpolatedStringExpression {
     segments: Vec<InterpolationSegment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<InterpolatedStringTokens>,
 }
 
@@ -272,7 +278,7 @@ impl FromIterator<InterpolationSegment> for InterpolatedStringExpression {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InterpolatedStringTokens {
     pub opening_tick: Token,
     pub closing_tick: Token,
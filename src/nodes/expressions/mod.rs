@@ -28,13 +28,16 @@ pub use table::*;
 pub use type_cast::*;
 pub use unary::*;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{FunctionCall, Identifier, Token, Variable};
 
 use super::impl_token_fns;
 
 use std::num::FpCategory;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Expression {
     Binary(Box<BinaryExpression>),
     Call(Box<FunctionCall>),
@@ -1,12 +1,15 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Token, Trivia};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DecimalNumber {
     float: f64,
     exponent: Option<(i64, bool)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -36,6 +39,11 @@ impl DecimalNumber {
         self.token.as_ref()
     }
 
+    #[inline]
+    fn mutate_token(&mut self) -> Option<&mut Token> {
+        self.token.as_mut()
+    }
+
     pub fn with_exponent(mut self, exponent: i64, is_uppercase: bool) -> Self {
         self.exponent.replace((exponent, is_uppercase));
         self
@@ -68,11 +76,12 @@ impl DecimalNumber {
     super::impl_token_fns!(iter = [token]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HexNumber {
     integer: u64,
     exponent: Option<(u32, bool)>,
     is_x_uppercase: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -101,6 +110,11 @@ impl HexNumber {
         self.token.as_ref()
     }
 
+    #[inline]
+    fn mutate_token(&mut self) -> Option<&mut Token> {
+        self.token.as_mut()
+    }
+
     pub fn with_exponent(mut self, exponent: u32, is_uppercase: bool) -> Self {
         self.exponent.replace((exponent, is_uppercase));
         self
@@ -142,10 +156,11 @@ impl HexNumber {
     super::impl_token_fns!(iter = [token]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BinaryNumber {
     value: u64,
     is_b_uppercase: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -173,6 +188,11 @@ impl BinaryNumber {
         self.token.as_ref()
     }
 
+    #[inline]
+    fn mutate_token(&mut self) -> Option<&mut Token> {
+        self.token.as_mut()
+    }
+
     pub fn set_uppercase(&mut self, is_uppercase: bool) {
         self.is_b_uppercase = is_uppercase;
     }
@@ -194,7 +214,8 @@ impl BinaryNumber {
     super::impl_token_fns!(iter = [token]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum NumberExpression {
     Decimal(DecimalNumber),
     Hex(HexNumber),
@@ -245,6 +266,15 @@ impl NumberExpression {
         }
     }
 
+    #[inline]
+    pub fn mutate_token(&mut self) -> Option<&mut Token> {
+        match self {
+            NumberExpression::Decimal(number) => number.mutate_token(),
+            NumberExpression::Hex(number) => number.mutate_token(),
+            NumberExpression::Binary(number) => number.mutate_token(),
+        }
+    }
+
     pub fn clear_comments(&mut self) {
         match self {
             NumberExpression::Decimal(number) => number.clear_comments(),
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParentheseTokens {
     pub left_parenthese: Token,
     pub right_parenthese: Token,
@@ -10,9 +12,10 @@ impl ParentheseTokens {
     super::impl_token_fns!(target = [left_parenthese, right_parenthese]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParentheseExpression {
     expression: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<ParentheseTokens>,
 }
 
@@ -1,8 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{
     Expression, FieldExpression, FunctionCall, Identifier, IndexExpression, ParentheseExpression,
 };
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Prefix {
     Call(FunctionCall),
     Field(Box<FieldExpression>),
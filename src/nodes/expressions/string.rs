@@ -1,12 +1,15 @@
 use std::str::CharIndices;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{StringError, Token};
 
 use super::string_utils;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StringExpression {
     value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
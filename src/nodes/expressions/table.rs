@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
     nodes::{Expression, Identifier, Token, Trivia},
     process::utils::is_valid_identifier,
@@ -5,11 +7,12 @@ use crate::{
 
 use super::StringExpression;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableFieldEntry {
     field: Identifier,
     value: Expression,
     /// The token for the `=` operator symbol.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -63,7 +66,7 @@ impl TableFieldEntry {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableIndexEntryTokens {
     pub opening_bracket: Token,
     pub closing_bracket: Token,
@@ -74,10 +77,11 @@ impl TableIndexEntryTokens {
     super::impl_token_fns!(target = [opening_bracket, closing_bracket, equal]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableIndexEntry {
     key: Expression,
     value: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<Box<TableIndexEntryTokens>>,
 }
 
@@ -128,7 +132,8 @@ impl TableIndexEntry {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TableEntry {
     Field(TableFieldEntry),
     Index(TableIndexEntry),
@@ -209,7 +214,7 @@ impl From<TableIndexEntry> for TableEntry {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableTokens {
     pub opening_brace: Token,
     pub closing_brace: Token,
@@ -223,9 +228,10 @@ impl TableTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableExpression {
     entries: Vec<TableEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TableTokens>,
 }
 
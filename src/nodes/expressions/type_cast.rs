@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, Token, Type};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeCastExpression {
     expression: Box<Expression>,
     r#type: Type,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
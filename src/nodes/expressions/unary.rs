@@ -1,10 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, Token};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum UnaryOperator {
     Length,
     Minus,
     Not,
+    BitwiseNot,
 }
 
 impl UnaryOperator {
@@ -13,14 +17,16 @@ impl UnaryOperator {
             Self::Length => "#",
             Self::Minus => "-",
             Self::Not => "not",
+            Self::BitwiseNot => "~",
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UnaryExpression {
     operator: UnaryOperator,
     expression: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
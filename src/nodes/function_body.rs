@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use super::{
     Block, ExportTypeFunctionStatement, ExportTypeFunctionTokens, FunctionExpression, FunctionName,
     FunctionReturnType, FunctionStatement, FunctionVariadicType, GenericParameters, Identifier,
@@ -331,7 +333,7 @@ impl FunctionBuilder {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionBodyTokens {
     pub function: Token,
     pub opening_parenthese: Token,
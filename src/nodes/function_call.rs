@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Arguments, Expression, Identifier, Prefix, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionCallTokens {
     pub colon: Option<Token>,
 }
@@ -9,11 +11,12 @@ impl FunctionCallTokens {
     super::impl_token_fns!(iter = [colon]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionCall {
     prefix: Box<Prefix>,
     arguments: Arguments,
     method: Option<Identifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<FunctionCallTokens>,
 }
 
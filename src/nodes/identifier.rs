@@ -1,10 +1,13 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::{Type, TypedIdentifier};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Identifier {
     name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -94,6 +97,7 @@ mod test {
             &Token::from_position(Position::LineNumber {
                 line_number: 1,
                 content: "newVar".into(),
+                column: None,
             })
         );
     }
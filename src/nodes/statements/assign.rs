@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, Token, Variable};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssignTokens {
     pub equal: Token,
     pub variable_commas: Vec<Token>,
@@ -14,10 +16,11 @@ impl AssignTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssignStatement {
     variables: Vec<Variable>,
     values: Vec<Expression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<AssignTokens>,
 }
 
@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{BinaryOperator, Expression, Token, Variable};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CompoundOperator {
     Plus,
     Minus,
@@ -40,7 +43,7 @@ impl CompoundOperator {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CompoundAssignTokens {
     pub operator: Token,
 }
@@ -49,11 +52,12 @@ impl CompoundAssignTokens {
     super::impl_token_fns!(target = [operator]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CompoundAssignStatement {
     operator: CompoundOperator,
     variable: Variable,
     value: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<CompoundAssignTokens>,
 }
 
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Block, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DoTokens {
     pub r#do: Token,
     pub end: Token,
@@ -10,9 +12,10 @@ impl DoTokens {
     super::impl_token_fns!(target = [r#do, end]);
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DoStatement {
     block: Block,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<DoTokens>,
 }
 
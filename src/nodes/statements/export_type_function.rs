@@ -1,9 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{
     Block, FunctionBodyTokens, FunctionReturnType, FunctionVariadicType, GenericParameters,
     Identifier, Token, TypedIdentifier,
 };
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExportTypeFunctionTokens {
     pub export: Token,
     pub r#type: Token,
@@ -28,7 +30,7 @@ impl std::ops::DerefMut for ExportTypeFunctionTokens {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExportTypeFunctionStatement {
     identifier: Identifier,
     block: Block,
@@ -37,6 +39,7 @@ pub struct ExportTypeFunctionStatement {
     variadic_type: Option<FunctionVariadicType>,
     return_type: Option<FunctionReturnType>,
     generic_parameters: Option<GenericParameters>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<Box<ExportTypeFunctionTokens>>,
 }
 
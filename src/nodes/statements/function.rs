@@ -1,9 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{
     Block, FunctionBodyTokens, FunctionReturnType, FunctionVariadicType, GenericParameters,
     Identifier, Token, TypedIdentifier,
 };
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionNameTokens {
     pub periods: Vec<Token>,
     pub colon: Option<Token>,
@@ -13,11 +15,12 @@ impl FunctionNameTokens {
     super::impl_token_fns!(iter = [periods, colon]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionName {
     name: Identifier,
     field_names: Vec<Identifier>,
     method: Option<Identifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<FunctionNameTokens>,
 }
 
@@ -112,7 +115,7 @@ impl FunctionName {
     super::impl_token_fns!(iter = [tokens, field_names, method]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionStatement {
     name: FunctionName,
     block: Block,
@@ -121,6 +124,7 @@ pub struct FunctionStatement {
     variadic_type: Option<FunctionVariadicType>,
     return_type: Option<FunctionReturnType>,
     generic_parameters: Option<GenericParameters>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<Box<FunctionBodyTokens>>,
 }
 
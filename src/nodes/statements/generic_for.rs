@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Block, Expression, Token, TypedIdentifier};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericForTokens {
     pub r#for: Token,
     pub r#in: Token,
@@ -17,11 +19,12 @@ impl GenericForTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericForStatement {
     identifiers: Vec<TypedIdentifier>,
     expressions: Vec<Expression>,
     block: Block,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<GenericForTokens>,
 }
 
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::nodes::{Identifier, Token};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GotoTokens {
+    pub goto: Token,
+}
+
+impl GotoTokens {
+    super::impl_token_fns!(target = [goto]);
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GotoStatement {
+    label: Identifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tokens: Option<GotoTokens>,
+}
+
+impl GotoStatement {
+    pub fn new<S: Into<Identifier>>(label: S) -> Self {
+        Self {
+            label: label.into(),
+            tokens: None,
+        }
+    }
+
+    #[inline]
+    pub fn get_label(&self) -> &Identifier {
+        &self.label
+    }
+
+    #[inline]
+    pub fn mutate_label(&mut self) -> &mut Identifier {
+        &mut self.label
+    }
+
+    pub fn with_tokens(mut self, tokens: GotoTokens) -> Self {
+        self.tokens = Some(tokens);
+        self
+    }
+
+    #[inline]
+    pub fn set_tokens(&mut self, tokens: GotoTokens) {
+        self.tokens = Some(tokens);
+    }
+
+    #[inline]
+    pub fn get_tokens(&self) -> Option<&GotoTokens> {
+        self.tokens.as_ref()
+    }
+
+    #[inline]
+    pub fn mutate_tokens(&mut self) -> Option<&mut GotoTokens> {
+        self.tokens.as_mut()
+    }
+
+    super::impl_token_fns!(
+        target = [label]
+        iter = [tokens]
+    );
+}
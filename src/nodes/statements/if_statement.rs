@@ -1,8 +1,10 @@
 use std::mem;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Block, Expression, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfBranchTokens {
     pub elseif: Token,
     pub then: Token,
@@ -12,10 +14,11 @@ impl IfBranchTokens {
     super::impl_token_fns!(target = [elseif, then]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfBranch {
     condition: Expression,
     block: Block,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<IfBranchTokens>,
 }
 
@@ -79,7 +82,7 @@ impl IfBranch {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfStatementTokens {
     pub r#if: Token,
     pub then: Token,
@@ -94,10 +97,11 @@ impl IfStatementTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfStatement {
     branches: Vec<IfBranch>,
     else_block: Option<Block>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<IfStatementTokens>,
 }
 
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::nodes::{Identifier, Token};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelTokens {
+    pub left_colons: Token,
+    pub right_colons: Token,
+}
+
+impl LabelTokens {
+    super::impl_token_fns!(target = [left_colons, right_colons]);
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelStatement {
+    name: Identifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tokens: Option<LabelTokens>,
+}
+
+impl LabelStatement {
+    pub fn new<S: Into<Identifier>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            tokens: None,
+        }
+    }
+
+    #[inline]
+    pub fn get_name(&self) -> &Identifier {
+        &self.name
+    }
+
+    #[inline]
+    pub fn mutate_name(&mut self) -> &mut Identifier {
+        &mut self.name
+    }
+
+    pub fn with_tokens(mut self, tokens: LabelTokens) -> Self {
+        self.tokens = Some(tokens);
+        self
+    }
+
+    #[inline]
+    pub fn set_tokens(&mut self, tokens: LabelTokens) {
+        self.tokens = Some(tokens);
+    }
+
+    #[inline]
+    pub fn get_tokens(&self) -> Option<&LabelTokens> {
+        self.tokens.as_ref()
+    }
+
+    #[inline]
+    pub fn mutate_tokens(&mut self) -> Option<&mut LabelTokens> {
+        self.tokens.as_mut()
+    }
+
+    super::impl_token_fns!(
+        target = [name]
+        iter = [tokens]
+    );
+}
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReturnTokens {
     pub r#return: Token,
     pub commas: Vec<Token>,
@@ -13,9 +15,10 @@ impl ReturnTokens {
     );
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReturnStatement {
     expressions: Vec<Expression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<ReturnTokens>,
 }
 
@@ -96,7 +99,8 @@ impl ReturnStatement {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LastStatement {
     Break(Option<Token>),
     Continue(Option<Token>),
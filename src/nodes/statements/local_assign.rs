@@ -1,6 +1,8 @@
-use crate::nodes::{Expression, Token, TypedIdentifier};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+use crate::nodes::{Expression, LocalAttribute, Token, TypedIdentifier};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LocalAssignTokens {
     pub local: Token,
     pub equal: Option<Token>,
@@ -15,10 +17,12 @@ impl LocalAssignTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LocalAssignStatement {
     variables: Vec<TypedIdentifier>,
+    attributes: Vec<Option<LocalAttribute>>,
     values: Vec<Expression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<LocalAssignTokens>,
 }
 
@@ -26,6 +30,7 @@ impl LocalAssignStatement {
     pub fn new(variables: Vec<TypedIdentifier>, values: Vec<Expression>) -> Self {
         Self {
             variables,
+            attributes: Vec::new(),
             values,
             tokens: None,
         }
@@ -34,6 +39,7 @@ impl LocalAssignStatement {
     pub fn from_variable<S: Into<TypedIdentifier>>(variable: S) -> Self {
         Self {
             variables: vec![variable.into()],
+            attributes: Vec::new(),
             values: Vec::new(),
             tokens: None,
         }
@@ -69,6 +75,50 @@ impl LocalAssignStatement {
         self
     }
 
+    pub fn with_attribute(mut self, index: usize, attribute: impl Into<LocalAttribute>) -> Self {
+        self.set_attribute(index, attribute);
+        self
+    }
+
+    /// Sets the attribute (like `<const>` or `<close>`) of the variable at the given index.
+    pub fn set_attribute(&mut self, index: usize, attribute: impl Into<LocalAttribute>) {
+        if index >= self.variables.len() {
+            return;
+        }
+        if self.attributes.len() <= index {
+            self.attributes.resize(index + 1, None);
+        }
+        self.attributes[index] = Some(attribute.into());
+    }
+
+    #[inline]
+    pub fn get_attribute(&self, index: usize) -> Option<&LocalAttribute> {
+        self.attributes.get(index).and_then(Option::as_ref)
+    }
+
+    pub fn remove_attribute(&mut self, index: usize) -> Option<LocalAttribute> {
+        self.attributes.get_mut(index).and_then(Option::take)
+    }
+
+    /// Returns an iterator with the attribute of each variable, in the same order
+    /// as the variables.
+    pub fn iter_attributes(&self) -> impl Iterator<Item = Option<&LocalAttribute>> {
+        let mut attributes = self.attributes.iter();
+        self.variables
+            .iter()
+            .map(move |_| attributes.next().and_then(Option::as_ref))
+    }
+
+    #[inline]
+    pub fn has_attributes(&self) -> bool {
+        self.attributes.iter().any(Option::is_some)
+    }
+
+    #[inline]
+    pub fn clear_attributes(&mut self) {
+        self.attributes.clear();
+    }
+
     pub fn into_assignments(self) -> (Vec<TypedIdentifier>, Vec<Expression>) {
         (self.variables, self.values)
     }
@@ -186,6 +236,10 @@ impl LocalAssignStatement {
         if len > 1 && index < len {
             let variable = self.variables.remove(index);
 
+            if index < self.attributes.len() {
+                self.attributes.remove(index);
+            }
+
             if let Some(tokens) = &mut self.tokens {
                 if index < tokens.variable_commas.len() {
                     tokens.variable_commas.remove(index);
@@ -219,7 +273,10 @@ impl LocalAssignStatement {
         }
     }
 
-    super::impl_token_fns!(iter = [variables, tokens]);
+    super::impl_token_fns!(
+        iter = [variables, tokens]
+        iter_flatten = [attributes]
+    );
 }
 
 #[cfg(test)]
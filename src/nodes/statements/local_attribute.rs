@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::nodes::Token;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalAttributeKind {
+    Const,
+    Close,
+}
+
+impl LocalAttributeKind {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Const => "const",
+            Self::Close => "close",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalAttributeTokens {
+    pub opening_bracket: Token,
+    pub name: Token,
+    pub closing_bracket: Token,
+}
+
+impl LocalAttributeTokens {
+    super::impl_token_fns!(target = [opening_bracket, name, closing_bracket]);
+}
+
+/// An attribute attached to a local variable declaration, like `<const>` or `<close>`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalAttribute {
+    kind: LocalAttributeKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tokens: Option<LocalAttributeTokens>,
+}
+
+impl LocalAttribute {
+    pub fn new(kind: LocalAttributeKind) -> Self {
+        Self { kind, tokens: None }
+    }
+
+    pub fn constant() -> Self {
+        Self::new(LocalAttributeKind::Const)
+    }
+
+    pub fn close() -> Self {
+        Self::new(LocalAttributeKind::Close)
+    }
+
+    #[inline]
+    pub fn kind(&self) -> LocalAttributeKind {
+        self.kind
+    }
+
+    #[inline]
+    pub fn is_const(&self) -> bool {
+        self.kind == LocalAttributeKind::Const
+    }
+
+    #[inline]
+    pub fn is_close(&self) -> bool {
+        self.kind == LocalAttributeKind::Close
+    }
+
+    pub fn with_tokens(mut self, tokens: LocalAttributeTokens) -> Self {
+        self.tokens = Some(tokens);
+        self
+    }
+
+    #[inline]
+    pub fn set_tokens(&mut self, tokens: LocalAttributeTokens) {
+        self.tokens = Some(tokens);
+    }
+
+    #[inline]
+    pub fn get_tokens(&self) -> Option<&LocalAttributeTokens> {
+        self.tokens.as_ref()
+    }
+
+    #[inline]
+    pub fn mutate_tokens(&mut self) -> Option<&mut LocalAttributeTokens> {
+        self.tokens.as_mut()
+    }
+
+    super::impl_token_fns!(iter = [tokens]);
+}
+
+impl From<LocalAttributeKind> for LocalAttribute {
+    fn from(kind: LocalAttributeKind) -> Self {
+        Self::new(kind)
+    }
+}
@@ -4,9 +4,12 @@ mod do_statement;
 mod export_type_function;
 mod function;
 mod generic_for;
+mod goto_statement;
 mod if_statement;
+mod label_statement;
 mod last_statement;
 mod local_assign;
+mod local_attribute;
 mod local_function;
 mod numeric_for;
 mod repeat_statement;
@@ -20,9 +23,12 @@ pub use do_statement::*;
 pub use export_type_function::*;
 pub use function::*;
 pub use generic_for::*;
+pub use goto_statement::*;
 pub use if_statement::*;
+pub use label_statement::*;
 pub use last_statement::*;
 pub use local_assign::*;
+pub use local_attribute::*;
 pub use local_function::*;
 pub use numeric_for::*;
 pub use repeat_statement::*;
@@ -30,11 +36,14 @@ pub use type_declaration::*;
 pub use type_function::*;
 pub use while_statement::*;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::FunctionCall;
 
 use super::impl_token_fns;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Statement {
     Assign(AssignStatement),
     Do(DoStatement),
@@ -42,7 +51,9 @@ pub enum Statement {
     CompoundAssign(CompoundAssignStatement),
     Function(FunctionStatement),
     GenericFor(GenericForStatement),
+    Goto(GotoStatement),
     If(IfStatement),
+    Label(LabelStatement),
     LocalAssign(LocalAssignStatement),
     LocalFunction(LocalFunctionStatement),
     ExportTypeFunction(ExportTypeFunctionStatement),
@@ -89,12 +100,24 @@ impl From<GenericForStatement> for Statement {
     }
 }
 
+impl From<GotoStatement> for Statement {
+    fn from(goto: GotoStatement) -> Statement {
+        Statement::Goto(goto)
+    }
+}
+
 impl From<IfStatement> for Statement {
     fn from(if_statement: IfStatement) -> Statement {
         Statement::If(if_statement)
     }
 }
 
+impl From<LabelStatement> for Statement {
+    fn from(label: LabelStatement) -> Statement {
+        Statement::Label(label)
+    }
+}
+
 impl From<LocalAssignStatement> for Statement {
     fn from(assign: LocalAssignStatement) -> Statement {
         Statement::LocalAssign(assign)
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Block, Expression, Token, TypedIdentifier};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NumericForTokens {
     pub r#for: Token,
     pub equal: Token,
@@ -17,13 +19,14 @@ impl NumericForTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NumericForStatement {
     identifier: TypedIdentifier,
     start: Expression,
     end: Expression,
     step: Option<Expression>,
     block: Block,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<NumericForTokens>,
 }
 
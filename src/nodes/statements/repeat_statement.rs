@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Block, Expression, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RepeatTokens {
     pub repeat: Token,
     pub until: Token,
@@ -10,10 +12,11 @@ impl RepeatTokens {
     super::impl_token_fns!(target = [repeat, until]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RepeatStatement {
     block: Block,
     condition: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<RepeatTokens>,
 }
 
@@ -1,8 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{
     GenericParameterMutRef, GenericParametersWithDefaults, Identifier, Token, Trivia, Type,
 };
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeDeclarationTokens {
     pub r#type: Token,
     pub equal: Token,
@@ -16,12 +18,13 @@ impl TypeDeclarationTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeDeclarationStatement {
     name: Identifier,
     r#type: Type,
     exported: bool,
     generic_parameters: Option<GenericParametersWithDefaults>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TypeDeclarationTokens>,
 }
 
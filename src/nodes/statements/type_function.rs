@@ -1,9 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{
     Block, FunctionBodyTokens, FunctionReturnType, FunctionVariadicType, GenericParameters,
     Identifier, Token, TypedIdentifier,
 };
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeFunctionTokens {
     pub r#type: Token,
     pub function_body: FunctionBodyTokens,
@@ -27,7 +29,7 @@ impl std::ops::DerefMut for TypeFunctionTokens {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeFunctionStatement {
     identifier: Identifier,
     block: Block,
@@ -36,6 +38,7 @@ pub struct TypeFunctionStatement {
     variadic_type: Option<FunctionVariadicType>,
     return_type: Option<FunctionReturnType>,
     generic_parameters: Option<GenericParameters>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<Box<TypeFunctionTokens>>,
 }
 
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{token::Token, Block, Expression};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WhileTokens {
     pub r#while: Token,
     pub r#do: Token,
@@ -11,10 +13,11 @@ impl WhileTokens {
     super::impl_token_fns!(target = [r#while, r#do, end]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WhileStatement {
     block: Block,
     condition: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<WhileTokens>,
 }
 
@@ -1,7 +1,12 @@
 use std::borrow::Cow;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Position {
+    /// Refers to a slice of the original code, which is not available once serialized.
+    #[serde(skip)]
     LineNumberReference {
         start: usize,
         end: usize,
@@ -10,6 +15,8 @@ pub enum Position {
     LineNumber {
         content: Cow<'static, str>,
         line_number: usize,
+        /// The zero-based column where the content started in its original code, if known
+        column: Option<usize>,
     },
     Any {
         content: Cow<'static, str>,
@@ -22,11 +29,29 @@ impl Position {
         Self::LineNumber {
             content: content.into(),
             line_number,
+            column: None,
+        }
+    }
+
+    /// Returns the zero-based column where the position starts. The original code is
+    /// needed to find the column of positions that refer to it.
+    pub fn get_column(&self, code: &str) -> Option<usize> {
+        match self {
+            Self::LineNumberReference { start, .. } => column_at(code, *start),
+            Self::LineNumber { column, .. } => *column,
+            Self::Any { .. } => None,
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+fn column_at(code: &str, offset: usize) -> Option<usize> {
+    let before = code.get(..offset)?;
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    Some(before[line_start..].chars().count())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TriviaKind {
     Comment,
     Whitespace,
@@ -54,7 +79,7 @@ impl TriviaKind {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Trivia {
     position: Position,
     kind: TriviaKind,
@@ -90,9 +115,14 @@ impl Trivia {
             Position::Any { .. } => None,
         }
     }
+
+    #[inline]
+    pub fn get_position(&self) -> &Position {
+        &self.position
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Token {
     position: Position,
     leading_trivia: Vec<Trivia>,
@@ -186,15 +216,29 @@ impl Token {
         }
     }
 
+    #[inline]
+    pub fn get_position(&self) -> &Position {
+        &self.position
+    }
+
     pub fn replace_with_content<IntoCowStr: Into<Cow<'static, str>>>(
         &mut self,
         content: IntoCowStr,
     ) {
         self.position = match &self.position {
-            Position::LineNumber { line_number, .. }
-            | Position::LineNumberReference { line_number, .. } => Position::LineNumber {
+            Position::LineNumber {
+                line_number,
+                column,
+                ..
+            } => Position::LineNumber {
+                line_number: *line_number,
+                content: content.into(),
+                column: *column,
+            },
+            Position::LineNumberReference { line_number, .. } => Position::LineNumber {
                 line_number: *line_number,
                 content: content.into(),
+                column: None,
             },
 
             Position::Any { .. } => Position::Any {
@@ -238,6 +282,7 @@ impl Token {
                     .expect("unable to extract code from position")
                     .to_owned()
                     .into(),
+                column: column_at(code, start),
             }
         };
         for trivia in self
@@ -258,6 +303,7 @@ impl Token {
                         .expect("unable to extract code from position")
                         .to_owned()
                         .into(),
+                    column: column_at(code, start),
                 }
             };
         }
@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Identifier, Token, Type};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypedIdentifier {
     name: Identifier,
     r#type: Option<Type>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -109,6 +112,7 @@ mod test {
             &Token::from_position(Position::LineNumber {
                 line_number: 1,
                 content: "newVar".into(),
+                column: None,
             })
         );
     }
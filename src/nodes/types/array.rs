@@ -1,10 +1,13 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::Type;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArrayType {
     inner_type: Box<Type>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<ArrayTypeTokens>,
 }
 
@@ -42,7 +45,7 @@ impl ArrayType {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArrayTypeTokens {
     pub opening_brace: Token,
     pub closing_brace: Token,
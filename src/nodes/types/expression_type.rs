@@ -1,8 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Expression, Token};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExpressionType {
     expression: Box<Expression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<ExpressionTypeTokens>,
 }
 
@@ -42,7 +45,7 @@ impl ExpressionType {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExpressionTypeTokens {
     pub r#typeof: Token,
     pub opening_parenthese: Token,
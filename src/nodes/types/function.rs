@@ -1,11 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Identifier, Token};
 
 use super::{GenericParameters, GenericTypePack, Type, TypePack, VariadicTypePack};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionArgumentType {
     argument_type: Type,
     name: Option<Identifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -71,7 +74,8 @@ impl FunctionArgumentType {
     super::impl_token_fns!(iter = [name, token]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FunctionReturnType {
     Type(Box<Type>),
     TypePack(TypePack),
@@ -108,7 +112,8 @@ impl From<VariadicTypePack> for FunctionReturnType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum VariadicArgumentType {
     GenericTypePack(GenericTypePack),
     VariadicTypePack(VariadicTypePack),
@@ -126,12 +131,13 @@ impl From<VariadicTypePack> for VariadicArgumentType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionType {
     arguments: Vec<FunctionArgumentType>,
     variadic_argument_type: Option<VariadicArgumentType>,
     return_type: FunctionReturnType,
     generic_parameters: Option<GenericParameters>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<FunctionTypeTokens>,
 }
 
@@ -247,7 +253,7 @@ impl FunctionType {
     super::impl_token_fns!(iter = [tokens, generic_parameters, arguments]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionTypeTokens {
     pub opening_parenthese: Token,
     pub closing_parenthese: Token,
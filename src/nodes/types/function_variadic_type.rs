@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use super::{GenericTypePack, Type};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FunctionVariadicType {
     Type(Type),
     GenericTypePack(GenericTypePack),
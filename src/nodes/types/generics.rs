@@ -1,13 +1,16 @@
 use std::marker::PhantomData;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Identifier, Token, TypePack, VariadicTypePack};
 
 use super::Type;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericTypePack {
     // name ...
     name: Identifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -50,11 +53,12 @@ impl GenericTypePack {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericParameters {
     // generic type list
     type_variables: Vec<Identifier>,
     generic_type_packs: Vec<GenericTypePack>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<GenericParametersTokens>,
 }
 
@@ -141,7 +145,7 @@ impl GenericParameters {
     super::impl_token_fns!(iter = [type_variables, generic_type_packs, tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericParametersTokens {
     pub opening_list: Token,
     pub closing_list: Token,
@@ -155,7 +159,8 @@ impl GenericParametersTokens {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GenericTypePackDefault {
     TypePack(TypePack),
     VariadicTypePack(VariadicTypePack),
@@ -180,11 +185,12 @@ impl From<GenericTypePack> for GenericTypePackDefault {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericTypePackWithDefault {
     generic_type_pack: GenericTypePack,
     default: GenericTypePackDefault,
     // equal sign token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -238,11 +244,12 @@ impl GenericTypePackWithDefault {
     super::impl_token_fns!(iter = [token]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeVariableWithDefault {
     variable: Identifier,
     default: Type,
     // equal sign token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -296,11 +303,12 @@ impl TypeVariableWithDefault {
     );
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericParametersWithDefaults {
     type_variables: Vec<Identifier>,
     middle: GenericParametersWithDefaultsMiddle,
     generic_type_packs_with_default: Vec<GenericTypePackWithDefault>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<GenericParametersTokens>,
 }
 
@@ -732,7 +740,8 @@ impl<'a> IntoIterator for &'a GenericParametersWithDefaults {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 enum GenericParametersWithDefaultsMiddle {
     Empty,
     GenericTypePacks(Vec<GenericTypePack>),
@@ -1,13 +1,16 @@
 use std::iter;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::Type;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IntersectionType {
     types: Vec<Type>,
     leading_operator: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<IntersectionTypeTokens>,
 }
 
@@ -132,7 +135,7 @@ impl iter::FromIterator<Type> for IntersectionType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IntersectionTypeTokens {
     pub leading_token: Option<Token>,
     pub separators: Vec<Token>,
@@ -30,11 +30,14 @@ pub use type_pack::*;
 pub use union::*;
 pub use variadic_type_pack::*;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::impl_token_fns;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Type {
     Name(TypeName),
     Field(TypeField),
@@ -1,10 +1,13 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::Type;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OptionalType {
     inner_type: Box<Type>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
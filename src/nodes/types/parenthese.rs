@@ -1,10 +1,13 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::Type;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParentheseType {
     inner_type: Box<Type>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<ParentheseTypeTokens>,
 }
 
@@ -54,7 +57,7 @@ impl ParentheseType {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParentheseTypeTokens {
     pub left_parenthese: Token,
     pub right_parenthese: Token,
@@ -1,11 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Identifier, Token, Trivia};
 
 use super::{StringType, Type};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableIndexerType {
     key_type: Type,
     value_type: Type,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TableIndexTypeTokens>,
 }
 
@@ -56,7 +59,7 @@ impl TableIndexerType {
     super::impl_token_fns!(iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableIndexTypeTokens {
     pub opening_bracket: Token,
     pub closing_bracket: Token,
@@ -67,10 +70,11 @@ impl TableIndexTypeTokens {
     super::impl_token_fns!(target = [opening_bracket, closing_bracket, colon]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TablePropertyType {
     property: Identifier,
     r#type: Type,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
@@ -121,10 +125,11 @@ impl TablePropertyType {
     super::impl_token_fns!(target = [property] iter = [token]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableLiteralPropertyType {
     string: StringType,
     r#type: Type,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TableIndexTypeTokens>,
 }
 
@@ -175,7 +180,8 @@ impl TableLiteralPropertyType {
     super::impl_token_fns!(target = [string] iter = [tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TableEntryType {
     Property(TablePropertyType),
     Literal(TableLiteralPropertyType),
@@ -242,9 +248,10 @@ impl From<TableIndexerType> for TableEntryType {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableType {
     entries: Vec<TableEntryType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TableTypeTokens>,
 }
 
@@ -359,7 +366,7 @@ impl TableType {
     super::impl_token_fns!(iter = [entries, tokens]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableTypeTokens {
     pub opening_brace: Token,
     pub closing_brace: Token,
@@ -1,11 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Identifier, Token};
 
 use super::TypeName;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeField {
     namespace: Identifier,
     name: TypeName,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     token: Option<Token>,
 }
 
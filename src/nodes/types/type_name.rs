@@ -1,10 +1,12 @@
 use std::iter::FromIterator;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{Identifier, Token};
 
 use super::{GenericTypePack, Type, TypePack, VariadicTypePack};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeName {
     type_name: Identifier,
     type_parameters: Option<TypeParameters>,
@@ -67,9 +69,10 @@ impl TypeName {
     super::impl_token_fns!(target = [type_name] iter = [type_parameters]);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeParameters {
     parameters: Vec<TypeParameter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TypeParametersTokens>,
 }
 
@@ -164,7 +167,8 @@ impl<'a> IntoIterator for &'a TypeParameters {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TypeParameter {
     Type(Type),
     TypePack(TypePack),
@@ -201,7 +205,7 @@ impl From<GenericTypePack> for TypeParameter {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeParametersTokens {
     pub opening_list: Token,
     pub closing_list: Token,
@@ -1,13 +1,16 @@
 use std::iter::FromIterator;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::Token;
 
 use super::{Type, VariadicArgumentType};
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypePack {
     types: Vec<Type>,
     variadic_type: Option<VariadicArgumentType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     tokens: Option<TypePackTokens>,
 }
 
@@ -125,7 +128,7 @@ impl<'a> IntoIterator for &'a TypePack {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypePackTokens {
     pub left_parenthese: Token,
     pub right_parenthese: Token,
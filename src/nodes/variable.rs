@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{FieldExpression, Identifier, IndexExpression};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Variable {
     Identifier(Identifier),
     Field(Box<FieldExpression>),
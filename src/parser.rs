@@ -1,6 +1,7 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 use full_moon::ast::Ast;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     ast_converter::{AstConverter, ConvertError},
@@ -8,15 +9,72 @@ use crate::{
     utils::Timer,
 };
 
+/// The Lua dialect that the [`Parser`](struct.Parser.html) accepts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LuaVersion {
+    #[default]
+    Luau,
+    Lua51,
+    Lua52,
+    Lua53,
+    Lua54,
+}
+
+impl LuaVersion {
+    fn to_full_moon_version(self) -> full_moon::LuaVersion {
+        match self {
+            Self::Luau => full_moon::LuaVersion::luau(),
+            Self::Lua51 => full_moon::LuaVersion::lua51(),
+            Self::Lua52 => full_moon::LuaVersion::lua52(),
+            Self::Lua53 => full_moon::LuaVersion::lua53(),
+            Self::Lua54 => full_moon::LuaVersion::lua54(),
+        }
+    }
+}
+
+impl FromStr for LuaVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "luau" => Self::Luau,
+            "lua51" => Self::Lua51,
+            "lua52" => Self::Lua52,
+            "lua53" => Self::Lua53,
+            "lua54" => Self::Lua54,
+            _ => return Err(format!("invalid lua version `{}`", s)),
+        })
+    }
+}
+
+impl fmt::Display for LuaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Luau => "luau",
+                Self::Lua51 => "lua51",
+                Self::Lua52 => "lua52",
+                Self::Lua53 => "lua53",
+                Self::Lua54 => "lua54",
+            }
+        )
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Parser {
     hold_token_data: bool,
+    lua_version: LuaVersion,
 }
 
 impl Parser {
     pub fn parse(&self, code: &str) -> Result<Block, ParserError> {
         let full_moon_parse_timer = Timer::now();
-        let parse_result = full_moon::parse(code);
+        let parse_result =
+            full_moon::parse_fallible(code, self.lua_version.to_full_moon_version()).into_result();
         log::trace!(
             "full-moon parsing done in {}",
             full_moon_parse_timer.duration_label()
@@ -42,6 +100,21 @@ impl Parser {
         self.hold_token_data
     }
 
+    pub fn with_lua_version(mut self, lua_version: LuaVersion) -> Self {
+        self.lua_version = lua_version;
+        self
+    }
+
+    #[inline]
+    pub fn set_lua_version(&mut self, lua_version: LuaVersion) {
+        self.lua_version = lua_version;
+    }
+
+    #[inline]
+    pub fn get_lua_version(&self) -> LuaVersion {
+        self.lua_version
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn convert_ast(&self, ast: Ast) -> Result<Block, ConvertError> {
         AstConverter::new(self.hold_token_data).convert(&ast)
@@ -510,6 +583,108 @@ mod test {
         ),
     );
 
+    mod lua54 {
+        use super::*;
+
+        macro_rules! test_parse_lua54 {
+            ($($name:ident($input:literal) => $value:expr),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let parser = Parser::default().with_lua_version(LuaVersion::Lua54);
+                        let block = parser.parse($input)
+                            .expect(&format!("failed to parse `{}`", $input));
+
+                        let expect_block = $value.into();
+                        pretty_assertions::assert_eq!(block, expect_block);
+                    }
+                )*
+            };
+        }
+
+        test_parse_lua54!(
+            goto_statement("goto continue") => GotoStatement::new("continue"),
+            label_statement("::continue::") => LabelStatement::new("continue"),
+            local_const("local var <const> = true") => LocalAssignStatement::from_variable("var")
+                .with_attribute(0, LocalAttribute::constant())
+                .with_value(true),
+            local_close("local var <close> = nil") => LocalAssignStatement::from_variable("var")
+                .with_attribute(0, LocalAttribute::close())
+                .with_value(Expression::nil()),
+            local_attribute_on_second_variable("local a, b <const> = 1, 2") => LocalAssignStatement::from_variable("a")
+                .with_variable("b")
+                .with_attribute(1, LocalAttributeKind::Const)
+                .with_value(1)
+                .with_value(2),
+            return_bitwise_and("return a & b") => ReturnStatement::one(BinaryExpression::new(
+                BinaryOperator::BitwiseAnd,
+                Expression::identifier("a"),
+                Expression::identifier("b"),
+            )),
+            return_bitwise_or("return a | b") => ReturnStatement::one(BinaryExpression::new(
+                BinaryOperator::BitwiseOr,
+                Expression::identifier("a"),
+                Expression::identifier("b"),
+            )),
+            return_bitwise_xor("return a ~ b") => ReturnStatement::one(BinaryExpression::new(
+                BinaryOperator::BitwiseXor,
+                Expression::identifier("a"),
+                Expression::identifier("b"),
+            )),
+            return_left_shift("return a << b") => ReturnStatement::one(BinaryExpression::new(
+                BinaryOperator::LeftShift,
+                Expression::identifier("a"),
+                Expression::identifier("b"),
+            )),
+            return_right_shift("return a >> b") => ReturnStatement::one(BinaryExpression::new(
+                BinaryOperator::RightShift,
+                Expression::identifier("a"),
+                Expression::identifier("b"),
+            )),
+            return_bitwise_not("return ~a") => ReturnStatement::one(UnaryExpression::new(
+                UnaryOperator::BitwiseNot,
+                Expression::identifier("a"),
+            )),
+            return_bitwise_or_of_bitwise_and("return a | b & c") => ReturnStatement::one(BinaryExpression::new(
+                BinaryOperator::BitwiseOr,
+                Expression::identifier("a"),
+                BinaryExpression::new(
+                    BinaryOperator::BitwiseAnd,
+                    Expression::identifier("b"),
+                    Expression::identifier("c"),
+                ),
+            )),
+        );
+
+        #[test]
+        fn default_parser_rejects_goto() {
+            assert!(Parser::default().parse("goto continue").is_err());
+        }
+
+        #[test]
+        fn lua51_parser_rejects_bitwise_operators() {
+            assert!(Parser::default()
+                .with_lua_version(LuaVersion::Lua51)
+                .parse("return a & b")
+                .is_err());
+        }
+
+        #[test]
+        fn lua52_parser_accepts_goto() {
+            assert!(Parser::default()
+                .with_lua_version(LuaVersion::Lua52)
+                .parse("goto continue ::continue::")
+                .is_ok());
+        }
+
+        #[test]
+        fn parse_lua_version_from_str() {
+            assert_eq!(LuaVersion::from_str("lua54"), Ok(LuaVersion::Lua54));
+            assert_eq!(LuaVersion::from_str("luau"), Ok(LuaVersion::Luau));
+            assert!(LuaVersion::from_str("lua60").is_err());
+        }
+    }
+
     mod parse_with_tokens {
         use super::*;
 
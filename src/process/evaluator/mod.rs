@@ -333,6 +333,13 @@ impl Evaluator {
             BinaryOperator::GreaterOrEqualThan => {
                 self.evaluate_relational(expression, |a, b| a >= b)
             }
+            // bitwise operators work on integers, which can't be
+            // represented exactly with the evaluator numbers
+            BinaryOperator::BitwiseAnd
+            | BinaryOperator::BitwiseOr
+            | BinaryOperator::BitwiseXor
+            | BinaryOperator::LeftShift
+            | BinaryOperator::RightShift => LuaValue::Unknown,
         }
     }
 
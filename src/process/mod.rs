@@ -8,6 +8,7 @@ mod node_processor;
 mod post_visitor;
 pub mod processors;
 mod scope_visitor;
+mod target_validator;
 pub(crate) mod utils;
 mod visitors;
 
@@ -17,6 +18,7 @@ pub(crate) use expression_serializer::*;
 pub use node_counter::NodeCounter;
 pub use node_processor::{NodePostProcessor, NodeProcessor};
 pub use post_visitor::{DefaultPostVisitor, NodePostVisitor};
-pub(crate) use scope_visitor::IdentifierTracker;
+pub(crate) use scope_visitor::{IdentifierTracker, VariableTracker};
 pub use scope_visitor::{Scope, ScopePostVisitor, ScopeVisitor};
+pub(crate) use target_validator::{TargetValidator, TargetViolation};
 pub use visitors::{DefaultVisitor, NodeVisitor};
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::DerefMut;
 
 use crate::nodes::*;
@@ -531,6 +531,66 @@ impl Scope for IdentifierTracker {
     }
 }
 
+/// Associates each variable declaration with a unique index. Since the declarations
+/// are always visited in the same order, the indexes can be used to match variables
+/// between different visits of the same block.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VariableTracker {
+    scopes: Vec<HashMap<String, usize>>,
+    next_index: usize,
+}
+
+impl VariableTracker {
+    pub fn declare(&mut self, identifier: &str) -> usize {
+        let index = self.next_index;
+        self.next_index += 1;
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(identifier.to_owned(), index);
+        } else {
+            let mut scope = HashMap::new();
+            scope.insert(identifier.to_owned(), index);
+            self.scopes.push(scope);
+        }
+
+        index
+    }
+
+    pub fn resolve(&self, identifier: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(identifier))
+            .copied()
+    }
+}
+
+impl Scope for VariableTracker {
+    fn push(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn insert(&mut self, identifier: &mut String) {
+        self.declare(identifier);
+    }
+
+    fn insert_self(&mut self) {
+        self.declare("self");
+    }
+
+    fn insert_local(&mut self, identifier: &mut String, _value: Option<&mut Expression>) {
+        self.declare(identifier);
+    }
+
+    fn insert_local_function(&mut self, function: &mut LocalFunctionStatement) {
+        self.declare(function.get_name());
+    }
+}
+
 // implement Scope on anything that can deref into a Scope
 impl<T, U> Scope for T
 where
@@ -0,0 +1,394 @@
+use std::fmt;
+
+use crate::nodes::*;
+use crate::rules::{
+    may_use_generalized_iteration, CONVERT_LUAU_NUMBER_RULE_NAME,
+    REMOVE_COMPOUND_ASSIGNMENT_RULE_NAME, REMOVE_CONTINUE_RULE_NAME,
+    REMOVE_FLOOR_DIVISION_RULE_NAME, REMOVE_GENERALIZED_ITERATION_RULE_NAME,
+    REMOVE_IF_EXPRESSION_RULE_NAME, REMOVE_INTERPOLATED_STRING_RULE_NAME, REMOVE_TYPES_RULE_NAME,
+};
+use crate::utils::lines;
+use crate::LuaVersion;
+
+use super::{DefaultPostVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor};
+
+/// A kind of syntax that is not supported by every Lua version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum TargetViolationKind {
+    Continue,
+    CompoundAssignment,
+    IfExpression,
+    InterpolatedString,
+    FloorDivision,
+    TypeAnnotation,
+    TypeDeclaration,
+    TypeFunction,
+    GeneralizedIteration,
+    BinaryNumber,
+    Goto,
+    Label,
+    LocalAttribute,
+    BitwiseOperator,
+}
+
+impl TargetViolationKind {
+    fn is_supported_by(&self, target: LuaVersion) -> bool {
+        match self {
+            Self::Continue
+            | Self::CompoundAssignment
+            | Self::IfExpression
+            | Self::InterpolatedString
+            | Self::TypeAnnotation
+            | Self::TypeDeclaration
+            | Self::TypeFunction
+            | Self::GeneralizedIteration
+            | Self::BinaryNumber => target == LuaVersion::Luau,
+            Self::FloorDivision => matches!(
+                target,
+                LuaVersion::Luau | LuaVersion::Lua53 | LuaVersion::Lua54
+            ),
+            Self::Goto | Self::Label => matches!(
+                target,
+                LuaVersion::Lua52 | LuaVersion::Lua53 | LuaVersion::Lua54
+            ),
+            Self::BitwiseOperator => matches!(target, LuaVersion::Lua53 | LuaVersion::Lua54),
+            Self::LocalAttribute => target == LuaVersion::Lua54,
+        }
+    }
+
+    fn fix_rule_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Continue => Some(REMOVE_CONTINUE_RULE_NAME),
+            Self::CompoundAssignment => Some(REMOVE_COMPOUND_ASSIGNMENT_RULE_NAME),
+            Self::IfExpression => Some(REMOVE_IF_EXPRESSION_RULE_NAME),
+            Self::InterpolatedString => Some(REMOVE_INTERPOLATED_STRING_RULE_NAME),
+            Self::FloorDivision => Some(REMOVE_FLOOR_DIVISION_RULE_NAME),
+            Self::TypeAnnotation | Self::TypeDeclaration | Self::TypeFunction => {
+                Some(REMOVE_TYPES_RULE_NAME)
+            }
+            Self::GeneralizedIteration => Some(REMOVE_GENERALIZED_ITERATION_RULE_NAME),
+            Self::BinaryNumber => Some(CONVERT_LUAU_NUMBER_RULE_NAME),
+            Self::Goto | Self::Label | Self::LocalAttribute | Self::BitwiseOperator => None,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::Continue => "`continue` statement",
+            Self::CompoundAssignment => "compound assignment",
+            Self::IfExpression => "if expression",
+            Self::InterpolatedString => "interpolated string",
+            Self::FloorDivision => "floor division (`//`)",
+            Self::TypeAnnotation => "type annotation",
+            Self::TypeDeclaration => "type declaration",
+            Self::TypeFunction => "type function",
+            Self::GeneralizedIteration => "generalized iteration",
+            Self::BinaryNumber => "binary number literal",
+            Self::Goto => "`goto` statement",
+            Self::Label => "label statement",
+            Self::LocalAttribute => "local variable attribute",
+            Self::BitwiseOperator => "bitwise operator",
+        }
+    }
+}
+
+impl fmt::Display for TargetViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.description())?;
+        if let Some(rule_name) = self.fix_rule_name() {
+            write!(f, " (can be removed with `{}`)", rule_name)?;
+        }
+        Ok(())
+    }
+}
+
+/// A construct found in a block that cannot be generated for a given target.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct TargetViolation {
+    line: Option<usize>,
+    kind: TargetViolationKind,
+}
+
+impl fmt::Display for TargetViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: {}", line, self.kind)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+/// Finds the constructs of a block that are not supported by a given Lua version.
+///
+/// Line numbers are only available when the block was parsed with its tokens. When a
+/// node does not have a token, the line of its closest parent statement is used.
+pub(crate) struct TargetValidator {
+    target: LuaVersion,
+    violations: Vec<TargetViolation>,
+    statement_lines: Vec<Option<usize>>,
+    type_statement_depth: usize,
+}
+
+impl TargetValidator {
+    pub(crate) fn new(target: LuaVersion) -> Self {
+        Self {
+            target,
+            violations: Vec::new(),
+            statement_lines: Vec::new(),
+            type_statement_depth: 0,
+        }
+    }
+
+    pub(crate) fn validate(mut self, block: &mut Block) -> Vec<TargetViolation> {
+        DefaultPostVisitor::visit_block(block, &mut self);
+
+        let mut violations = self.violations;
+        violations.sort();
+        violations.dedup();
+        violations
+    }
+
+    fn current_line(&self) -> Option<usize> {
+        self.statement_lines.iter().rev().find_map(|line| *line)
+    }
+
+    fn report(&mut self, kind: TargetViolationKind, token: Option<&Token>) {
+        if !kind.is_supported_by(self.target) {
+            let line = token
+                .and_then(Token::get_line_number)
+                .or_else(|| self.current_line());
+            self.violations.push(TargetViolation { line, kind });
+        }
+    }
+}
+
+fn is_type_statement(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::TypeDeclaration(_)
+            | Statement::TypeFunction(_)
+            | Statement::ExportTypeFunction(_)
+    )
+}
+
+impl NodeProcessor for TargetValidator {
+    fn process_statement(&mut self, statement: &mut Statement) {
+        self.statement_lines.push(lines::statement_line(statement));
+
+        match statement {
+            Statement::TypeDeclaration(type_declaration) => {
+                let token = type_declaration
+                    .get_tokens()
+                    .map(|tokens| tokens.export.as_ref().unwrap_or(&tokens.r#type));
+                self.report(TargetViolationKind::TypeDeclaration, token);
+            }
+            Statement::TypeFunction(_) | Statement::ExportTypeFunction(_) => {
+                self.report(TargetViolationKind::TypeFunction, None);
+            }
+            _ => {}
+        }
+
+        if is_type_statement(statement) {
+            self.type_statement_depth += 1;
+        }
+    }
+
+    fn process_compound_assign_statement(&mut self, assign: &mut CompoundAssignStatement) {
+        let token = assign.get_tokens().map(|tokens| &tokens.operator);
+        self.report(TargetViolationKind::CompoundAssignment, token);
+    }
+
+    fn process_generic_for_statement(&mut self, generic_for: &mut GenericForStatement) {
+        if may_use_generalized_iteration(generic_for) {
+            let token = generic_for.get_tokens().map(|tokens| &tokens.r#in);
+            self.report(TargetViolationKind::GeneralizedIteration, token);
+        }
+    }
+
+    fn process_goto_statement(&mut self, goto: &mut GotoStatement) {
+        let token = goto.get_tokens().map(|tokens| &tokens.goto);
+        self.report(TargetViolationKind::Goto, token);
+    }
+
+    fn process_label_statement(&mut self, label: &mut LabelStatement) {
+        let token = label.get_tokens().map(|tokens| &tokens.left_colons);
+        self.report(TargetViolationKind::Label, token);
+    }
+
+    fn process_last_statement(&mut self, statement: &mut LastStatement) {
+        if let LastStatement::Continue(token) = statement {
+            self.report(TargetViolationKind::Continue, token.as_ref());
+        }
+    }
+
+    fn process_local_assign_statement(&mut self, local_assign: &mut LocalAssignStatement) {
+        for attribute in local_assign.iter_attributes().flatten() {
+            let token = attribute.get_tokens().map(|tokens| &tokens.opening_bracket);
+            self.report(TargetViolationKind::LocalAttribute, token);
+        }
+    }
+
+    fn process_binary_expression(&mut self, binary: &mut BinaryExpression) {
+        let kind = match binary.operator() {
+            BinaryOperator::DoubleSlash => TargetViolationKind::FloorDivision,
+            BinaryOperator::BitwiseAnd
+            | BinaryOperator::BitwiseOr
+            | BinaryOperator::BitwiseXor
+            | BinaryOperator::LeftShift
+            | BinaryOperator::RightShift => TargetViolationKind::BitwiseOperator,
+            _ => return,
+        };
+        self.report(kind, binary.get_token());
+    }
+
+    fn process_unary_expression(&mut self, unary: &mut UnaryExpression) {
+        if unary.operator() == UnaryOperator::BitwiseNot {
+            self.report(TargetViolationKind::BitwiseOperator, unary.get_token());
+        }
+    }
+
+    fn process_if_expression(&mut self, if_expression: &mut IfExpression) {
+        let token = if_expression.get_tokens().map(|tokens| &tokens.r#if);
+        self.report(TargetViolationKind::IfExpression, token);
+    }
+
+    fn process_interpolated_string_expression(
+        &mut self,
+        string: &mut InterpolatedStringExpression,
+    ) {
+        let token = string.get_tokens().map(|tokens| &tokens.opening_tick);
+        self.report(TargetViolationKind::InterpolatedString, token);
+    }
+
+    fn process_number_expression(&mut self, number: &mut NumberExpression) {
+        if matches!(number, NumberExpression::Binary(_)) {
+            self.report(TargetViolationKind::BinaryNumber, number.get_token());
+        }
+    }
+
+    fn process_type(&mut self, _: &mut Type) {
+        if self.type_statement_depth == 0 {
+            self.report(TargetViolationKind::TypeAnnotation, None);
+        }
+    }
+}
+
+impl NodePostProcessor for TargetValidator {
+    fn process_after_statement(&mut self, statement: &mut Statement) {
+        self.statement_lines.pop();
+
+        if is_type_statement(statement) {
+            self.type_statement_depth -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::Parser;
+
+    fn validate(code: &str, target: LuaVersion) -> Vec<(Option<usize>, TargetViolationKind)> {
+        let mut block = Parser::default()
+            .with_lua_version(LuaVersion::Lua54)
+            .preserve_tokens()
+            .parse(code)
+            .or_else(|_| Parser::default().preserve_tokens().parse(code))
+            .expect("unable to parse code");
+
+        TargetValidator::new(target)
+            .validate(&mut block)
+            .into_iter()
+            .map(|violation| (violation.line, violation.kind))
+            .collect()
+    }
+
+    macro_rules! test_valid {
+        ($($name:ident ($target:ident) => $code:literal),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    pretty_assertions::assert_eq!(validate($code, LuaVersion::$target), Vec::new());
+                }
+            )*
+        };
+    }
+
+    macro_rules! test_violations {
+        ($($name:ident ($target:ident, $code:literal) => [$(($line:expr, $kind:ident)),* $(,)?]),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    pretty_assertions::assert_eq!(
+                        validate($code, LuaVersion::$target),
+                        vec![$((Some($line), TargetViolationKind::$kind)),*]
+                    );
+                }
+            )*
+        };
+    }
+
+    test_valid!(
+        lua51_plain_code(Lua51) => "local a = 1\nfor i, v in pairs(t) do print(i, v) end\nreturn a",
+        lua51_generic_for_with_varargs(Lua51) => "for k, v in ... do end",
+        lua51_generic_for_with_explicit_iterator(Lua51) => "for k, v in next, t do end",
+        luau_continue(Luau) => "while true do continue end",
+        luau_types(Luau) => "local a: number = 1",
+        lua53_floor_division(Lua53) => "return a // b",
+        lua53_bitwise_operator(Lua53) => "return a & b",
+        lua52_goto(Lua52) => "goto skip\n::skip::",
+        lua54_local_attribute(Lua54) => "local a <const> = 1",
+    );
+
+    test_violations!(
+        lua51_continue(Lua51, "while true do\n\tcontinue\nend") => [(2, Continue)],
+        lua51_compound_assignment(Lua51, "local a = 1\na += 1") => [(2, CompoundAssignment)],
+        lua51_if_expression(Lua51, "return\nif a then b else c") => [(2, IfExpression)],
+        lua51_interpolated_string(Lua51, "print(`{a}`)") => [(1, InterpolatedString)],
+        lua51_floor_division(Lua51, "local a = 1\n\nreturn a // 2") => [(3, FloorDivision)],
+        lua52_floor_division(Lua52, "return a // 2") => [(1, FloorDivision)],
+        lua51_type_annotation(Lua51, "local a: string = 'a'") => [(1, TypeAnnotation)],
+        lua51_nested_type_annotations_reported_once(Lua51, "local a: { string? } = {}") => [(1, TypeAnnotation)],
+        lua51_type_annotation_in_function(Lua51, "local function f(\n\ta: string\n)\nend") => [(1, TypeAnnotation)],
+        lua51_type_declaration(Lua51, "local a = 1\ntype A = { string }") => [(2, TypeDeclaration)],
+        lua51_generalized_iteration(Lua51, "for k, v in {} do end") => [(1, GeneralizedIteration)],
+        lua51_generalized_iteration_with_identifier(Lua51, "for k, v in tbl do end") => [(1, GeneralizedIteration)],
+        lua51_binary_number(Lua51, "return 0b101") => [(1, BinaryNumber)],
+        lua51_goto(Lua51, "goto skip\n::skip::") => [(1, Goto), (2, Label)],
+        luau_goto(Luau, "goto skip\n::skip::") => [(1, Goto), (2, Label)],
+        lua52_bitwise_operators(Lua52, "return ~a\n| b") => [(1, BitwiseOperator), (2, BitwiseOperator)],
+        lua53_local_attribute(Lua53, "local a <const>, b <close> = 1, f()") => [(1, LocalAttribute)],
+        lua51_multiple_violations(Lua51, "local a: number = 1\nwhile a do\n\ta += 1\n\tcontinue\nend") => [
+            (1, TypeAnnotation),
+            (3, CompoundAssignment),
+            (4, Continue),
+        ],
+    );
+
+    #[test]
+    fn violation_without_tokens_has_no_line() {
+        let mut block = Parser::default().parse("a += 1").unwrap();
+
+        let violations = TargetValidator::new(LuaVersion::Lua51).validate(&mut block);
+
+        pretty_assertions::assert_eq!(
+            violations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["compound assignment (can be removed with `remove_compound_assignment`)"]
+        );
+    }
+
+    #[test]
+    fn display_violation_with_line() {
+        let violation = TargetViolation {
+            line: Some(4),
+            kind: TargetViolationKind::Goto,
+        };
+
+        pretty_assertions::assert_eq!(violation.to_string(), "line 4: `goto` statement");
+    }
+}
@@ -29,7 +29,9 @@ pub trait NodeVisitor<T: NodeProcessor> {
             }
             Statement::Function(statement) => Self::visit_function_statement(statement, processor),
             Statement::GenericFor(statement) => Self::visit_generic_for(statement, processor),
+            Statement::Goto(statement) => Self::visit_goto_statement(statement, processor),
             Statement::If(statement) => Self::visit_if_statement(statement, processor),
+            Statement::Label(statement) => Self::visit_label_statement(statement, processor),
             Statement::LocalAssign(statement) => Self::visit_local_assign(statement, processor),
             Statement::LocalFunction(statement) => Self::visit_local_function(statement, processor),
             Statement::TypeFunction(statement) => Self::visit_type_function(statement, processor),
@@ -377,6 +379,14 @@ pub trait NodeVisitor<T: NodeProcessor> {
         Self::visit_block(statement.mutate_block(), processor);
     }
 
+    fn visit_goto_statement(statement: &mut GotoStatement, processor: &mut T) {
+        processor.process_goto_statement(statement);
+    }
+
+    fn visit_label_statement(statement: &mut LabelStatement, processor: &mut T) {
+        processor.process_label_statement(statement);
+    }
+
     fn visit_type_declaration(statement: &mut TypeDeclarationStatement, processor: &mut T) {
         processor.process_type_declaration(statement);
 
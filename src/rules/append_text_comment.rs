@@ -4,9 +4,10 @@ use std::sync::OnceLock;
 
 use crate::nodes::{
     Block, BlockTokens, DoTokens, ExportTypeFunctionTokens, FunctionBodyTokens, GenericForTokens,
-    Identifier, IfStatementTokens, LastStatement, LocalAssignTokens, LocalFunctionTokens,
-    NumericForTokens, ParentheseExpression, ParentheseTokens, Prefix, RepeatTokens, ReturnTokens,
-    Statement, Token, TriviaKind, TypeDeclarationTokens, TypeFunctionTokens, Variable, WhileTokens,
+    GotoTokens, Identifier, IfStatementTokens, LabelTokens, LastStatement, LocalAssignTokens,
+    LocalFunctionTokens, NumericForTokens, ParentheseExpression, ParentheseTokens, Prefix,
+    RepeatTokens, ReturnTokens, Statement, Token, TriviaKind, TypeDeclarationTokens,
+    TypeFunctionTokens, Variable, WhileTokens,
 };
 use crate::rules::{
     verify_property_collisions, verify_required_any_properties, Context, Rule, RuleConfiguration,
@@ -170,6 +171,16 @@ impl Rule for AppendTextComment {
                                 });
                             }
                         }
+                        Statement::Goto(goto) => {
+                            if let Some(tokens) = goto.mutate_tokens() {
+                                self.location.append_comment(&mut tokens.goto, text);
+                            } else {
+                                let mut token = Token::from_content("goto");
+                                self.location.append_comment(&mut token, text);
+
+                                goto.set_tokens(GotoTokens { goto: token });
+                            }
+                        }
                         Statement::If(if_statement) => {
                             if let Some(tokens) = if_statement.mutate_tokens() {
                                 self.location.append_comment(&mut tokens.r#if, text);
@@ -185,6 +196,19 @@ impl Rule for AppendTextComment {
                                 });
                             }
                         }
+                        Statement::Label(label) => {
+                            if let Some(tokens) = label.mutate_tokens() {
+                                self.location.append_comment(&mut tokens.left_colons, text);
+                            } else {
+                                let mut token = Token::from_content("::");
+                                self.location.append_comment(&mut token, text);
+
+                                label.set_tokens(LabelTokens {
+                                    left_colons: token,
+                                    right_colons: Token::from_content("::"),
+                                });
+                            }
+                        }
                         Statement::LocalAssign(local_assign) => {
                             if let Some(tokens) = local_assign.mutate_tokens() {
                                 self.location.append_comment(&mut tokens.local, text);
@@ -0,0 +1,140 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::utils;
+
+/// A placeholder in the manifest path replaced with the path of the bundled entry.
+pub(crate) const MANIFEST_ENTRY_PLACEHOLDER: &str = "{entry}";
+
+/// A JSON report of the modules inlined in a bundle.
+#[derive(Debug, Serialize)]
+pub(crate) struct BundleManifest {
+    entry: String,
+    dependencies: Vec<String>,
+    modules: Vec<ManifestModule>,
+    excluded_requires: Vec<ExcludedRequire>,
+}
+
+impl BundleManifest {
+    pub(crate) fn new(entry: &Path, dependencies: Vec<PathBuf>, project_location: &Path) -> Self {
+        Self {
+            entry: display_path(entry, project_location),
+            dependencies: dependencies
+                .iter()
+                .map(|path| display_path(path, project_location))
+                .collect(),
+            modules: Vec::new(),
+            excluded_requires: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push_module(&mut self, module: ManifestModule) {
+        self.modules.push(module);
+    }
+
+    pub(crate) fn with_excluded_requires(
+        mut self,
+        excluded_requires: impl IntoIterator<Item = ExcludedRequire>,
+    ) -> Self {
+        self.excluded_requires.extend(excluded_requires);
+        self
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("bundle manifest should serialize to json")
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ManifestModule {
+    path: String,
+    hash: String,
+    size: usize,
+    dependencies: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    duplicates: Vec<String>,
+}
+
+impl ManifestModule {
+    pub(crate) fn new(
+        path: &Path,
+        hash: u64,
+        size: usize,
+        dependencies: Vec<PathBuf>,
+        project_location: &Path,
+    ) -> Self {
+        Self {
+            path: display_path(path, project_location),
+            hash: format!("{:016x}", hash),
+            size,
+            dependencies: dependencies
+                .iter()
+                .map(|path| display_path(path, project_location))
+                .collect(),
+            duplicates: Vec::new(),
+        }
+    }
+
+    /// Adds the paths of identical modules that were merged into this module.
+    pub(crate) fn with_duplicates(mut self, paths: &[PathBuf], project_location: &Path) -> Self {
+        self.duplicates.extend(
+            paths
+                .iter()
+                .map(|path| display_path(path, project_location)),
+        );
+        self
+    }
+}
+
+/// A require call that was kept in the bundle because it matches an exclude pattern.
+#[derive(Debug, Serialize)]
+pub(crate) struct ExcludedRequire {
+    source: String,
+    require: String,
+}
+
+impl ExcludedRequire {
+    pub(crate) fn new(source: &Path, require: impl Into<String>, project_location: &Path) -> Self {
+        Self {
+            source: display_path(source, project_location),
+            require: require.into(),
+        }
+    }
+}
+
+/// Returns the location where the manifest of the given entry is written. The `{entry}`
+/// placeholder is replaced with the entry path (relative to the project, without extension).
+pub(crate) fn manifest_location(manifest: &Path, entry: &Path, project_location: &Path) -> PathBuf {
+    let manifest = manifest.to_string_lossy();
+
+    let location = if manifest.contains(MANIFEST_ENTRY_PLACEHOLDER) {
+        manifest.replace(
+            MANIFEST_ENTRY_PLACEHOLDER,
+            &display_path(&entry.with_extension(""), project_location),
+        )
+    } else {
+        manifest.into_owned()
+    };
+
+    project_location.join(location)
+}
+
+pub(crate) fn hash_content(content: &str) -> u64 {
+    xxh3_64(content.as_bytes())
+}
+
+// displays the path relative to the project with `/` separators
+fn display_path(path: &Path, project_location: &Path) -> String {
+    let path = utils::normalize_path(path);
+    let project_location = utils::normalize_path(project_location);
+    let relative_path = path.strip_prefix(&project_location).unwrap_or(&path);
+
+    relative_path
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
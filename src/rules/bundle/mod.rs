@@ -1,17 +1,33 @@
+mod manifest;
+mod module_initialization;
+mod module_require_mode;
 pub(crate) mod path_require_mode;
 mod rename_type_declaration;
 mod require_mode;
+mod resource_loader;
+mod roblox_require_mode;
+mod shared_chunk;
 
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
+use crate::frontend::DarkluaResult;
 use crate::nodes::Block;
 use crate::rules::{
     Context, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult, RuleProperties,
 };
-use crate::Parser;
+use crate::utils::normalize_path;
+use crate::{DarkluaError, Parser};
 
+use path_require_mode::SharedModules;
+
+pub use module_initialization::ModuleInitialization;
 pub(crate) use rename_type_declaration::RenameTypeDeclarationProcessor;
 pub use require_mode::BundleRequireMode;
+pub use resource_loader::ResourceLoader;
+use resource_loader::ResourceLoaders;
+pub use shared_chunk::SharedChunk;
 use wax::Pattern;
 
 pub const BUNDLER_RULE_NAME: &str = "bundler";
@@ -21,6 +37,19 @@ pub(crate) struct BundleOptions {
     parser: Parser,
     modules_identifier: String,
     excludes: Option<wax::Any<'static>>,
+    tree_shake: bool,
+    initialization: ModuleInitialization,
+    allow_cycles: bool,
+    manifest: Option<PathBuf>,
+    // the entry of each manifest written while processing, to avoid overwriting the
+    // manifest of another entry
+    written_manifests: Mutex<HashMap<PathBuf, PathBuf>>,
+    loaders: ResourceLoaders,
+    deduplicate: bool,
+    shared_chunk: Option<SharedChunk>,
+    shared_modules: Option<SharedModules>,
+    // the rules applied to each file before its requires are resolved
+    file_rules: Vec<Box<dyn Rule>>,
 }
 
 impl BundleOptions {
@@ -45,6 +74,16 @@ impl BundleOptions {
         Self {
             parser,
             modules_identifier: modules_identifier.into(),
+            tree_shake: false,
+            initialization: ModuleInitialization::default(),
+            allow_cycles: false,
+            manifest: None,
+            written_manifests: Default::default(),
+            loaders: ResourceLoaders::default(),
+            deduplicate: false,
+            shared_chunk: None,
+            shared_modules: None,
+            file_rules: Vec::new(),
             excludes: if excludes.is_empty() {
                 None
             } else {
@@ -63,12 +102,87 @@ impl BundleOptions {
         &self.modules_identifier
     }
 
+    fn tree_shake(&self) -> bool {
+        self.tree_shake
+    }
+
+    fn initialization(&self) -> ModuleInitialization {
+        self.initialization
+    }
+
+    fn allow_cycles(&self) -> bool {
+        self.allow_cycles
+    }
+
+    fn manifest(&self) -> Option<&Path> {
+        self.manifest.as_deref()
+    }
+
+    /// Records that the manifest at the given location is written for an entry. If the
+    /// manifest was already written for another entry, that entry is returned.
+    fn register_manifest(&self, location: &Path, entry: &Path) -> Option<PathBuf> {
+        let mut written_manifests = self.written_manifests.lock().unwrap();
+
+        match written_manifests.get(location) {
+            Some(previous_entry) if previous_entry != entry => Some(previous_entry.clone()),
+            _ => {
+                written_manifests.insert(location.to_path_buf(), entry.to_path_buf());
+                None
+            }
+        }
+    }
+
+    fn loaders(&self) -> &ResourceLoaders {
+        &self.loaders
+    }
+
+    fn deduplicate(&self) -> bool {
+        self.deduplicate
+    }
+
+    /// Returns the modules to load from the shared chunk when bundling the given path,
+    /// if it is one of the shared chunk entries.
+    fn shared_modules(&self, path: &Path) -> Option<&SharedModules> {
+        self.shared_modules.as_ref().filter(|_| {
+            self.shared_chunk
+                .as_ref()
+                .map(|shared_chunk| shared_chunk.is_entry(path))
+                .unwrap_or(false)
+        })
+    }
+
     fn is_excluded(&self, require: &Path) -> bool {
         self.excludes
             .as_ref()
             .map(|any| any.is_match(require))
             .unwrap_or(false)
     }
+
+    /// Applies the rules that must run before bundling (like `conditional_compilation`) to a
+    /// file, so that its requires are resolved from the processed code.
+    fn apply_file_rules(&self, block: &mut Block, context: &Context) -> DarkluaResult<()> {
+        let path = normalize_path(context.current_path());
+        let relative_path = path
+            .strip_prefix(normalize_path(context.project_location()))
+            .unwrap_or(&path);
+
+        for rule in self
+            .file_rules
+            .iter()
+            .filter(|rule| rule.applies_to(relative_path))
+        {
+            log::trace!(
+                "apply rule `{}` to `{}` before bundling",
+                rule.get_name(),
+                path.display()
+            );
+            rule.process(block, context).map_err(|err| {
+                DarkluaError::orphan_rule_error(context.current_path(), rule.as_ref(), err)
+            })?;
+        }
+
+        Ok(())
+    }
 }
 
 /// A rule that inlines required modules
@@ -94,6 +208,78 @@ impl Bundler {
         self.options.modules_identifier = modules_identifier.into();
         self
     }
+
+    pub(crate) fn with_tree_shake(mut self, tree_shake: bool) -> Self {
+        self.options.tree_shake = tree_shake;
+        self
+    }
+
+    pub(crate) fn with_initialization(mut self, initialization: ModuleInitialization) -> Self {
+        self.options.initialization = initialization;
+        self
+    }
+
+    pub(crate) fn with_allow_cycles(mut self, allow_cycles: bool) -> Self {
+        self.options.allow_cycles = allow_cycles;
+        self
+    }
+
+    pub(crate) fn with_manifest(mut self, manifest: Option<PathBuf>) -> Self {
+        self.options.manifest = manifest;
+        self
+    }
+
+    pub(crate) fn with_loaders(mut self, loaders: &BTreeMap<String, ResourceLoader>) -> Self {
+        self.options.loaders = ResourceLoaders::new(loaders);
+        self
+    }
+
+    pub(crate) fn with_deduplicate(mut self, deduplicate: bool) -> Self {
+        self.options.deduplicate = deduplicate;
+        self
+    }
+
+    pub(crate) fn with_shared_chunk(mut self, shared_chunk: Option<SharedChunk>) -> Self {
+        self.options.shared_chunk = shared_chunk;
+        self
+    }
+
+    pub(crate) fn with_file_rules(mut self, file_rules: Vec<Box<dyn Rule>>) -> Self {
+        self.options.file_rules = file_rules;
+        self
+    }
+
+    pub(crate) fn shared_chunk(&self) -> Option<&SharedChunk> {
+        self.options.shared_chunk.as_ref()
+    }
+
+    /// Returns `true` if the given path is an entry of the shared chunk and the shared
+    /// chunk has not been built yet.
+    pub(crate) fn needs_shared_chunk(&self, path: &Path) -> bool {
+        self.options.shared_modules.is_none()
+            && self
+                .shared_chunk()
+                .map(|shared_chunk| shared_chunk.is_entry(path))
+                .unwrap_or(false)
+    }
+
+    /// Builds the shared chunk from the modules inlined by more than one entry. These
+    /// modules are then loaded from the shared chunk when bundling the entries.
+    pub(crate) fn build_shared_chunk(&mut self, context: &Context) -> DarkluaResult<Block> {
+        let shared_chunk = self
+            .options
+            .shared_chunk
+            .as_ref()
+            .ok_or_else(|| DarkluaError::custom("bundle configuration has no shared chunk"))?;
+
+        let (block, shared_modules) =
+            self.require_mode
+                .build_shared_chunk(context, &self.options, shared_chunk)?;
+
+        self.options.shared_modules = Some(shared_modules);
+
+        Ok(block)
+    }
 }
 
 impl Rule for Bundler {
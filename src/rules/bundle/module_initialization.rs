@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Defines when the bundled modules are executed.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleInitialization {
+    /// Modules are wrapped in functions and executed the first time they are required
+    #[default]
+    Lazy,
+    /// Modules are inlined in dependency order and executed when the bundle starts
+    Eager,
+}
+
+impl ModuleInitialization {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use crate::frontend::DarkluaResult;
+use crate::nodes::{Block, FunctionCall};
+use crate::rules::require::{match_string_require_call, ModuleRequireMode};
+use crate::rules::Context;
+use crate::Resources;
+
+use super::path_require_mode::{
+    build_shared_chunk_with_locator, process_block_with_locator, BundledRequire, FindRequirePath,
+    SharedModules,
+};
+use super::{BundleOptions, SharedChunk};
+
+#[derive(Debug)]
+struct ModuleRequireLocator<'a, 'b, 'resources> {
+    module_require_mode: &'a ModuleRequireMode,
+    project_location: &'b Path,
+    resources: &'resources Resources,
+}
+
+impl FindRequirePath for ModuleRequireLocator<'_, '_, '_> {
+    fn find_bundled_require(
+        &self,
+        call: &FunctionCall,
+        source: &Path,
+        options: &BundleOptions,
+    ) -> DarkluaResult<BundledRequire> {
+        let module_name = match match_string_require_call(call) {
+            Some(module_name) => module_name,
+            None => return Ok(BundledRequire::Skip),
+        };
+
+        if options.is_excluded(Path::new(&module_name)) {
+            log::info!(
+                "exclude `{}` from bundle [from `{}`]",
+                module_name,
+                source.display()
+            );
+            return Ok(BundledRequire::Excluded(module_name));
+        }
+
+        let require_path = self.module_require_mode.find_module_path(
+            &module_name,
+            self.project_location,
+            self.resources,
+        )?;
+
+        if options.is_excluded(&require_path) {
+            log::info!(
+                "exclude `{}` ({}) from bundle [from `{}`]",
+                require_path.display(),
+                module_name,
+                source.display()
+            );
+            return Ok(BundledRequire::Excluded(module_name));
+        }
+
+        log::debug!(
+            "found require call to module `{}` (file `{}`)",
+            module_name,
+            require_path.display()
+        );
+
+        Ok(BundledRequire::Inline(require_path))
+    }
+}
+
+pub(crate) fn process_block(
+    block: &mut Block,
+    context: &Context,
+    options: &BundleOptions,
+    module_require_mode: &ModuleRequireMode,
+) -> Result<(), String> {
+    let locator = ModuleRequireLocator {
+        module_require_mode,
+        project_location: context.project_location(),
+        resources: context.resources(),
+    };
+
+    process_block_with_locator(block, context, options, locator)
+}
+
+pub(crate) fn build_shared_chunk(
+    context: &Context,
+    options: &BundleOptions,
+    shared_chunk: &SharedChunk,
+    module_require_mode: &ModuleRequireMode,
+) -> DarkluaResult<(Block, SharedModules)> {
+    let locator = ModuleRequireLocator {
+        module_require_mode,
+        project_location: context.project_location(),
+        resources: context.resources(),
+    };
+
+    build_shared_chunk_with_locator(context, options, shared_chunk, locator)
+}
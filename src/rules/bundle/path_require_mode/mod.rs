@@ -1,18 +1,23 @@
 mod module_definitions;
+mod require_graph;
+mod tree_shake;
 
-use module_definitions::BuildModuleDefinitions;
+use module_definitions::{
+    build_load_expression, match_load_call, BuildModuleDefinitions, BundledModule,
+};
+use require_graph::{RequireGraph, RequireSite};
 
 use std::collections::{HashMap, HashSet};
+use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
-use std::{iter, mem};
 
 use serde::Serialize;
 
 use crate::frontend::DarkluaResult;
 use crate::nodes::{
-    Block, DoStatement, Expression, FunctionCall, LocalAssignStatement, Prefix, Statement,
-    StringExpression,
+    Block, DoStatement, Expression, FunctionCall, Identifier, LastStatement, LocalAssignStatement,
+    Prefix, ReturnStatement, Statement, StringExpression, TableExpression,
 };
 use crate::process::{
     to_expression, DefaultVisitor, IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor,
@@ -24,60 +29,233 @@ use crate::rules::{
     Context, ContextBuilder, FlawlessRule, ReplaceReferencedTokens, RuleProcessResult,
 };
 use crate::utils::Timer;
-use crate::{DarkluaError, Resources};
+use crate::{DarkluaError, Parser, Resources};
 
-use super::BundleOptions;
+use super::manifest::{
+    hash_content, manifest_location, BundleManifest, ExcludedRequire, ManifestModule,
+    MANIFEST_ENTRY_PLACEHOLDER,
+};
+use super::resource_loader::{load_separated_values, ResourceLoader};
+use super::{BundleOptions, ModuleInitialization, SharedChunk};
 
 pub(crate) enum RequiredResource {
     Block(Block),
     Expression(Expression),
 }
 
+/// The outcome of looking for the file required by a `require` call while bundling.
+pub(crate) enum BundledRequire {
+    /// The required file is inlined in the bundle
+    Inline(PathBuf),
+    /// The require matches an exclude pattern and is kept in the bundle as is
+    Excluded(String),
+    /// The call is not handled by the bundler
+    Skip,
+}
+
+/// Finds the files required by `require` calls while bundling.
+pub(crate) trait FindRequirePath {
+    /// Returns the path of the file required by the given call, unless the call should
+    /// not be inlined in the bundle.
+    fn find_bundled_require(
+        &self,
+        call: &FunctionCall,
+        source: &Path,
+        options: &BundleOptions,
+    ) -> DarkluaResult<BundledRequire>;
+}
+
+impl FindRequirePath for RequirePathLocator<'_, '_, '_> {
+    fn find_bundled_require(
+        &self,
+        call: &FunctionCall,
+        source: &Path,
+        options: &BundleOptions,
+    ) -> DarkluaResult<BundledRequire> {
+        let literal_require_path = match match_path_require_call(call) {
+            Some(path) => path,
+            None => return Ok(BundledRequire::Skip),
+        };
+
+        if options.is_excluded(&literal_require_path) {
+            log::info!(
+                "exclude `{}` from bundle [from `{}`]",
+                literal_require_path.display(),
+                source.display()
+            );
+            return Ok(BundledRequire::Excluded(
+                literal_require_path.display().to_string(),
+            ));
+        }
+
+        let require_path = self.find_require_path(&literal_require_path, source)?;
+
+        log::debug!(
+            "found require call to path `{}` (normalized `{}`)",
+            literal_require_path.display(),
+            require_path.display()
+        );
+
+        Ok(BundledRequire::Inline(require_path))
+    }
+}
+
+impl<T: FindRequirePath> FindRequirePath for &T {
+    fn find_bundled_require(
+        &self,
+        call: &FunctionCall,
+        source: &Path,
+        options: &BundleOptions,
+    ) -> DarkluaResult<BundledRequire> {
+        (*self).find_bundled_require(call, source, options)
+    }
+}
+
+/// The modules inlined by more than one entry of a shared chunk, with the expression that
+/// loads each of them from the shared chunk.
 #[derive(Debug)]
-struct RequirePathProcessor<'a, 'b, 'resources, 'code> {
+pub(crate) struct SharedModules {
+    loads: HashMap<PathBuf, Expression>,
+    load_chunk: Expression,
+    dependencies: Vec<PathBuf>,
+}
+
+fn shared_modules_identifier(modules_identifier: &str) -> String {
+    format!("{}_SHARED", modules_identifier)
+}
+
+#[derive(Debug)]
+struct RequirePathProcessor<'a, 'resources, L> {
     options: &'a BundleOptions,
     identifier_tracker: IdentifierTracker,
-    path_locator: RequirePathLocator<'b, 'code, 'resources>,
+    locator: L,
     module_definitions: BuildModuleDefinitions,
     source: PathBuf,
     module_cache: HashMap<PathBuf, Expression>,
-    require_stack: Vec<PathBuf>,
+    require_graph: RequireGraph,
+    module_hashes: HashMap<PathBuf, u64>,
+    excluded_requires: Vec<ExcludedRequire>,
+    project_location: PathBuf,
     skip_module_paths: HashSet<PathBuf>,
+    shared_modules: Option<&'a SharedModules>,
+    uses_shared_chunk: bool,
+    manifest: Option<&'a Path>,
     resources: &'resources Resources,
     errors: Vec<String>,
 }
 
-impl<'a, 'b, 'code, 'resources> RequirePathProcessor<'a, 'b, 'code, 'resources> {
+impl<'a, 'resources, L: FindRequirePath> RequirePathProcessor<'a, 'resources, L> {
     fn new<'context>(
-        context: &'context Context<'b, 'resources, 'code>,
+        context: &'context Context<'_, 'resources, '_>,
         options: &'a BundleOptions,
-        path_require_mode: &'b PathRequireMode,
+        locator: L,
     ) -> Self
     where
-        'context: 'b,
         'context: 'resources,
-        'context: 'code,
     {
         Self {
             options,
             identifier_tracker: IdentifierTracker::new(),
-            path_locator: RequirePathLocator::new(
-                path_require_mode,
-                context.project_location(),
-                context.resources(),
-            ),
-            module_definitions: BuildModuleDefinitions::new(options.modules_identifier()),
+            locator,
+            module_definitions: BuildModuleDefinitions::new(options),
             source: context.current_path().to_path_buf(),
             module_cache: Default::default(),
-            require_stack: Default::default(),
+            require_graph: Default::default(),
+            module_hashes: Default::default(),
+            excluded_requires: Vec::new(),
+            project_location: context.project_location().to_path_buf(),
             skip_module_paths: Default::default(),
+            shared_modules: None,
+            uses_shared_chunk: false,
+            manifest: options.manifest(),
             resources: context.resources(),
             errors: Vec::new(),
         }
     }
 
-    fn apply(self, block: &mut Block, context: &Context) -> RuleProcessResult {
-        self.module_definitions.apply(block, context);
+    fn with_shared_modules(mut self, shared_modules: &'a SharedModules) -> Self {
+        self.shared_modules = Some(shared_modules);
+        self
+    }
+
+    fn for_shared_chunk(mut self) -> Self {
+        self.module_definitions = self.module_definitions.for_shared_chunk();
+        self.manifest = None;
+        self
+    }
+
+    fn apply(mut self, block: &mut Block, context: &Context) -> RuleProcessResult {
+        let mut module_sizes = self.manifest.map(|_| Vec::new());
+
+        self.module_definitions
+            .apply(block, context, module_sizes.as_mut());
+
+        let uses_shared_chunk = self.uses_shared_chunk;
+        if let Some(shared_modules) = self.shared_modules.filter(|_| uses_shared_chunk) {
+            block.insert_statement(
+                0,
+                LocalAssignStatement::from_variable(shared_modules_identifier(
+                    self.options.modules_identifier(),
+                ))
+                .with_value(shared_modules.load_chunk.clone()),
+            );
+        }
+
+        if let (Some(manifest_path), Some(module_sizes)) = (self.manifest, module_sizes) {
+            if self.errors.is_empty() {
+                let entry = context.current_path();
+                let mut manifest = BundleManifest::new(
+                    entry,
+                    self.require_graph.dependencies(entry),
+                    &self.project_location,
+                );
+
+                for BundledModule {
+                    path,
+                    size,
+                    duplicate_paths,
+                } in module_sizes
+                {
+                    manifest.push_module(
+                        ManifestModule::new(
+                            &path,
+                            self.module_hashes.get(&path).copied().unwrap_or_default(),
+                            size,
+                            self.require_graph.dependencies(&path),
+                            &self.project_location,
+                        )
+                        .with_duplicates(&duplicate_paths, &self.project_location),
+                    );
+                }
+
+                let manifest = manifest.with_excluded_requires(self.excluded_requires.drain(..));
+                let manifest_path =
+                    manifest_location(manifest_path, entry, context.project_location());
+
+                log::debug!("write bundle manifest to `{}`", manifest_path.display());
+
+                if let Some(previous_entry) = self.options.register_manifest(&manifest_path, entry)
+                {
+                    self.errors.push(format!(
+                        "unable to write bundle manifest `{}` for `{}` because it is already written for `{}` (use `{}` in the manifest path to write one manifest per entry)",
+                        manifest_path.display(),
+                        entry.display(),
+                        previous_entry.display(),
+                        MANIFEST_ENTRY_PLACEHOLDER,
+                    ));
+                } else if let Err(err) = context
+                    .resources()
+                    .write(&manifest_path, &manifest.to_json())
+                {
+                    self.errors.push(
+                        DarkluaError::from(err)
+                            .context("unable to write bundle manifest")
+                            .to_string(),
+                    );
+                }
+            }
+        }
+
         match self.errors.len() {
             0 => Ok(()),
             1 => Err(self.errors.first().unwrap().to_string()),
@@ -85,43 +263,31 @@ impl<'a, 'b, 'code, 'resources> RequirePathProcessor<'a, 'b, 'code, 'resources>
         }
     }
 
-    fn require_call(&self, call: &FunctionCall) -> Option<PathBuf> {
-        if is_require_call(call, self) {
-            match_path_require_call(call)
-        } else {
-            None
-        }
-    }
-
     fn try_inline_call(&mut self, call: &FunctionCall) -> Option<Expression> {
-        let literal_require_path = self.require_call(call)?;
-
-        if self.options.is_excluded(&literal_require_path) {
-            log::info!(
-                "exclude `{}` from bundle [from `{}`]",
-                literal_require_path.display(),
-                self.source.display()
-            );
+        if !is_require_call(call, self) {
             return None;
         }
 
         let require_path = match self
-            .path_locator
-            .find_require_path(&literal_require_path, &self.source)
+            .locator
+            .find_bundled_require(call, &self.source, self.options)
         {
-            Ok(path) => path,
+            Ok(BundledRequire::Inline(path)) => path,
+            Ok(BundledRequire::Excluded(require)) => {
+                self.excluded_requires.push(ExcludedRequire::new(
+                    &self.source,
+                    require,
+                    &self.project_location,
+                ));
+                return None;
+            }
+            Ok(BundledRequire::Skip) => return None,
             Err(err) => {
                 self.errors.push(err.to_string());
                 return None;
             }
         };
 
-        log::debug!(
-            "found require call to path `{}` (normalized `{}`)",
-            literal_require_path.display(),
-            require_path.display()
-        );
-
         if self.skip_module_paths.contains(&require_path) {
             log::trace!(
                 "skip `{}` because it previously errored",
@@ -145,33 +311,42 @@ impl<'a, 'b, 'code, 'resources> RequirePathProcessor<'a, 'b, 'code, 'resources>
         require_path: &Path,
         call: &FunctionCall,
     ) -> DarkluaResult<Expression> {
-        if let Some(expression) = self.module_cache.get(require_path) {
+        let cycle = self.require_graph.add_require(
+            &self.source,
+            require_path,
+            RequireSite::from_call(call),
+        );
+
+        if let Some(expression) = self
+            .shared_modules
+            .and_then(|shared_modules| shared_modules.loads.get(require_path))
+        {
+            log::trace!("load `{}` from the shared chunk", require_path.display());
+            self.uses_shared_chunk = true;
+            Ok(expression.clone())
+        } else if let Some(expression) = self.module_cache.get(require_path) {
             Ok(expression.clone())
         } else {
-            if let Some(i) = self
-                .require_stack
-                .iter()
-                .enumerate()
-                .find(|(_, path)| **path == require_path)
-                .map(|(i, _)| i)
-            {
-                let require_stack_paths: Vec<_> = self
-                    .require_stack
-                    .iter()
-                    .skip(i)
-                    .map(|path| path.display().to_string())
-                    .chain(iter::once(require_path.display().to_string()))
-                    .collect();
-
-                return Err(DarkluaError::custom(format!(
-                    "cyclic require detected with `{}`",
-                    require_stack_paths.join("` > `")
-                )));
+            if let Some(cycle) = cycle {
+                if !self.options.allow_cycles() {
+                    return Err(DarkluaError::custom(cycle.to_string()));
+                }
+
+                if self.options.initialization() == ModuleInitialization::Eager {
+                    return Err(DarkluaError::custom(format!(
+                        "unable to initialize modules eagerly because of a {}",
+                        cycle
+                    )));
+                }
+
+                log::warn!("{}", cycle);
+
+                return Ok(self
+                    .module_definitions
+                    .build_cyclic_require(require_path, call));
             }
 
-            self.require_stack.push(require_path.to_path_buf());
             let required_resource = self.require_resource(require_path);
-            self.require_stack.pop();
 
             let module_value = self.module_definitions.build_module_from_resource(
                 required_resource?,
@@ -191,77 +366,97 @@ impl<'a, 'b, 'code, 'resources> RequirePathProcessor<'a, 'b, 'code, 'resources>
         log::trace!("look for resource `{}`", path.display());
         let content = self.resources.get(path).map_err(DarkluaError::from)?;
 
-        match path.extension() {
-            Some(extension) => match extension.to_string_lossy().as_ref() {
-                "lua" | "luau" => {
-                    let parser_timer = Timer::now();
-                    let mut block =
-                        self.options
-                            .parser()
-                            .parse(&content)
-                            .map_err(|parser_error| {
-                                DarkluaError::parser_error(path.to_path_buf(), parser_error)
-                            })?;
-                    log::debug!(
-                        "parsed `{}` in {}",
+        if self.options.manifest().is_some() {
+            self.module_hashes
+                .insert(path.to_path_buf(), hash_content(&content));
+        }
+
+        let loader = self
+            .options
+            .loaders()
+            .find(path)
+            .ok_or_else(|| DarkluaError::invalid_resource_extension(path))?;
+
+        match loader {
+            ResourceLoader::Lua => {
+                let parser_timer = Timer::now();
+                let mut block = self
+                    .options
+                    .parser()
+                    .parse(&content)
+                    .map_err(|parser_error| {
+                        DarkluaError::parser_error(path.to_path_buf(), parser_error)
+                    })?;
+                log::debug!(
+                    "parsed `{}` in {}",
+                    path.display(),
+                    parser_timer.duration_label()
+                );
+
+                let context = ContextBuilder::new(path, self.resources, &content)
+                    .with_project_location(&self.project_location)
+                    .build();
+
+                self.options.apply_file_rules(&mut block, &context)?;
+
+                if self.options.parser().is_preserving_tokens() {
+                    log::trace!("replacing token references of {}", path.display());
+                    // run `replace_referenced_tokens` rule to avoid generating invalid code
+                    // when using the token-based generator
+                    let replace_tokens = ReplaceReferencedTokens::default();
+
+                    let apply_replace_tokens_timer = Timer::now();
+
+                    replace_tokens.flawless_process(&mut block, &context);
+
+                    log::trace!(
+                        "replaced token references for `{}` in {}",
                         path.display(),
-                        parser_timer.duration_label()
+                        apply_replace_tokens_timer.duration_label()
                     );
+                }
 
-                    if self.options.parser().is_preserving_tokens() {
-                        log::trace!("replacing token references of {}", path.display());
-                        let context = ContextBuilder::new(path, self.resources, &content).build();
-                        // run `replace_referenced_tokens` rule to avoid generating invalid code
-                        // when using the token-based generator
-                        let replace_tokens = ReplaceReferencedTokens::default();
-
-                        let apply_replace_tokens_timer = Timer::now();
-
-                        replace_tokens.flawless_process(&mut block, &context);
-
-                        log::trace!(
-                            "replaced token references for `{}` in {}",
-                            path.display(),
-                            apply_replace_tokens_timer.duration_label()
-                        );
-                    }
-
-                    let current_source = mem::replace(&mut self.source, path.to_path_buf());
+                let current_source = mem::replace(&mut self.source, path.to_path_buf());
 
-                    let apply_processor_timer = Timer::now();
-                    DefaultVisitor::visit_block(&mut block, self);
+                let apply_processor_timer = Timer::now();
+                DefaultVisitor::visit_block(&mut block, self);
 
-                    log::debug!(
-                        "processed `{}` into bundle in {}",
-                        path.display(),
-                        apply_processor_timer.duration_label()
-                    );
+                log::debug!(
+                    "processed `{}` into bundle in {}",
+                    path.display(),
+                    apply_processor_timer.duration_label()
+                );
 
-                    self.source = current_source;
+                self.source = current_source;
 
-                    Ok(RequiredResource::Block(block))
-                }
-                "json" | "json5" => {
-                    transcode("json", path, json5::from_str::<serde_json::Value>, &content)
-                }
-                "yml" | "yaml" => transcode(
-                    "yaml",
-                    path,
-                    serde_yaml::from_str::<serde_yaml::Value>,
-                    &content,
-                ),
-                "toml" => transcode("toml", path, toml::from_str::<toml::Value>, &content),
-                "txt" => Ok(RequiredResource::Expression(
-                    StringExpression::from_value(content).into(),
-                )),
-                _ => Err(DarkluaError::invalid_resource_extension(path)),
-            },
-            None => unreachable!("extension should be defined"),
+                Ok(RequiredResource::Block(block))
+            }
+            ResourceLoader::Json => {
+                transcode("json", path, json5::from_str::<serde_json::Value>, &content)
+            }
+            ResourceLoader::Yaml => transcode(
+                "yaml",
+                path,
+                serde_yaml::from_str::<serde_yaml::Value>,
+                &content,
+            ),
+            ResourceLoader::Toml => {
+                transcode("toml", path, toml::from_str::<toml::Value>, &content)
+            }
+            ResourceLoader::Text => Ok(RequiredResource::Expression(
+                StringExpression::from_value(content).into(),
+            )),
+            ResourceLoader::Csv => {
+                load_separated_values(&content, ',', "csv").map(RequiredResource::Expression)
+            }
+            ResourceLoader::Tsv => {
+                load_separated_values(&content, '\t', "tsv").map(RequiredResource::Expression)
+            }
         }
     }
 }
 
-impl Deref for RequirePathProcessor<'_, '_, '_, '_> {
+impl<L> Deref for RequirePathProcessor<'_, '_, L> {
     type Target = IdentifierTracker;
 
     fn deref(&self) -> &Self::Target {
@@ -269,7 +464,7 @@ impl Deref for RequirePathProcessor<'_, '_, '_, '_> {
     }
 }
 
-impl DerefMut for RequirePathProcessor<'_, '_, '_, '_> {
+impl<L> DerefMut for RequirePathProcessor<'_, '_, L> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.identifier_tracker
     }
@@ -300,7 +495,7 @@ where
     expression
 }
 
-impl NodeProcessor for RequirePathProcessor<'_, '_, '_, '_> {
+impl<L: FindRequirePath> NodeProcessor for RequirePathProcessor<'_, '_, L> {
     fn process_expression(&mut self, expression: &mut Expression) {
         if let Expression::Call(call) = expression {
             if let Some(replace_with) = self.try_inline_call(call) {
@@ -344,6 +539,39 @@ pub(crate) fn process_block(
     options: &BundleOptions,
     path_require_mode: &PathRequireMode,
 ) -> Result<(), String> {
+    let locator = RequirePathLocator::new(
+        path_require_mode,
+        context.project_location(),
+        context.resources(),
+    );
+    process_block_with_locator(block, context, options, locator)
+}
+
+pub(crate) fn build_shared_chunk(
+    context: &Context,
+    options: &BundleOptions,
+    shared_chunk: &SharedChunk,
+    path_require_mode: &PathRequireMode,
+) -> DarkluaResult<(Block, SharedModules)> {
+    let locator = RequirePathLocator::new(
+        path_require_mode,
+        context.project_location(),
+        context.resources(),
+    );
+    build_shared_chunk_with_locator(context, options, shared_chunk, locator)
+}
+
+/// Inlines the files found by the given locator into the block.
+pub(crate) fn process_block_with_locator(
+    block: &mut Block,
+    context: &Context,
+    options: &BundleOptions,
+    locator: impl FindRequirePath,
+) -> Result<(), String> {
+    options
+        .apply_file_rules(block, context)
+        .map_err(|err| err.to_string())?;
+
     if options.parser().is_preserving_tokens() {
         log::trace!(
             "replacing token references of {}",
@@ -362,7 +590,188 @@ pub(crate) fn process_block(
         );
     }
 
-    let mut processor = RequirePathProcessor::new(context, options, path_require_mode);
+    let mut processor = RequirePathProcessor::new(context, options, locator);
+
+    if let Some(shared_modules) = options.shared_modules(context.current_path()) {
+        // any change to the files of the other entries can change the shared modules
+        for path in shared_modules.dependencies.iter() {
+            context.add_file_dependency(path.clone());
+        }
+        processor = processor.with_shared_modules(shared_modules);
+    }
+
     ScopeVisitor::visit_block(block, &mut processor);
     processor.apply(block, context)
 }
+
+/// Finds the modules inlined by more than one entry of the shared chunk and builds the
+/// block of the shared chunk, which returns the table of these modules.
+pub(crate) fn build_shared_chunk_with_locator(
+    context: &Context,
+    options: &BundleOptions,
+    shared_chunk: &SharedChunk,
+    locator: impl FindRequirePath,
+) -> DarkluaResult<(Block, SharedModules)> {
+    let load_chunk = parse_shared_chunk_require(shared_chunk.require())?;
+
+    let mut entries_count: HashMap<PathBuf, usize> = HashMap::new();
+    let mut dependencies = Vec::new();
+
+    for entry in shared_chunk.entries() {
+        log::trace!("find modules of shared chunk entry `{}`", entry.display());
+
+        let content = context.resources().get(entry).map_err(|err| {
+            DarkluaError::from(err).context(format!(
+                "unable to read shared chunk entry `{}`",
+                entry.display()
+            ))
+        })?;
+        dependencies.push(entry.to_path_buf());
+
+        let mut block = match options.parser().parse(&content) {
+            Ok(block) => block,
+            Err(err) => {
+                // the error is reported when the entry itself is processed
+                log::debug!(
+                    "unable to parse shared chunk entry `{}`: {}",
+                    entry.display(),
+                    err
+                );
+                continue;
+            }
+        };
+
+        let entry_context = ContextBuilder::new(entry, context.resources(), &content)
+            .with_project_location(context.project_location())
+            .build();
+
+        if let Err(err) = options.apply_file_rules(&mut block, &entry_context) {
+            // the error is reported when the entry itself is processed
+            log::debug!(
+                "unable to process shared chunk entry `{}`: {}",
+                entry.display(),
+                err
+            );
+            continue;
+        }
+
+        let mut processor = RequirePathProcessor::new(&entry_context, options, &locator);
+        ScopeVisitor::visit_block(&mut block, &mut processor);
+
+        for path in processor.module_cache.into_keys() {
+            *entries_count.entry(path.clone()).or_default() += 1;
+            dependencies.push(path);
+        }
+    }
+
+    let mut shared_paths: Vec<_> = entries_count
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(path, _)| path)
+        .collect();
+    shared_paths.sort();
+
+    dependencies.sort();
+    dependencies.dedup();
+
+    if shared_paths.is_empty() {
+        log::warn!(
+            "no module is used by more than one entry of the shared chunk `{}`",
+            shared_chunk.output().display()
+        );
+        let block =
+            Block::default().with_last_statement(ReturnStatement::one(TableExpression::default()));
+
+        return Ok((
+            block,
+            SharedModules {
+                loads: Default::default(),
+                load_chunk,
+                dependencies,
+            },
+        ));
+    }
+
+    log::debug!(
+        "build shared chunk `{}` with {} module(s)",
+        shared_chunk.output().display(),
+        shared_paths.len()
+    );
+
+    let chunk_context = ContextBuilder::new(shared_chunk.output(), context.resources(), "")
+        .with_project_location(context.project_location())
+        .build();
+
+    let mut processor =
+        RequirePathProcessor::new(&chunk_context, options, &locator).for_shared_chunk();
+
+    let require_call = FunctionCall::from_name("require");
+
+    for path in shared_paths.iter() {
+        if let Err(err) = processor.inline_require(path, &require_call) {
+            processor.errors.push(err.to_string());
+        }
+    }
+
+    let shared_identifier = shared_modules_identifier(options.modules_identifier());
+
+    let loads = processor
+        .module_cache
+        .iter()
+        .filter_map(|(path, expression)| match expression {
+            Expression::Call(call) => {
+                match_load_call(call, options.modules_identifier()).map(|module_name| {
+                    (
+                        path.clone(),
+                        build_load_expression(&shared_identifier, &module_name),
+                    )
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut block = Block::default().with_last_statement(ReturnStatement::one(Identifier::new(
+        options.modules_identifier(),
+    )));
+
+    processor
+        .apply(&mut block, &chunk_context)
+        .map_err(DarkluaError::custom)?;
+
+    Ok((
+        block,
+        SharedModules {
+            loads,
+            load_chunk,
+            dependencies,
+        },
+    ))
+}
+
+fn parse_shared_chunk_require(require: &str) -> DarkluaResult<Expression> {
+    // the expression is parsed without tokens because it gets inserted in other files
+    let mut block = Parser::default()
+        .parse(&format!("return {}", require))
+        .map_err(|err| {
+            DarkluaError::custom(format!(
+                "unable to parse shared chunk require `{}`: {}",
+                require, err
+            ))
+        })?;
+
+    match block.take_last_statement() {
+        Some(LastStatement::Return(return_statement))
+            if return_statement.len() == 1 && block.is_empty() =>
+        {
+            Ok(return_statement
+                .into_iter_expressions()
+                .next()
+                .expect("return statement should have one expression"))
+        }
+        _ => Err(DarkluaError::custom(format!(
+            "invalid shared chunk require `{}`: expected a single expression",
+            require
+        ))),
+    }
+}
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use indexmap::IndexMap;
+use xxhash_rust::xxh3::xxh3_64;
 
-use crate::frontend::DarkluaResult;
+use crate::frontend::{DarkluaResult, SourceRange};
+use crate::generator::{DenseLuaGenerator, LuaGenerator};
 use crate::nodes::{
     Arguments, AssignStatement, Block, DoStatement, Expression, FieldExpression, FunctionCall,
     FunctionExpression, FunctionName, FunctionStatement, Identifier, IfStatement, IndexExpression,
@@ -11,50 +14,117 @@ use crate::nodes::{
     UnaryOperator,
 };
 use crate::process::utils::{generate_identifier, identifier_permutator, CharPermutator};
-use crate::rules::bundle::RenameTypeDeclarationProcessor;
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::rules::bundle::{BundleOptions, ModuleInitialization, RenameTypeDeclarationProcessor};
 use crate::rules::{Context, FlawlessRule, ShiftTokenLine};
 use crate::utils::lines;
 use crate::DarkluaError;
 
+use super::tree_shake::tree_shake_modules;
 use super::RequiredResource;
 
 #[derive(Debug)]
 pub(crate) struct BuildModuleDefinitions {
     modules_identifier: String,
     module_definitions: IndexMap<String, ModuleDefinition>,
+    cyclic_module_names: HashMap<PathBuf, String>,
+    module_hashes: ModuleHashes,
     module_name_permutator: CharPermutator,
     rename_type_declaration: RenameTypeDeclarationProcessor,
+    tree_shake: bool,
+    initialization: ModuleInitialization,
+    deduplicate: bool,
+}
+
+/// The code of the modules inserted in the bundle, grouped by the hash of their code. The
+/// code is compared when hashes match, so that different modules are never merged.
+#[derive(Debug, Default)]
+struct ModuleHashes {
+    modules: HashMap<u64, Vec<(String, String)>>,
+}
+
+impl ModuleHashes {
+    fn find(&self, code: &str) -> Option<&str> {
+        self.find_with_hash(xxh3_64(code.as_bytes()), code)
+    }
+
+    fn find_with_hash(&self, hash: u64, code: &str) -> Option<&str> {
+        self.modules.get(&hash).and_then(|modules| {
+            modules
+                .iter()
+                .find(|(module_code, _)| module_code == code)
+                .map(|(_, module_name)| module_name.as_str())
+        })
+    }
+
+    fn insert(&mut self, code: String, module_name: String) {
+        self.insert_with_hash(xxh3_64(code.as_bytes()), code, module_name);
+    }
+
+    fn insert_with_hash(&mut self, hash: u64, code: String, module_name: String) {
+        self.modules
+            .entry(hash)
+            .or_default()
+            .push((code, module_name));
+    }
 }
 
 #[derive(Debug)]
 struct ModuleDefinition {
     block: Block,
     path: PathBuf,
+    duplicate_paths: Vec<PathBuf>,
 }
 
 impl ModuleDefinition {
     fn new(block: Block, path: PathBuf) -> Self {
-        Self { block, path }
+        Self {
+            block,
+            path,
+            duplicate_paths: Vec::new(),
+        }
     }
 }
 
-const BUNDLE_MODULES_VARIABLE_LOAD_FIELD: &str = "load";
+/// A module inserted in the bundle, with the size of its code (generated with the dense
+/// generator) and the paths of the identical modules merged into it.
+#[derive(Debug)]
+pub(crate) struct BundledModule {
+    pub(crate) path: PathBuf,
+    pub(crate) size: usize,
+    pub(crate) duplicate_paths: Vec<PathBuf>,
+}
+
+pub(super) const BUNDLE_MODULES_VARIABLE_LOAD_FIELD: &str = "load";
 const BUNDLE_MODULES_VARIABLE_CACHE_FIELD: &str = "cache";
 
 impl BuildModuleDefinitions {
-    pub(crate) fn new(modules_identifier: impl Into<String>) -> Self {
-        let modules_identifier = modules_identifier.into();
+    pub(crate) fn new(options: &BundleOptions) -> Self {
+        let modules_identifier = options.modules_identifier().to_owned();
         Self {
             modules_identifier: modules_identifier.clone(),
             module_definitions: Default::default(),
+            cyclic_module_names: Default::default(),
+            module_hashes: Default::default(),
             module_name_permutator: identifier_permutator(),
             rename_type_declaration: RenameTypeDeclarationProcessor::new(
                 modules_identifier,
                 BUNDLE_MODULES_VARIABLE_LOAD_FIELD,
             ),
+            tree_shake: options.tree_shake(),
+            initialization: options.initialization(),
+            deduplicate: options.deduplicate(),
         }
     }
 
+    /// Disables tree shaking and always initializes modules lazily, because the modules
+    /// of a shared chunk are used by code outside of the chunk.
+    pub(crate) fn for_shared_chunk(mut self) -> Self {
+        self.tree_shake = false;
+        self.initialization = ModuleInitialization::Lazy;
+        self
+    }
+
     pub(crate) fn build_module_from_resource(
         &mut self,
         required_resource: RequiredResource,
@@ -83,11 +153,39 @@ impl BuildModuleDefinitions {
             }
         };
 
+        let cyclic_module_name = self.cyclic_module_names.remove(require_path);
+
+        // modules in a require cycle are already loaded using their own name
+        let module_code = if self.deduplicate && cyclic_module_name.is_none() {
+            let code = generate_dense_code(&block);
+
+            if let Some(module_name) = self.module_hashes.find(&code) {
+                log::debug!(
+                    "merge `{}` with identical module `{}`",
+                    require_path.display(),
+                    module_name
+                );
+                let module_name = module_name.to_owned();
+                if let Some(module) = self.module_definitions.get_mut(&module_name) {
+                    module.duplicate_paths.push(require_path.to_path_buf());
+                }
+                return Ok(self.build_load_call(module_name, call));
+            }
+
+            Some(code)
+        } else {
+            None
+        };
+
         let exported_types = self
             .rename_type_declaration
             .extract_exported_types(&mut block);
 
-        let module_name = self.generate_module_name();
+        let module_name = cyclic_module_name.unwrap_or_else(|| self.generate_module_name());
+
+        if let Some(code) = module_code {
+            self.module_hashes.insert(code, module_name.clone());
+        }
 
         self.module_definitions.insert(
             module_name.clone(),
@@ -96,6 +194,29 @@ impl BuildModuleDefinitions {
         self.rename_type_declaration
             .insert_module_types(module_name.clone(), exported_types);
 
+        Ok(self.build_load_call(module_name, call))
+    }
+
+    /// Creates the call to load a module that is still being built because it is part
+    /// of a require cycle. The module will use the same name once it is built.
+    pub(crate) fn build_cyclic_require(
+        &mut self,
+        require_path: &Path,
+        call: &FunctionCall,
+    ) -> Expression {
+        let module_name = if let Some(name) = self.cyclic_module_names.get(require_path) {
+            name.clone()
+        } else {
+            let name = self.generate_module_name();
+            self.cyclic_module_names
+                .insert(require_path.to_path_buf(), name.clone());
+            name
+        };
+
+        self.build_load_call(module_name, call)
+    }
+
+    fn build_load_call(&self, module_name: String, call: &FunctionCall) -> Expression {
         let token_trivia_identifier = match call.get_prefix() {
             Prefix::Identifier(require_identifier) => require_identifier.get_token(),
             _ => None,
@@ -143,14 +264,12 @@ impl BuildModuleDefinitions {
             Arguments::Table(_) => TupleArguments::default(),
         };
 
-        let new_require_call = FunctionCall::from_prefix(FieldExpression::new(
+        FunctionCall::from_prefix(FieldExpression::new(
             Identifier::from(&self.modules_identifier),
             load_field,
         ))
         .with_arguments(arguments.with_argument(StringExpression::from_value(module_name)))
-        .into();
-
-        Ok(new_require_call)
+        .into()
     }
 
     fn generate_module_name(&mut self) -> String {
@@ -165,18 +284,46 @@ impl BuildModuleDefinitions {
         }
     }
 
-    pub(crate) fn apply(mut self, block: &mut Block, context: &Context) {
+    /// Inserts the module definitions into the block. When `module_sizes` is provided, the
+    /// size of each module is pushed into it.
+    pub(crate) fn apply(
+        mut self,
+        block: &mut Block,
+        context: &Context,
+        module_sizes: Option<&mut Vec<BundledModule>>,
+    ) {
         if self.module_definitions.is_empty() {
             return;
         }
 
         for module in self.module_definitions.values() {
             context.add_file_dependency(module.path.clone());
+            for duplicate_path in module.duplicate_paths.iter() {
+                context.add_file_dependency(duplicate_path.clone());
+            }
         }
 
-        self.rename_type_declaration.rename_types(block);
+        if self.tree_shake {
+            tree_shake_modules(
+                &self.modules_identifier,
+                block,
+                self.module_definitions.iter_mut().map(|(name, module)| {
+                    (name.as_str(), module.path.as_path(), &mut module.block)
+                }),
+            );
+        }
 
-        let modules_identifier = Identifier::from(&self.modules_identifier);
+        if let Some(module_sizes) = module_sizes {
+            for module in self.module_definitions.values() {
+                module_sizes.push(BundledModule {
+                    path: module.path.clone(),
+                    size: generate_dense_code(&module.block).len(),
+                    duplicate_paths: module.duplicate_paths.clone(),
+                });
+            }
+        }
+
+        self.rename_type_declaration.rename_types(block);
 
         let mut shift_lines = self.rename_type_declaration.get_type_lines();
         for module in self.module_definitions.values_mut() {
@@ -184,11 +331,43 @@ impl BuildModuleDefinitions {
 
             ShiftTokenLine::new(shift_lines).flawless_process(&mut module.block, context);
 
+            context.add_source_range(SourceRange::new(
+                &module.path,
+                shift_lines as usize + 1,
+                Some(shift_lines as usize + inserted_lines),
+                shift_lines as usize,
+            ));
+
             shift_lines += inserted_lines as isize;
         }
 
         ShiftTokenLine::new(shift_lines).flawless_process(block, context);
 
+        context.add_source_range(SourceRange::new(
+            context.current_path(),
+            shift_lines as usize + 1,
+            None,
+            shift_lines as usize,
+        ));
+
+        match self.initialization {
+            ModuleInitialization::Lazy => self.insert_lazy_modules(block),
+            ModuleInitialization::Eager => self.insert_eager_modules(block),
+        }
+
+        for statement in self
+            .rename_type_declaration
+            .extract_type_declarations()
+            .into_iter()
+            .rev()
+        {
+            block.insert_statement(0, statement);
+        }
+    }
+
+    fn insert_lazy_modules(&mut self, block: &mut Block) {
+        let modules_identifier = Identifier::from(&self.modules_identifier);
+
         let statements = self
             .module_definitions
             .drain(..)
@@ -208,15 +387,78 @@ impl BuildModuleDefinitions {
         );
         block.insert_statement(
             0,
-            LocalAssignStatement::from_variable(self.modules_identifier),
+            LocalAssignStatement::from_variable(self.modules_identifier.clone()),
         );
+    }
 
-        for statement in self
-            .rename_type_declaration
-            .extract_type_declarations()
-            .into_iter()
-            .rev()
-        {
+    /// Inlines each module in dependency order, storing its value in a local variable
+    /// that replaces the calls to the module loading function.
+    fn insert_eager_modules(&mut self, block: &mut Block) {
+        let module_variables: HashMap<String, String> = self
+            .module_definitions
+            .keys()
+            .map(|module_name| {
+                (
+                    module_name.clone(),
+                    format!("{}_{}", self.modules_identifier, module_name),
+                )
+            })
+            .collect();
+
+        let mut inline_loads = InlineModuleLoads::new(&self.modules_identifier, &module_variables);
+
+        DefaultVisitor::visit_block(block, &mut inline_loads);
+
+        let mut statements: Vec<Statement> = Vec::new();
+
+        for (module_name, mut module) in self.module_definitions.drain(..) {
+            DefaultVisitor::visit_block(&mut module.block, &mut inline_loads);
+
+            let variable = module_variables[&module_name].as_str();
+
+            if has_nested_return(&module.block) {
+                log::debug!(
+                    "module `{}` returns from a nested block and is wrapped in a function",
+                    module.path.display()
+                );
+                statements.push(
+                    LocalAssignStatement::from_variable(variable)
+                        .with_value(FunctionCall::from_prefix(Expression::from(
+                            FunctionExpression::from_block(module.block),
+                        )))
+                        .into(),
+                );
+                continue;
+            }
+
+            // modules are validated to end with a return statement of exactly one value
+            let value = match module.block.take_last_statement() {
+                Some(LastStatement::Return(return_statement)) => {
+                    return_statement.into_iter_expressions().next()
+                }
+                _ => None,
+            }
+            .unwrap_or_else(Expression::nil);
+
+            if module.block.is_empty() {
+                statements.push(
+                    LocalAssignStatement::from_variable(variable)
+                        .with_value(value)
+                        .into(),
+                );
+            } else {
+                statements.push(LocalAssignStatement::from_variable(variable).into());
+                statements.push(
+                    DoStatement::new(module.block.with_statement(AssignStatement::from_variable(
+                        Identifier::new(variable),
+                        value,
+                    )))
+                    .into(),
+                );
+            }
+        }
+
+        for statement in statements.into_iter().rev() {
             block.insert_statement(0, statement);
         }
     }
@@ -264,6 +506,119 @@ impl BuildModuleDefinitions {
     }
 }
 
+fn generate_dense_code(block: &Block) -> String {
+    let mut generator = DenseLuaGenerator::default();
+    generator.write_block(block);
+    generator.into_string()
+}
+
+/// Creates a call to load a module from the given modules identifier.
+pub(super) fn build_load_expression(modules_identifier: &str, module_name: &str) -> Expression {
+    FunctionCall::from_prefix(FieldExpression::new(
+        Identifier::new(modules_identifier),
+        BUNDLE_MODULES_VARIABLE_LOAD_FIELD,
+    ))
+    .with_argument(StringExpression::from_value(module_name))
+    .into()
+}
+
+pub(super) fn match_load_call(call: &FunctionCall, modules_identifier: &str) -> Option<String> {
+    if call.get_method().is_some() {
+        return None;
+    }
+
+    match call.get_prefix() {
+        Prefix::Field(field)
+            if field.get_field().get_name() == BUNDLE_MODULES_VARIABLE_LOAD_FIELD
+                && matches!(
+                    field.get_prefix(),
+                    Prefix::Identifier(identifier) if identifier.get_name() == modules_identifier
+                ) => {}
+        _ => return None,
+    }
+
+    match call.get_arguments() {
+        Arguments::Tuple(tuple) if tuple.len() == 1 => match tuple.iter_values().next() {
+            Some(Expression::String(string)) => Some(string.get_value().to_owned()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+struct InlineModuleLoads<'a> {
+    modules_identifier: &'a str,
+    module_variables: &'a HashMap<String, String>,
+}
+
+impl<'a> InlineModuleLoads<'a> {
+    fn new(modules_identifier: &'a str, module_variables: &'a HashMap<String, String>) -> Self {
+        Self {
+            modules_identifier,
+            module_variables,
+        }
+    }
+
+    fn get_variable(&self, call: &FunctionCall) -> Option<Identifier> {
+        match_load_call(call, self.modules_identifier)
+            .and_then(|module_name| self.module_variables.get(&module_name))
+            .map(Identifier::new)
+    }
+}
+
+impl NodeProcessor for InlineModuleLoads<'_> {
+    fn process_block(&mut self, block: &mut Block) {
+        // modules are already executed, so loading them as a statement does nothing
+        block.filter_statements(|statement| match statement {
+            Statement::Call(call) => self.get_variable(call).is_none(),
+            _ => true,
+        });
+    }
+
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(variable) = self.get_variable(call) {
+                *expression = variable.into();
+            }
+        }
+    }
+
+    fn process_prefix_expression(&mut self, prefix: &mut Prefix) {
+        if let Prefix::Call(call) = prefix {
+            if let Some(variable) = self.get_variable(call) {
+                *prefix = variable.into();
+            }
+        }
+    }
+}
+
+// returns true if the block has a return statement that is not at the end of the
+// block (in nested blocks like if statements or loops, but not in functions)
+fn has_nested_return(block: &Block) -> bool {
+    block.iter_statements().any(|statement| {
+        let nested_blocks: Vec<&Block> = match statement {
+            Statement::Do(do_statement) => vec![do_statement.get_block()],
+            Statement::If(if_statement) => if_statement
+                .iter_branches()
+                .map(|branch| branch.get_block())
+                .chain(if_statement.get_else_block())
+                .collect(),
+            Statement::While(while_statement) => vec![while_statement.get_block()],
+            Statement::Repeat(repeat_statement) => vec![repeat_statement.get_block()],
+            Statement::NumericFor(numeric_for) => vec![numeric_for.get_block()],
+            Statement::GenericFor(generic_for) => vec![generic_for.get_block()],
+            _ => Vec::new(),
+        };
+
+        nested_blocks.into_iter().any(|nested_block| {
+            matches!(
+                nested_block.get_last_statement(),
+                Some(LastStatement::Return(_))
+            ) || has_nested_return(nested_block)
+        })
+    })
+}
+
 fn transfer_trivia(mut receiving_token: Token, take_token: &Token) -> Token {
     for (content, kind) in take_token.iter_trailing_trivia().filter_map(|trivia| {
         trivia
@@ -275,3 +630,30 @@ fn transfer_trivia(mut receiving_token: Token, take_token: &Token) -> Token {
     }
     receiving_token
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find_module_with_same_code() {
+        let mut hashes = ModuleHashes::default();
+        hashes.insert("return 1".to_owned(), "a".to_owned());
+
+        pretty_assertions::assert_eq!(hashes.find("return 1"), Some("a"));
+        pretty_assertions::assert_eq!(hashes.find("return 2"), None);
+    }
+
+    #[test]
+    fn modules_with_colliding_hashes_are_not_merged() {
+        let mut hashes = ModuleHashes::default();
+        hashes.insert_with_hash(0, "return 1".to_owned(), "a".to_owned());
+
+        pretty_assertions::assert_eq!(hashes.find_with_hash(0, "return 2"), None);
+
+        hashes.insert_with_hash(0, "return 2".to_owned(), "b".to_owned());
+
+        pretty_assertions::assert_eq!(hashes.find_with_hash(0, "return 1"), Some("a"));
+        pretty_assertions::assert_eq!(hashes.find_with_hash(0, "return 2"), Some("b"));
+    }
+}
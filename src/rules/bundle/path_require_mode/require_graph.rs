@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use petgraph::algo::{astar, has_path_connecting};
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use crate::generator::{DenseLuaGenerator, LuaGenerator};
+use crate::nodes::{FunctionCall, Prefix};
+
+/// The location of a `require` call that links two modules of the bundle.
+#[derive(Debug, Clone)]
+pub(crate) struct RequireSite {
+    code: String,
+    line: Option<usize>,
+}
+
+impl RequireSite {
+    pub(crate) fn from_call(call: &FunctionCall) -> Self {
+        let mut generator = DenseLuaGenerator::default();
+        generator.write_function_call(call);
+
+        let line = match call.get_prefix() {
+            Prefix::Identifier(identifier) => identifier
+                .get_token()
+                .and_then(|token| token.get_line_number()),
+            _ => None,
+        };
+
+        Self {
+            code: generator.into_string(),
+            line,
+        }
+    }
+}
+
+impl fmt::Display for RequireSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`", self.code)?;
+        if let Some(line) = self.line {
+            write!(f, " at line {}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// The graph of the modules inlined in a bundle, where each edge is a `require` call.
+#[derive(Debug, Default)]
+pub(crate) struct RequireGraph {
+    graph: DiGraph<PathBuf, RequireSite>,
+    nodes: HashMap<PathBuf, NodeIndex>,
+}
+
+impl RequireGraph {
+    fn get_or_insert_node(&mut self, path: &Path) -> NodeIndex {
+        if let Some(index) = self.nodes.get(path) {
+            *index
+        } else {
+            let index = self.graph.add_node(path.to_path_buf());
+            self.nodes.insert(path.to_path_buf(), index);
+            index
+        }
+    }
+
+    /// Adds a require from the `source` module to the `required` module and returns the
+    /// cycle that this new edge closes, if any.
+    pub(crate) fn add_require(
+        &mut self,
+        source: &Path,
+        required: &Path,
+        site: RequireSite,
+    ) -> Option<RequireCycle> {
+        let source_index = self.get_or_insert_node(source);
+        let required_index = self.get_or_insert_node(required);
+
+        let edge = self.graph.add_edge(source_index, required_index, site);
+
+        if !has_path_connecting(&self.graph, required_index, source_index, None) {
+            return None;
+        }
+
+        let (_, path) = astar(
+            &self.graph,
+            required_index,
+            |node| node == source_index,
+            |_| 1,
+            |_| 0,
+        )?;
+
+        let mut requires: Vec<_> = path
+            .windows(2)
+            .filter_map(|nodes| {
+                let edge = self.graph.find_edge(nodes[0], nodes[1])?;
+                Some(self.get_require(nodes[0], nodes[1], &self.graph[edge]))
+            })
+            .collect();
+        requires.push(self.get_require(source_index, required_index, &self.graph[edge]));
+
+        Some(RequireCycle { requires })
+    }
+
+    /// Returns the modules directly required by the given module, in the order of their
+    /// first require.
+    pub(crate) fn dependencies(&self, path: &Path) -> Vec<PathBuf> {
+        let index = match self.nodes.get(path) {
+            Some(index) => *index,
+            None => return Vec::new(),
+        };
+
+        let mut edges: Vec<_> = self.graph.edges(index).map(|edge| edge.id()).collect();
+        edges.sort();
+
+        let mut dependencies: Vec<PathBuf> = Vec::new();
+        for edge in edges {
+            if let Some((_, target)) = self.graph.edge_endpoints(edge) {
+                let target_path = &self.graph[target];
+                if !dependencies.contains(target_path) {
+                    dependencies.push(target_path.clone());
+                }
+            }
+        }
+        dependencies
+    }
+
+    fn get_require(&self, from: NodeIndex, to: NodeIndex, site: &RequireSite) -> CyclicRequire {
+        CyclicRequire {
+            source: self.graph[from].clone(),
+            required: self.graph[to].clone(),
+            site: site.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CyclicRequire {
+    source: PathBuf,
+    required: PathBuf,
+    site: RequireSite,
+}
+
+/// A sequence of requires that starts and ends with the same module.
+#[derive(Debug)]
+pub(crate) struct RequireCycle {
+    requires: Vec<CyclicRequire>,
+}
+
+impl fmt::Display for RequireCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let paths: Vec<_> = self
+            .requires
+            .iter()
+            .map(|require| require.source.display().to_string())
+            .chain(
+                self.requires
+                    .last()
+                    .map(|require| require.required.display().to_string()),
+            )
+            .collect();
+
+        write!(f, "cyclic require detected with `{}`", paths.join("` > `"))?;
+
+        for require in self.requires.iter() {
+            write!(
+                f,
+                "\n    `{}` requires `{}` with {}",
+                require.source.display(),
+                require.required.display(),
+                require.site
+            )?;
+        }
+
+        Ok(())
+    }
+}
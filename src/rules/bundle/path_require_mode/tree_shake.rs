@@ -0,0 +1,610 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::nodes::{
+    AssignStatement, Block, Expression, FieldExpression, FunctionCall, FunctionStatement,
+    Identifier, LastStatement, Prefix, Statement, TableEntry, TableExpression, Variable,
+};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+
+use super::module_definitions::match_load_call;
+
+/// Removes the fields of bundled modules that are never read by the code that requires them,
+/// along with the local functions that are only reachable from these fields.
+pub(crate) fn tree_shake_modules<'a>(
+    modules_identifier: &str,
+    block: &mut Block,
+    modules: impl Iterator<Item = (&'a str, &'a Path, &'a mut Block)>,
+) {
+    let mut modules: Vec<_> = modules.collect();
+
+    let mut collector = ModuleUsageCollector::new(modules_identifier);
+    DefaultVisitor::visit_block(block, &mut collector);
+    for (_, _, module_block) in modules.iter_mut() {
+        DefaultVisitor::visit_block(module_block, &mut collector);
+    }
+    let usages = collector.into_usages();
+
+    for (module_name, module_path, module_block) in modules {
+        match usages.get(module_name) {
+            Some(ModuleUsage::Fields(fields)) => {
+                let removed = shake_module(module_block, fields);
+                if removed > 0 {
+                    log::debug!(
+                        "tree shaking removed {} unused definition(s) from `{}`",
+                        removed,
+                        module_path.display()
+                    );
+                }
+            }
+            Some(ModuleUsage::All) => {
+                log::trace!(
+                    "skip tree shaking of `{}` because the module value is not only indexed",
+                    module_path.display()
+                );
+            }
+            None => {}
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ModuleUsage {
+    All,
+    Fields(HashSet<String>),
+}
+
+impl Default for ModuleUsage {
+    fn default() -> Self {
+        Self::Fields(HashSet::new())
+    }
+}
+
+impl ModuleUsage {
+    fn add_field(&mut self, field: &str) {
+        if let Self::Fields(fields) = self {
+            fields.insert(field.to_owned());
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ModuleUsageCollector<'a> {
+    modules_identifier: &'a str,
+    usages: HashMap<String, ModuleUsage>,
+    load_calls: HashMap<String, usize>,
+    analyzed_calls: HashMap<String, usize>,
+}
+
+impl<'a> ModuleUsageCollector<'a> {
+    fn new(modules_identifier: &'a str) -> Self {
+        Self {
+            modules_identifier,
+            usages: Default::default(),
+            load_calls: Default::default(),
+            analyzed_calls: Default::default(),
+        }
+    }
+
+    fn match_load_call(&self, call: &FunctionCall) -> Option<String> {
+        match_load_call(call, self.modules_identifier)
+    }
+
+    fn match_local_load(&self, statement: &Statement) -> Option<(String, String)> {
+        match statement {
+            Statement::LocalAssign(assign)
+                if assign.variables_len() == 1 && assign.values_len() == 1 =>
+            {
+                match assign.iter_values().next() {
+                    Some(Expression::Call(call)) => self.match_load_call(call).map(|module_name| {
+                        (assign.get_variables()[0].get_name().to_owned(), module_name)
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn add_analyzed_call(&mut self, module_name: &str) -> &mut ModuleUsage {
+        *self
+            .analyzed_calls
+            .entry(module_name.to_owned())
+            .or_default() += 1;
+        self.usages.entry(module_name.to_owned()).or_default()
+    }
+
+    fn into_usages(mut self) -> HashMap<String, ModuleUsage> {
+        for (module_name, count) in self.load_calls {
+            if self.analyzed_calls.get(&module_name).copied().unwrap_or(0) < count {
+                self.usages.insert(module_name, ModuleUsage::All);
+            }
+        }
+        self.usages
+    }
+}
+
+impl NodeProcessor for ModuleUsageCollector<'_> {
+    fn process_block(&mut self, block: &mut Block) {
+        let local_loads: Vec<_> = block
+            .iter_statements()
+            .enumerate()
+            .filter_map(|(index, statement)| {
+                self.match_local_load(statement)
+                    .map(|(variable, module_name)| (index, variable, module_name))
+            })
+            .collect();
+
+        for (index, variable, module_name) in local_loads {
+            let mut usage = IdentifierUsage::new(&variable);
+
+            for statement in block.iter_mut_statements().skip(index + 1) {
+                DefaultVisitor::visit_statement(statement, &mut usage);
+            }
+            if let Some(last_statement) = block.mutate_last_statement() {
+                DefaultVisitor::visit_last_statement(last_statement, &mut usage);
+            }
+
+            let module_usage = self.add_analyzed_call(&module_name);
+
+            if usage.is_only_indexed() {
+                for field in usage.fields.iter() {
+                    module_usage.add_field(field);
+                }
+            } else {
+                *module_usage = ModuleUsage::All;
+            }
+        }
+    }
+
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        if let Some(module_name) = self.match_load_call(call) {
+            *self.load_calls.entry(module_name).or_default() += 1;
+        }
+    }
+
+    fn process_field_expression(&mut self, field: &mut FieldExpression) {
+        if let Prefix::Call(call) = field.get_prefix() {
+            if let Some(module_name) = self.match_load_call(call) {
+                self.add_analyzed_call(&module_name)
+                    .add_field(field.get_field().get_name());
+            }
+        }
+    }
+}
+
+/// Counts the references to an identifier to find if it is only used to index fields.
+#[derive(Debug)]
+struct IdentifierUsage<'a> {
+    name: &'a str,
+    references: usize,
+    field_references: usize,
+    fields: HashSet<String>,
+}
+
+impl<'a> IdentifierUsage<'a> {
+    fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            references: 0,
+            field_references: 0,
+            fields: HashSet::new(),
+        }
+    }
+
+    fn is_only_indexed(&self) -> bool {
+        self.references == self.field_references
+    }
+}
+
+impl NodeProcessor for IdentifierUsage<'_> {
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        if identifier.get_name() == self.name {
+            self.references += 1;
+        }
+    }
+
+    fn process_field_expression(&mut self, field: &mut FieldExpression) {
+        if is_identifier(field.get_prefix(), self.name) {
+            self.field_references += 1;
+            self.fields.insert(field.get_field().get_name().to_owned());
+        }
+    }
+
+    fn process_function_statement(&mut self, statement: &mut FunctionStatement) {
+        if let Some(field) = match_field_function(statement, self.name) {
+            self.field_references += 1;
+            self.fields.insert(field.to_owned());
+        }
+    }
+}
+
+/// Collects the identifiers and the fields of the exported table that a node refers to.
+#[derive(Debug)]
+struct References<'a> {
+    export_name: Option<&'a str>,
+    identifiers: HashSet<String>,
+    fields: HashSet<String>,
+}
+
+impl<'a> References<'a> {
+    fn new(export_name: Option<&'a str>) -> Self {
+        Self {
+            export_name,
+            identifiers: HashSet::new(),
+            fields: HashSet::new(),
+        }
+    }
+
+    fn from_statement(statement: &mut Statement, export_name: Option<&'a str>) -> Self {
+        let mut references = Self::new(export_name);
+        DefaultVisitor::visit_statement(statement, &mut references);
+        references
+    }
+
+    fn from_expression(expression: &mut Expression, export_name: Option<&'a str>) -> Self {
+        let mut references = Self::new(export_name);
+        DefaultVisitor::visit_expression(expression, &mut references);
+        references
+    }
+
+    fn from_entry(entry: &mut TableEntry, export_name: Option<&'a str>) -> Self {
+        let mut references = Self::new(export_name);
+        match entry {
+            TableEntry::Field(entry) => {
+                DefaultVisitor::visit_expression(entry.mutate_value(), &mut references)
+            }
+            TableEntry::Index(entry) => {
+                DefaultVisitor::visit_expression(entry.mutate_key(), &mut references);
+                DefaultVisitor::visit_expression(entry.mutate_value(), &mut references);
+            }
+            TableEntry::Value(value) => DefaultVisitor::visit_expression(value, &mut references),
+        }
+        references
+    }
+
+    fn extend(&mut self, other: Self) {
+        self.identifiers.extend(other.identifiers);
+        self.fields.extend(other.fields);
+    }
+}
+
+impl NodeProcessor for References<'_> {
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        self.identifiers.insert(identifier.get_name().to_owned());
+    }
+
+    fn process_field_expression(&mut self, field: &mut FieldExpression) {
+        if let Some(export_name) = self.export_name {
+            if is_identifier(field.get_prefix(), export_name) {
+                self.fields.insert(field.get_field().get_name().to_owned());
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ExportedTable {
+    /// The module returns a table constructor
+    Constructor,
+    /// The module returns a local variable assigned to a table constructor
+    Local { name: String, index: usize },
+}
+
+impl ExportedTable {
+    fn find(block: &mut Block) -> Option<Self> {
+        let name = match block.get_last_statement() {
+            Some(LastStatement::Return(statement)) if statement.len() == 1 => {
+                match statement.iter_expressions().next() {
+                    Some(Expression::Table(_)) => return Some(Self::Constructor),
+                    Some(Expression::Identifier(identifier)) => identifier.get_name().to_owned(),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        let index = {
+            let mut declarations = block
+                .iter_statements()
+                .enumerate()
+                .filter(|(_, statement)| match statement {
+                    Statement::LocalAssign(assign) => assign
+                        .iter_variables()
+                        .any(|variable| variable.get_name() == &name),
+                    Statement::LocalFunction(function) => function.get_name() == name,
+                    _ => false,
+                });
+
+            match (declarations.next(), declarations.next()) {
+                (Some((index, Statement::LocalAssign(assign))), None)
+                    if assign.variables_len() == 1
+                        && assign.values_len() == 1
+                        && matches!(assign.iter_values().next(), Some(Expression::Table(_))) =>
+                {
+                    index
+                }
+                _ => return None,
+            }
+        };
+
+        let mut usage = IdentifierUsage::new(&name);
+        DefaultVisitor::visit_block(block, &mut usage);
+
+        // the returned identifier is the only reference that is not indexing a field
+        if usage.references != usage.field_references + 1 {
+            return None;
+        }
+
+        Some(Self::Local { name, index })
+    }
+
+    fn export_name(&self) -> Option<&str> {
+        match self {
+            Self::Constructor => None,
+            Self::Local { name, .. } => Some(name),
+        }
+    }
+
+    fn local_index(&self) -> Option<usize> {
+        match self {
+            Self::Constructor => None,
+            Self::Local { index, .. } => Some(*index),
+        }
+    }
+
+    fn mutate_table<'b>(&self, block: &'b mut Block) -> Option<&'b mut TableExpression> {
+        let expression = match self {
+            Self::Constructor => match block.mutate_last_statement() {
+                Some(LastStatement::Return(statement)) => statement.iter_mut_expressions().next(),
+                _ => None,
+            },
+            Self::Local { index, .. } => match block.iter_mut_statements().nth(*index) {
+                Some(Statement::LocalAssign(assign)) => assign.iter_mut_values().next(),
+                _ => None,
+            },
+        };
+
+        match expression {
+            Some(Expression::Table(table)) => Some(table),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Definitions {
+    references: Vec<(HashSet<String>, HashSet<String>)>,
+}
+
+impl Definitions {
+    fn push(&mut self, references: References) {
+        self.references
+            .push((references.identifiers, references.fields));
+    }
+}
+
+fn shake_module(block: &mut Block, used_fields: &HashSet<String>) -> usize {
+    let exported_table = match ExportedTable::find(block) {
+        Some(exported_table) => exported_table,
+        None => return 0,
+    };
+    let export_name = exported_table.export_name().map(ToOwned::to_owned);
+    let export_name = export_name.as_deref();
+
+    let mut roots = References::new(export_name);
+    let mut field_definitions: HashMap<String, Definitions> = HashMap::new();
+    let mut function_definitions: HashMap<String, Definitions> = HashMap::new();
+    let mut removable_entries: HashMap<usize, String> = HashMap::new();
+    let mut removable_statements: HashMap<usize, (bool, String)> = HashMap::new();
+
+    for (index, statement) in block.iter_mut_statements().enumerate() {
+        if Some(index) == exported_table.local_index() {
+            continue;
+        }
+
+        let definition = match statement {
+            Statement::Function(function) => export_name
+                .and_then(|name| match_field_function(function, name))
+                .map(|field| (true, field.to_owned())),
+            Statement::Assign(assign) => export_name
+                .and_then(|name| match_field_assignment(assign, name))
+                .map(|field| (true, field)),
+            Statement::LocalFunction(function) => Some((false, function.get_name().to_owned())),
+            _ => None,
+        };
+
+        match definition {
+            Some((is_field, name)) => {
+                let references = match statement {
+                    Statement::Assign(assign) => References::from_expression(
+                        assign
+                            .iter_mut_values()
+                            .next()
+                            .expect("assignment has one value"),
+                        export_name,
+                    ),
+                    _ => References::from_statement(statement, export_name),
+                };
+                let definitions = if is_field {
+                    &mut field_definitions
+                } else {
+                    &mut function_definitions
+                };
+                definitions
+                    .entry(name.clone())
+                    .or_default()
+                    .push(references);
+                removable_statements.insert(index, (is_field, name));
+            }
+            None => {
+                roots.extend(References::from_statement(statement, export_name));
+            }
+        }
+    }
+
+    if let Some(table) = exported_table.mutate_table(block) {
+        for (index, entry) in table.iter_mut_entries().enumerate() {
+            let field = match entry {
+                TableEntry::Field(field_entry) if is_pure(field_entry.get_value()) => {
+                    Some(field_entry.get_field().get_name().to_owned())
+                }
+                _ => None,
+            };
+            let references = References::from_entry(entry, export_name);
+
+            if let Some(field) = field {
+                field_definitions
+                    .entry(field.clone())
+                    .or_default()
+                    .push(references);
+                removable_entries.insert(index, field);
+            } else {
+                roots.extend(references);
+            }
+        }
+    } else {
+        return 0;
+    }
+
+    let mut kept_fields: HashSet<String> = used_fields.iter().cloned().collect();
+    kept_fields.extend(roots.fields.iter().cloned());
+    let mut kept_identifiers: HashSet<String> = roots.identifiers.clone();
+
+    let mut field_queue: Vec<String> = kept_fields.iter().cloned().collect();
+    let mut identifier_queue: Vec<String> = kept_identifiers.iter().cloned().collect();
+
+    loop {
+        let definitions = if let Some(field) = field_queue.pop() {
+            field_definitions.get(&field)
+        } else if let Some(identifier) = identifier_queue.pop() {
+            function_definitions.get(&identifier)
+        } else {
+            break;
+        };
+
+        for (identifiers, fields) in definitions.iter().flat_map(|d| d.references.iter()) {
+            for field in fields {
+                if kept_fields.insert(field.clone()) {
+                    field_queue.push(field.clone());
+                }
+            }
+            for identifier in identifiers {
+                if kept_identifiers.insert(identifier.clone()) {
+                    identifier_queue.push(identifier.clone());
+                }
+            }
+        }
+    }
+
+    let remove_entries: HashSet<usize> = removable_entries
+        .into_iter()
+        .filter(|(_, field)| !kept_fields.contains(field))
+        .map(|(index, _)| index)
+        .collect();
+    let remove_statements: HashSet<usize> = removable_statements
+        .into_iter()
+        .filter(|(_, (is_field, name))| {
+            if *is_field {
+                !kept_fields.contains(name)
+            } else {
+                !kept_identifiers.contains(name)
+            }
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    if !remove_entries.is_empty() {
+        if let Some(table) = exported_table.mutate_table(block) {
+            remove_table_entries(table, &remove_entries);
+        }
+    }
+
+    if !remove_statements.is_empty() {
+        let mut index = 0;
+        block.filter_statements(|_| {
+            let keep = !remove_statements.contains(&index);
+            index += 1;
+            keep
+        });
+    }
+
+    remove_entries.len() + remove_statements.len()
+}
+
+fn remove_table_entries(table: &mut TableExpression, remove: &HashSet<usize>) {
+    if let Some(mut tokens) = table.get_tokens().cloned() {
+        let mut index = 0;
+        tokens.separators.retain(|_| {
+            let keep = !remove.contains(&index);
+            index += 1;
+            keep
+        });
+        table.set_tokens(tokens);
+    }
+
+    let mut index = 0;
+    table.mutate_entries().retain(|_| {
+        let keep = !remove.contains(&index);
+        index += 1;
+        keep
+    });
+}
+
+fn is_identifier(prefix: &Prefix, name: &str) -> bool {
+    matches!(prefix, Prefix::Identifier(identifier) if identifier.get_name() == name)
+}
+
+// matches `function name.field()`
+fn match_field_function<'b>(statement: &'b FunctionStatement, name: &str) -> Option<&'b str> {
+    let function_name = statement.get_name();
+
+    if function_name.get_name().get_name() == name
+        && !function_name.has_method()
+        && function_name.get_field_names().len() == 1
+    {
+        Some(function_name.get_field_names()[0].get_name())
+    } else {
+        None
+    }
+}
+
+// matches `name.field = value` when the value has no side effects
+fn match_field_assignment(statement: &AssignStatement, name: &str) -> Option<String> {
+    if statement.variables_len() != 1 || statement.values_len() != 1 {
+        return None;
+    }
+
+    match (
+        statement.iter_variables().next(),
+        statement.iter_values().next(),
+    ) {
+        (Some(Variable::Field(field)), Some(value))
+            if is_identifier(field.get_prefix(), name) && is_pure(value) =>
+        {
+            Some(field.get_field().get_name().to_owned())
+        }
+        _ => None,
+    }
+}
+
+// returns true for expressions that can be removed without changing the behavior of a program
+fn is_pure(expression: &Expression) -> bool {
+    match expression {
+        Expression::False(_)
+        | Expression::True(_)
+        | Expression::Nil(_)
+        | Expression::Number(_)
+        | Expression::String(_)
+        | Expression::Function(_)
+        | Expression::Identifier(_) => true,
+        Expression::Parenthese(parenthese) => is_pure(parenthese.inner_expression()),
+        Expression::TypeCast(type_cast) => is_pure(type_cast.get_expression()),
+        Expression::Table(table) => table.iter_entries().all(|entry| match entry {
+            TableEntry::Field(entry) => is_pure(entry.get_value()),
+            TableEntry::Index(entry) => is_pure(entry.get_key()) && is_pure(entry.get_value()),
+            TableEntry::Value(value) => is_pure(value),
+        }),
+        _ => false,
+    }
+}
@@ -2,15 +2,24 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::rules::{require::PathRequireMode, RuleProcessResult};
+use crate::frontend::DarkluaResult;
+use crate::rules::{
+    require::{ModuleRequireMode, PathRequireMode},
+    RobloxRequireMode, RuleProcessResult,
+};
 use crate::{nodes::Block, rules::Context};
 
-use super::{path_require_mode, BundleOptions};
+use super::path_require_mode::SharedModules;
+use super::{
+    module_require_mode, path_require_mode, roblox_require_mode, BundleOptions, SharedChunk,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "name")]
 pub enum BundleRequireMode {
     Path(PathRequireMode),
+    Roblox(RobloxRequireMode),
+    Module(ModuleRequireMode),
 }
 
 impl From<PathRequireMode> for BundleRequireMode {
@@ -19,12 +28,26 @@ impl From<PathRequireMode> for BundleRequireMode {
     }
 }
 
+impl From<RobloxRequireMode> for BundleRequireMode {
+    fn from(mode: RobloxRequireMode) -> Self {
+        Self::Roblox(mode)
+    }
+}
+
+impl From<ModuleRequireMode> for BundleRequireMode {
+    fn from(mode: ModuleRequireMode) -> Self {
+        Self::Module(mode)
+    }
+}
+
 impl FromStr for BundleRequireMode {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "path" => Self::Path(Default::default()),
+            "roblox" => Self::Roblox(Default::default()),
+            "module" => Self::Module(Default::default()),
             _ => return Err(format!("invalid require mode `{}`", s)),
         })
     }
@@ -51,6 +74,47 @@ impl BundleRequireMode {
                     .map_err(|err| err.to_string())?;
                 path_require_mode::process_block(block, context, options, &require_mode)
             }
+            Self::Roblox(roblox_require_mode) => {
+                let mut require_mode = roblox_require_mode.clone();
+                require_mode
+                    .initialize(context)
+                    .map_err(|err| err.to_string())?;
+                roblox_require_mode::process_block(block, context, options, &require_mode)
+            }
+            Self::Module(module_require_mode) => {
+                module_require_mode::process_block(block, context, options, module_require_mode)
+            }
+        }
+    }
+
+    pub(crate) fn build_shared_chunk(
+        &self,
+        context: &Context,
+        options: &BundleOptions,
+        shared_chunk: &SharedChunk,
+    ) -> DarkluaResult<(Block, SharedModules)> {
+        match self {
+            Self::Path(path_require_mode) => {
+                let mut require_mode = path_require_mode.clone();
+                require_mode.initialize(context)?;
+                path_require_mode::build_shared_chunk(context, options, shared_chunk, &require_mode)
+            }
+            Self::Roblox(roblox_require_mode) => {
+                let mut require_mode = roblox_require_mode.clone();
+                require_mode.initialize(context)?;
+                roblox_require_mode::build_shared_chunk(
+                    context,
+                    options,
+                    shared_chunk,
+                    &require_mode,
+                )
+            }
+            Self::Module(module_require_mode) => module_require_mode::build_shared_chunk(
+                context,
+                options,
+                shared_chunk,
+                module_require_mode,
+            ),
         }
     }
 }
@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::nodes::{Expression, StringExpression, TableEntry, TableExpression};
+use crate::DarkluaError;
+
+/// Defines how the content of a required file is converted into Lua.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceLoader {
+    /// Parses the file as a Lua module
+    Lua,
+    /// Converts JSON (or JSON5) data to a Lua value
+    Json,
+    /// Converts YAML data to a Lua value
+    Yaml,
+    /// Converts Toml data to a Lua value
+    Toml,
+    /// Converts the file content to a Lua string
+    Text,
+    /// Converts comma-separated values to an array of row tables
+    Csv,
+    /// Converts tab-separated values to an array of row tables
+    Tsv,
+}
+
+const DEFAULT_LOADERS: [(&str, ResourceLoader); 10] = [
+    ("lua", ResourceLoader::Lua),
+    ("luau", ResourceLoader::Lua),
+    ("json", ResourceLoader::Json),
+    ("json5", ResourceLoader::Json),
+    ("yml", ResourceLoader::Yaml),
+    ("yaml", ResourceLoader::Yaml),
+    ("toml", ResourceLoader::Toml),
+    ("txt", ResourceLoader::Text),
+    ("csv", ResourceLoader::Csv),
+    ("tsv", ResourceLoader::Tsv),
+];
+
+/// Associates file extensions to resource loaders. The configured loaders take
+/// precedence over the default ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ResourceLoaders {
+    loaders: BTreeMap<String, ResourceLoader>,
+}
+
+impl ResourceLoaders {
+    pub(crate) fn new(loaders: &BTreeMap<String, ResourceLoader>) -> Self {
+        Self {
+            loaders: loaders
+                .iter()
+                .map(|(extension, loader)| {
+                    (extension.trim_start_matches('.').to_lowercase(), *loader)
+                })
+                .collect(),
+        }
+    }
+
+    /// Finds the loader of a file using its longest matching extension, so that
+    /// compound extensions (like `.luau.json`) can be associated to a different
+    /// loader than their last extension.
+    pub(crate) fn find(&self, path: &Path) -> Option<ResourceLoader> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let file_name = file_name.trim_start_matches('.');
+
+        file_name
+            .match_indices('.')
+            .map(|(index, _)| &file_name[index + 1..])
+            .find_map(|extension| self.get(extension))
+    }
+
+    fn get(&self, extension: &str) -> Option<ResourceLoader> {
+        self.loaders.get(extension).copied().or_else(|| {
+            DEFAULT_LOADERS
+                .iter()
+                .find(|(default_extension, _)| *default_extension == extension)
+                .map(|(_, loader)| *loader)
+        })
+    }
+}
+
+/// Converts delimiter-separated values into an array of tables, where each row is
+/// a table indexed by the names of the header row.
+pub(crate) fn load_separated_values(
+    content: &str,
+    delimiter: char,
+    data_type: &'static str,
+) -> Result<Expression, DarkluaError> {
+    let mut rows = read_separated_values(content, delimiter)
+        .map_err(|message| DarkluaError::deserialization(message, data_type))?
+        .into_iter();
+
+    let header = match rows.next() {
+        Some(header) => header,
+        None => return Ok(TableExpression::default().into()),
+    };
+
+    let mut table = TableExpression::default();
+
+    for Row { line, fields: row } in rows {
+        if row.len() != header.fields.len() {
+            return Err(DarkluaError::deserialization(
+                format!(
+                    "row at line {} has {} field(s) but the header has {}",
+                    line,
+                    row.len(),
+                    header.fields.len()
+                ),
+                data_type,
+            ));
+        }
+
+        let row_table = header.fields.iter().zip(row).fold(
+            TableExpression::default(),
+            |row_table, (name, value)| {
+                row_table.append_entry(TableEntry::from_string_key_and_value(
+                    name.clone(),
+                    StringExpression::from_value(value),
+                ))
+            },
+        );
+
+        table = table.append_array_value(row_table);
+    }
+
+    Ok(table.into())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Row {
+    // the line where the row starts (quoted fields can span multiple lines)
+    line: usize,
+    fields: Vec<String>,
+}
+
+fn read_separated_values(content: &str, delimiter: char) -> Result<Vec<Row>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if in_quotes {
+            match char {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(char);
+                }
+                _ => field.push(char),
+            }
+        } else {
+            match char {
+                '"' if field.is_empty() => in_quotes = true,
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(Row {
+                        line: row_line,
+                        fields: std::mem::take(&mut row),
+                    });
+                    line += 1;
+                    row_line = line;
+                }
+                _ if char == delimiter => row.push(std::mem::take(&mut field)),
+                _ => field.push(char),
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("unclosed quoted field at line {}", line));
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(Row {
+            line: row_line,
+            fields: row,
+        });
+    }
+
+    // ignore empty lines
+    rows.retain(|row| !(row.fields.len() == 1 && row.fields[0].is_empty()));
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn loaders(entries: &[(&str, ResourceLoader)]) -> ResourceLoaders {
+        ResourceLoaders::new(
+            &entries
+                .iter()
+                .map(|(extension, loader)| (extension.to_string(), *loader))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn find_default_loader() {
+        let loaders = ResourceLoaders::default();
+
+        assert_eq!(
+            loaders.find(Path::new("src/data.json5")),
+            Some(ResourceLoader::Json)
+        );
+        assert_eq!(
+            loaders.find(Path::new("src/module.luau")),
+            Some(ResourceLoader::Lua)
+        );
+        assert_eq!(
+            loaders.find(Path::new("src/strings.csv")),
+            Some(ResourceLoader::Csv)
+        );
+    }
+
+    #[test]
+    fn find_loader_of_unknown_extension() {
+        let loaders = ResourceLoaders::default();
+
+        assert_eq!(loaders.find(Path::new("src/image.png")), None);
+        assert_eq!(loaders.find(Path::new("src/file")), None);
+    }
+
+    #[test]
+    fn find_loader_with_compound_extension() {
+        let loaders = loaders(&[("luau.json", ResourceLoader::Text)]);
+
+        assert_eq!(
+            loaders.find(Path::new("src/data.luau.json")),
+            Some(ResourceLoader::Text)
+        );
+        assert_eq!(
+            loaders.find(Path::new("src/data.json")),
+            Some(ResourceLoader::Json)
+        );
+    }
+
+    #[test]
+    fn find_default_loader_of_last_extension() {
+        let loaders = ResourceLoaders::default();
+
+        assert_eq!(
+            loaders.find(Path::new("src/data.server.json")),
+            Some(ResourceLoader::Json)
+        );
+    }
+
+    #[test]
+    fn configured_loader_overrides_default_loader() {
+        let loaders = loaders(&[(".txt", ResourceLoader::Tsv)]);
+
+        assert_eq!(
+            loaders.find(Path::new("src/data.TXT")),
+            Some(ResourceLoader::Tsv)
+        );
+    }
+
+    mod read_separated_values {
+        use super::*;
+
+        fn read_csv(content: &str) -> Vec<Vec<String>> {
+            read_fields(content, ',')
+        }
+
+        fn read_fields(content: &str, delimiter: char) -> Vec<Vec<String>> {
+            read_separated_values(content, delimiter)
+                .unwrap()
+                .into_iter()
+                .map(|row| row.fields)
+                .collect()
+        }
+
+        #[test]
+        fn empty_content() {
+            assert_eq!(read_csv(""), Vec::<Vec<String>>::new());
+        }
+
+        #[test]
+        fn simple_rows() {
+            assert_eq!(read_csv("a,b\n1,2\n"), vec![vec!["a", "b"], vec!["1", "2"]]);
+        }
+
+        #[test]
+        fn rows_with_crlf_line_endings() {
+            assert_eq!(
+                read_csv("a,b\r\n1,2\r\n"),
+                vec![vec!["a", "b"], vec!["1", "2"]]
+            );
+        }
+
+        #[test]
+        fn quoted_field_with_delimiter_and_new_line() {
+            assert_eq!(
+                read_csv("key,text\nhello,\"Hello, world!\nBye\""),
+                vec![vec!["key", "text"], vec!["hello", "Hello, world!\nBye"]]
+            );
+        }
+
+        #[test]
+        fn quoted_field_with_escaped_quote() {
+            assert_eq!(read_csv("\"say \"\"hi\"\"\""), vec![vec!["say \"hi\""]]);
+        }
+
+        #[test]
+        fn empty_fields() {
+            assert_eq!(read_csv("a,,c\n"), vec![vec!["a", "", "c"]]);
+        }
+
+        #[test]
+        fn skip_empty_lines() {
+            assert_eq!(read_csv("a\n\nb\n"), vec![vec!["a"], vec!["b"]]);
+        }
+
+        #[test]
+        fn tab_separated_values() {
+            assert_eq!(read_fields("a\tb,c\n", '\t'), vec![vec!["a", "b,c"]]);
+        }
+
+        #[test]
+        fn rows_start_line() {
+            let lines: Vec<_> = read_separated_values("a,b\n\n\"1\n2\",3\n4,5", ',')
+                .unwrap()
+                .into_iter()
+                .map(|row| row.line)
+                .collect();
+
+            assert_eq!(lines, vec![1, 3, 5]);
+        }
+
+        #[test]
+        fn unclosed_quoted_field_is_an_error() {
+            assert_eq!(
+                read_separated_values("a\n\"b", ','),
+                Err("unclosed quoted field at line 2".to_owned())
+            );
+        }
+    }
+}
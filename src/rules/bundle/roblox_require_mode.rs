@@ -0,0 +1,108 @@
+use std::path::Path;
+
+use crate::frontend::DarkluaResult;
+use crate::nodes::{Block, FunctionCall};
+use crate::rules::convert_require::{match_roblox_require_call, RobloxRequireMode, RojoSourcemap};
+use crate::rules::Context;
+use crate::utils;
+use crate::DarkluaError;
+
+use super::path_require_mode::{
+    build_shared_chunk_with_locator, process_block_with_locator, BundledRequire, FindRequirePath,
+    SharedModules,
+};
+use super::{BundleOptions, SharedChunk};
+
+#[derive(Debug)]
+struct RobloxRequireLocator<'a> {
+    sourcemap: &'a RojoSourcemap,
+}
+
+impl FindRequirePath for RobloxRequireLocator<'_> {
+    fn find_bundled_require(
+        &self,
+        call: &FunctionCall,
+        source: &Path,
+        options: &BundleOptions,
+    ) -> DarkluaResult<BundledRequire> {
+        let instance_path = match match_roblox_require_call(call) {
+            Some(instance_path) => instance_path,
+            None => return Ok(BundledRequire::Skip),
+        };
+
+        let require_path = match self
+            .sourcemap
+            .get_file_path(utils::normalize_path(source), &instance_path)
+        {
+            Some(require_path) => utils::normalize_path(require_path),
+            None if instance_path.is_from_root() => {
+                // instances found from the DataModel may come from outside the project
+                log::info!(
+                    "skip `{}` from bundle because it is not in the Rojo sourcemap [from `{}`]",
+                    instance_path,
+                    source.display()
+                );
+                return Ok(BundledRequire::Skip);
+            }
+            None => {
+                return Err(DarkluaError::custom(format!(
+                    "unable to find `{}` in Rojo sourcemap (from `{}`)",
+                    instance_path,
+                    source.display()
+                )))
+            }
+        };
+
+        if options.is_excluded(&require_path) {
+            log::info!(
+                "exclude `{}` ({}) from bundle [from `{}`]",
+                require_path.display(),
+                instance_path,
+                source.display()
+            );
+            return Ok(BundledRequire::Excluded(instance_path.to_string()));
+        }
+
+        log::debug!(
+            "found require call to `{}` (file `{}`)",
+            instance_path,
+            require_path.display()
+        );
+
+        Ok(BundledRequire::Inline(require_path))
+    }
+}
+
+pub(crate) fn process_block(
+    block: &mut Block,
+    context: &Context,
+    options: &BundleOptions,
+    roblox_require_mode: &RobloxRequireMode,
+) -> Result<(), String> {
+    let sourcemap = roblox_require_mode.rojo_sourcemap().ok_or_else(|| {
+        "Roblox require mode needs a Rojo sourcemap to bundle code (use the `rojo_sourcemap` field)"
+            .to_owned()
+    })?;
+
+    process_block_with_locator(block, context, options, RobloxRequireLocator { sourcemap })
+}
+
+pub(crate) fn build_shared_chunk(
+    context: &Context,
+    options: &BundleOptions,
+    shared_chunk: &SharedChunk,
+    roblox_require_mode: &RobloxRequireMode,
+) -> DarkluaResult<(Block, SharedModules)> {
+    let sourcemap = roblox_require_mode.rojo_sourcemap().ok_or_else(|| {
+        DarkluaError::custom(
+            "Roblox require mode needs a Rojo sourcemap to bundle code (use the `rojo_sourcemap` field)",
+        )
+    })?;
+
+    build_shared_chunk_with_locator(
+        context,
+        options,
+        shared_chunk,
+        RobloxRequireLocator { sourcemap },
+    )
+}
@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::normalize_path;
+
+/// Defines a set of entry points that share their common modules through a separate
+/// file (the shared chunk) instead of inlining them in each bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct SharedChunk {
+    entries: Vec<PathBuf>,
+    output: PathBuf,
+    require: String,
+}
+
+impl SharedChunk {
+    /// Creates a shared chunk written at `output`. The `require` parameter is the Lua
+    /// expression used by each entry to load the shared chunk (like `require("./shared")`).
+    pub fn new(output: impl Into<PathBuf>, require: impl Into<String>) -> Self {
+        Self {
+            entries: Vec::new(),
+            output: output.into(),
+            require: require.into(),
+        }
+    }
+
+    pub fn with_entry(mut self, entry: impl Into<PathBuf>) -> Self {
+        self.entries.push(entry.into());
+        self
+    }
+
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &Path> {
+        self.entries.iter().map(AsRef::as_ref)
+    }
+
+    pub(crate) fn output(&self) -> &Path {
+        &self.output
+    }
+
+    pub(crate) fn require(&self) -> &str {
+        &self.require
+    }
+
+    pub(crate) fn is_entry(&self, path: &Path) -> bool {
+        let path = normalize_path(path);
+        self.entries.contains(&path)
+    }
+
+    /// Makes the entries and the output path relative to the given location.
+    pub(crate) fn resolve(&self, location: &Path) -> Self {
+        Self {
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| normalize_path(location.join(entry)))
+                .collect(),
+            output: normalize_path(location.join(&self.output)),
+            require: self.require.clone(),
+        }
+    }
+}
@@ -7,12 +7,12 @@ use crate::rules::{
 use super::verify_no_rule_properties;
 
 #[derive(Debug, Clone, Default)]
-struct Computer {
+pub(crate) struct Computer {
     evaluator: Evaluator,
 }
 
 impl Computer {
-    fn replace_with(&mut self, expression: &Expression) -> Option<Expression> {
+    pub(crate) fn replace_with(&mut self, expression: &Expression) -> Option<Expression> {
         match expression {
             Expression::Unary(_) => {
                 if !self.evaluator.has_side_effects(expression) {
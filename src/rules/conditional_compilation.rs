@@ -0,0 +1,788 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::env;
+
+use crate::nodes::{
+    Block, Expression, FunctionBodyTokens, FunctionExpression, LastStatement, Position, Statement,
+    StringExpression, Token, Trivia,
+};
+use crate::process::utils::is_valid_identifier;
+use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult, RuleProperties,
+    RulePropertyValue,
+};
+use crate::utils::lines;
+use crate::Parser;
+
+use super::remove_comments::FilterCommentProcessor;
+
+const DIRECTIVE_PREFIX: &str = "--#";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Directive {
+    If(String),
+    ElseIf(String),
+    Else,
+    EndIf,
+}
+
+impl Directive {
+    fn keyword(&self) -> &'static str {
+        match self {
+            Self::If(_) => "if",
+            Self::ElseIf(_) => "elseif",
+            Self::Else => "else",
+            Self::EndIf => "endif",
+        }
+    }
+
+    /// Parses a comment into a directive. Comments that start with `--#` followed by an
+    /// unknown keyword (like `--#region`) are not directives.
+    fn parse(comment: &str) -> Option<Result<Self, String>> {
+        let content = comment.strip_prefix(DIRECTIVE_PREFIX)?.trim_end();
+
+        let (keyword, rest) = match content.find(char::is_whitespace) {
+            Some(index) => (&content[..index], content[index..].trim()),
+            None => (content, ""),
+        };
+
+        let directive = match keyword {
+            "if" => Self::If(rest.to_owned()),
+            "elseif" => Self::ElseIf(rest.to_owned()),
+            "else" => Self::Else,
+            "endif" => Self::EndIf,
+            _ => return None,
+        };
+
+        Some(match &directive {
+            Self::If(condition) | Self::ElseIf(condition) if condition.is_empty() => Err(format!(
+                "missing condition after `{}{}`",
+                DIRECTIVE_PREFIX,
+                directive.keyword()
+            )),
+            Self::Else | Self::EndIf if !rest.is_empty() => Err(format!(
+                "unexpected content after `{}{}`: `{}`",
+                DIRECTIVE_PREFIX,
+                directive.keyword(),
+                rest
+            )),
+            _ => Ok(directive),
+        })
+    }
+}
+
+struct ReplaceDefines<'a> {
+    values: &'a BTreeMap<String, String>,
+}
+
+impl NodeProcessor for ReplaceDefines<'_> {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Identifier(identifier) = expression {
+            *expression = self
+                .values
+                .get(identifier.get_name())
+                .map(|value| parse_define_value(value))
+                .unwrap_or_else(Expression::nil);
+        }
+    }
+}
+
+fn parse_define_value(value: &str) -> Expression {
+    match value {
+        "true" => true.into(),
+        "false" => false.into(),
+        _ => StringExpression::from_value(value).into(),
+    }
+}
+
+#[derive(Debug)]
+struct Frame {
+    parent_active: bool,
+    branch_taken: bool,
+    active: bool,
+    has_else: bool,
+    line: usize,
+}
+
+/// The line and column where a token or a comment starts. Locations are read from lines
+/// instead of byte offsets, so that blocks where the token references were replaced (like
+/// the modules inlined by the bundler) can be processed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Location {
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    fn from_position(position: &Position, code: &str) -> Option<Self> {
+        match position {
+            Position::LineNumberReference { line_number, .. }
+            | Position::LineNumber { line_number, .. } => Some(Self {
+                line: *line_number,
+                column: position.get_column(code)?,
+            }),
+            Position::Any { .. } => None,
+        }
+    }
+
+    fn from_token(token: &Token, code: &str) -> Option<Self> {
+        Self::from_position(token.get_position(), code)
+    }
+}
+
+/// Computes the ranges (from a directive to the next one) of the code that is inactive.
+struct InactiveRanges<'a> {
+    values: &'a BTreeMap<String, String>,
+    stack: Vec<Frame>,
+    inactive_start: Option<Location>,
+    ranges: Vec<(Location, Location)>,
+}
+
+impl<'a> InactiveRanges<'a> {
+    fn new(values: &'a BTreeMap<String, String>) -> Self {
+        Self {
+            values,
+            stack: Vec::new(),
+            inactive_start: None,
+            ranges: Vec::new(),
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.stack.last().map(|frame| frame.active).unwrap_or(true)
+    }
+
+    fn evaluate(&self, condition: &str) -> Result<bool, String> {
+        let mut block = Parser::default()
+            .parse(&format!("return {}", condition))
+            .map_err(|err| format!("unable to parse condition `{}`: {}", condition, err))?;
+
+        DefaultVisitor::visit_block(
+            &mut block,
+            &mut ReplaceDefines {
+                values: self.values,
+            },
+        );
+
+        match block.get_last_statement() {
+            Some(LastStatement::Return(statement))
+                if statement.len() == 1 && block.statements_len() == 0 =>
+            {
+                statement
+                    .iter_expressions()
+                    .next()
+                    .and_then(|expression| Evaluator::default().evaluate(expression).is_truthy())
+                    .ok_or_else(|| format!("unable to evaluate condition `{}`", condition))
+            }
+            _ => Err(format!("invalid condition `{}`", condition)),
+        }
+    }
+
+    fn push_directive(&mut self, location: Location, directive: Directive) -> Result<(), String> {
+        let line = location.line;
+        let was_active = self.is_active();
+
+        match directive {
+            Directive::If(condition) => {
+                let evaluated = self.evaluate(&condition)?;
+                let active = was_active && evaluated;
+                self.stack.push(Frame {
+                    parent_active: was_active,
+                    branch_taken: active,
+                    active,
+                    has_else: false,
+                    line,
+                });
+            }
+            Directive::ElseIf(condition) => {
+                let evaluated = self.evaluate(&condition)?;
+                let frame = self.current_frame(line, "elseif")?;
+                if frame.has_else {
+                    return Err(format!(
+                        "unexpected `{}elseif` after `{}else` at line {}",
+                        DIRECTIVE_PREFIX, DIRECTIVE_PREFIX, line
+                    ));
+                }
+                frame.active = frame.parent_active && !frame.branch_taken && evaluated;
+                frame.branch_taken = frame.branch_taken || frame.active;
+            }
+            Directive::Else => {
+                let frame = self.current_frame(line, "else")?;
+                if frame.has_else {
+                    return Err(format!(
+                        "unexpected second `{}else` at line {}",
+                        DIRECTIVE_PREFIX, line
+                    ));
+                }
+                frame.has_else = true;
+                frame.active = frame.parent_active && !frame.branch_taken;
+                frame.branch_taken = true;
+            }
+            Directive::EndIf => {
+                self.current_frame(line, "endif")?;
+                self.stack.pop();
+            }
+        }
+
+        match (was_active, self.is_active()) {
+            (true, false) => {
+                self.inactive_start = Some(location);
+            }
+            (false, true) => {
+                if let Some(start) = self.inactive_start.take() {
+                    self.ranges.push((start, location));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn current_frame(&mut self, line: usize, keyword: &str) -> Result<&mut Frame, String> {
+        self.stack.last_mut().ok_or_else(|| {
+            format!(
+                "unexpected `{}{}` without a matching `{}if` at line {}",
+                DIRECTIVE_PREFIX, keyword, DIRECTIVE_PREFIX, line
+            )
+        })
+    }
+
+    fn finish(self) -> Result<Vec<(Location, Location)>, String> {
+        if let Some(frame) = self.stack.first() {
+            Err(format!(
+                "missing `{}endif` for the `{}if` directive at line {}",
+                DIRECTIVE_PREFIX, DIRECTIVE_PREFIX, frame.line
+            ))
+        } else {
+            Ok(self.ranges)
+        }
+    }
+}
+
+struct RemoveInactiveStatements<'a> {
+    code: &'a str,
+    ranges: Vec<(Location, Location)>,
+}
+
+impl RemoveInactiveStatements<'_> {
+    fn is_inactive(&self, token: Option<&Token>) -> bool {
+        token
+            .and_then(|token| Location::from_token(token, self.code))
+            .map(|location| {
+                self.ranges
+                    .iter()
+                    .any(|(start, end)| *start < location && location < *end)
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl NodeProcessor for RemoveInactiveStatements<'_> {
+    fn process_block(&mut self, block: &mut Block) {
+        block.filter_statements(|statement| {
+            !self.is_inactive(lines::first_statement_token(statement))
+        });
+
+        if self.is_inactive(
+            block
+                .get_last_statement()
+                .and_then(lines::first_last_statement_token),
+        ) {
+            block.take_last_statement();
+        }
+    }
+}
+
+/// The range of a statement (from its first token to its last token), with the ranges of
+/// the blocks it contains (from the token that opens a block to the token that closes it).
+struct StatementRange {
+    start: Location,
+    end: Location,
+    blocks: Vec<(Location, Location)>,
+}
+
+impl StatementRange {
+    fn contains(&self, location: Location) -> bool {
+        self.start < location && location < self.end
+    }
+}
+
+fn between(opening: &Token, closing: &Token, code: &str) -> Option<(Location, Location)> {
+    Some((
+        Location::from_token(opening, code)?,
+        Location::from_token(closing, code)?,
+    ))
+}
+
+fn function_body_range(tokens: &FunctionBodyTokens, code: &str) -> Option<(Location, Location)> {
+    between(&tokens.closing_parenthese, &tokens.end, code)
+}
+
+fn statement_blocks(statement: &Statement, code: &str) -> Vec<(Location, Location)> {
+    match statement {
+        Statement::Do(do_statement) => do_statement
+            .get_tokens()
+            .and_then(|tokens| between(&tokens.r#do, &tokens.end, code))
+            .into_iter()
+            .collect(),
+        Statement::Function(function) => function
+            .get_tokens()
+            .and_then(|tokens| function_body_range(tokens, code))
+            .into_iter()
+            .collect(),
+        Statement::GenericFor(generic_for) => generic_for
+            .get_tokens()
+            .and_then(|tokens| between(&tokens.r#do, &tokens.end, code))
+            .into_iter()
+            .collect(),
+        Statement::If(if_statement) => {
+            let tokens = match if_statement.get_tokens() {
+                Some(tokens) => tokens,
+                None => return Vec::new(),
+            };
+            let branch_tokens: Vec<_> = if_statement
+                .iter_branches()
+                .skip(1)
+                .filter_map(|branch| branch.get_tokens())
+                .collect();
+
+            let openings = std::iter::once(&tokens.then)
+                .chain(branch_tokens.iter().map(|branch| &branch.then))
+                .chain(tokens.r#else.as_ref());
+            let closings = branch_tokens
+                .iter()
+                .map(|branch| &branch.elseif)
+                .chain(tokens.r#else.as_ref())
+                .chain(std::iter::once(&tokens.end));
+
+            openings
+                .zip(closings)
+                .filter_map(|(opening, closing)| between(opening, closing, code))
+                .collect()
+        }
+        Statement::LocalFunction(local_function) => local_function
+            .get_tokens()
+            .and_then(|tokens| function_body_range(tokens, code))
+            .into_iter()
+            .collect(),
+        Statement::NumericFor(numeric_for) => numeric_for
+            .get_tokens()
+            .and_then(|tokens| between(&tokens.r#do, &tokens.end, code))
+            .into_iter()
+            .collect(),
+        Statement::Repeat(repeat) => repeat
+            .get_tokens()
+            .and_then(|tokens| between(&tokens.repeat, &tokens.until, code))
+            .into_iter()
+            .collect(),
+        Statement::While(while_statement) => while_statement
+            .get_tokens()
+            .and_then(|tokens| between(&tokens.r#do, &tokens.end, code))
+            .into_iter()
+            .collect(),
+        Statement::TypeFunction(type_function) => type_function
+            .get_tokens()
+            .and_then(|tokens| function_body_range(&tokens.function_body, code))
+            .into_iter()
+            .collect(),
+        Statement::ExportTypeFunction(export_type_function) => export_type_function
+            .get_tokens()
+            .and_then(|tokens| function_body_range(&tokens.function_body, code))
+            .into_iter()
+            .collect(),
+        Statement::Assign(_)
+        | Statement::Call(_)
+        | Statement::CompoundAssign(_)
+        | Statement::Goto(_)
+        | Statement::Label(_)
+        | Statement::LocalAssign(_)
+        | Statement::TypeDeclaration(_) => Vec::new(),
+    }
+}
+
+/// Collects the ranges of the statements and of the function expressions, to find the
+/// directives that are placed inside a statement instead of between statements.
+struct StatementRanges<'a> {
+    code: &'a str,
+    statements: Vec<StatementRange>,
+    functions: Vec<(Location, Location)>,
+}
+
+impl<'a> StatementRanges<'a> {
+    fn new(code: &'a str) -> Self {
+        Self {
+            code,
+            statements: Vec::new(),
+            functions: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        first: Option<&Token>,
+        last: Option<&Token>,
+        blocks: Vec<(Location, Location)>,
+    ) {
+        let location =
+            |token: Option<&Token>| token.and_then(|token| Location::from_token(token, self.code));
+
+        if let (Some(start), Some(end)) = (location(first), location(last)) {
+            self.statements.push(StatementRange { start, end, blocks });
+        }
+    }
+
+    fn is_between_statements(&self, location: Location) -> bool {
+        let innermost = self
+            .statements
+            .iter()
+            .filter(|statement| statement.contains(location))
+            .max_by_key(|statement| statement.start);
+
+        match innermost {
+            Some(statement) => statement
+                .blocks
+                .iter()
+                .chain(
+                    self.functions
+                        .iter()
+                        .filter(|(start, _)| statement.start < *start),
+                )
+                .any(|(start, end)| *start < location && location < *end),
+            None => true,
+        }
+    }
+}
+
+impl NodeProcessor for StatementRanges<'_> {
+    fn process_statement(&mut self, statement: &mut Statement) {
+        self.push(
+            lines::first_statement_token(statement),
+            lines::last_statement_token(statement),
+            statement_blocks(statement, self.code),
+        );
+    }
+
+    fn process_last_statement(&mut self, statement: &mut LastStatement) {
+        self.push(
+            lines::first_last_statement_token(statement),
+            lines::last_last_statement_token(statement),
+            Vec::new(),
+        );
+    }
+
+    fn process_function_expression(&mut self, function: &mut FunctionExpression) {
+        if let Some(range) = function
+            .get_tokens()
+            .and_then(|tokens| function_body_range(tokens, self.code))
+        {
+            self.functions.push(range);
+        }
+    }
+}
+
+pub const CONDITIONAL_COMPILATION_RULE_NAME: &str = "conditional_compilation";
+
+/// A rule that removes statements based on comment directives (`--#if`, `--#elseif`, `--#else`
+/// and `--#endif`).
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConditionalCompilation {
+    defines: BTreeMap<String, String>,
+    env: Vec<String>,
+    // the defined values, overridden by the values read from the environment
+    values: BTreeMap<String, String>,
+}
+
+impl ConditionalCompilation {
+    /// Defines a variable that will be `true` when evaluating the directive conditions.
+    pub fn with_define(self, name: impl Into<String>) -> Self {
+        self.with_value(name, "true")
+    }
+
+    /// Defines a variable that can be compared in the directive conditions. The values `true`
+    /// and `false` are converted to booleans.
+    pub fn with_value(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        let value = value.into();
+        self.defines.insert(name.clone(), value.clone());
+        self.values.insert(name, value);
+        self
+    }
+
+    fn insert_define(&mut self, key: &str, define: &str) -> Result<(), RuleConfigurationError> {
+        let (name, value) = define.split_once('=').unwrap_or((define, "true"));
+        let name = name.trim();
+
+        if !is_valid_identifier(name) {
+            return Err(RuleConfigurationError::UnexpectedValue {
+                property: key.to_owned(),
+                message: format!("invalid variable name `{}`", name),
+            });
+        }
+
+        self.defines
+            .insert(name.to_owned(), value.trim().to_owned());
+        Ok(())
+    }
+
+    fn read_env(&mut self) -> Result<(), RuleConfigurationError> {
+        self.values = self.defines.clone();
+
+        for variable_name in self.env.iter() {
+            if let Some(os_value) = env::var_os(variable_name) {
+                if let Some(value) = os_value.to_str() {
+                    self.values
+                        .insert(variable_name.to_owned(), value.to_owned());
+                } else {
+                    return Err(RuleConfigurationError::UnexpectedValue {
+                        property: "env".to_owned(),
+                        message: format!(
+                            "invalid string assigned to the `{}` environment variable",
+                            variable_name,
+                        ),
+                    });
+                }
+            } else {
+                log::warn!(
+                    "environment variable `{}` is not defined. The rule `{}` will use `nil`",
+                    variable_name,
+                    CONDITIONAL_COMPILATION_RULE_NAME,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Rule for ConditionalCompilation {
+    fn process(&self, block: &mut Block, context: &Context) -> RuleProcessResult {
+        let original_code = context.original_code();
+        let directives = RefCell::new(Vec::new());
+
+        let mut processor = FilterCommentProcessor::new(|trivia: &Trivia| {
+            let content = trivia.read(original_code);
+
+            if let Some(directive) = Directive::parse(content) {
+                let location = Location::from_position(trivia.get_position(), original_code);
+                directives.borrow_mut().push((location, directive));
+                false
+            } else {
+                true
+            }
+        });
+        DefaultVisitor::visit_block(block, &mut processor);
+
+        let mut directives = directives
+            .into_inner()
+            .into_iter()
+            .map(|(location, directive)| {
+                let location = location.ok_or_else(|| {
+                    "unable to locate directive comment (the parser must preserve tokens)"
+                        .to_owned()
+                })?;
+                directive
+                    .map(|directive| (location, directive))
+                    .map_err(|err| format!("{} at line {}", err, location.line))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        if directives.is_empty() {
+            return Ok(());
+        }
+
+        directives.sort_by_key(|(location, _)| *location);
+
+        let mut statement_ranges = StatementRanges::new(original_code);
+        DefaultVisitor::visit_block(block, &mut statement_ranges);
+
+        if let Some((location, directive)) = directives
+            .iter()
+            .find(|(location, _)| !statement_ranges.is_between_statements(*location))
+        {
+            return Err(format!(
+                "unexpected `{}{}` inside a statement at line {} (directives must be placed between statements)",
+                DIRECTIVE_PREFIX,
+                directive.keyword(),
+                location.line
+            ));
+        }
+
+        let mut inactive_ranges = InactiveRanges::new(&self.values);
+        for (location, directive) in directives {
+            inactive_ranges.push_directive(location, directive)?;
+        }
+
+        let mut processor = RemoveInactiveStatements {
+            code: original_code,
+            ranges: inactive_ranges.finish()?,
+        };
+        DefaultVisitor::visit_block(block, &mut processor);
+
+        Ok(())
+    }
+
+    fn require_tokens(&self) -> bool {
+        true
+    }
+
+    fn applies_before_bundling(&self) -> bool {
+        // requires placed in inactive branches must not be inlined
+        true
+    }
+}
+
+impl RuleConfiguration for ConditionalCompilation {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "defines" => {
+                    for define in value.expect_string_list(&key)? {
+                        self.insert_define(&key, &define)?;
+                    }
+                }
+                "env" => {
+                    self.env = value.expect_string_list(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        self.read_env()
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONDITIONAL_COMPILATION_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if !self.defines.is_empty() {
+            properties.insert(
+                "defines".to_owned(),
+                RulePropertyValue::StringList(
+                    self.defines
+                        .iter()
+                        .map(|(name, value)| {
+                            if value == "true" {
+                                name.to_owned()
+                            } else {
+                                format!("{}={}", name, value)
+                            }
+                        })
+                        .collect(),
+                ),
+            );
+        }
+
+        if !self.env.is_empty() {
+            properties.insert(
+                "env".to_owned(),
+                RulePropertyValue::StringList(self.env.clone()),
+            );
+        }
+
+        properties
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConditionalCompilation {
+        ConditionalCompilation::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_conditional_compilation", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_values() {
+        let rule: Box<dyn Rule> = Box::new(
+            new_rule()
+                .with_define("DEBUG")
+                .with_value("PLATFORM", "roblox"),
+        );
+
+        assert_json_snapshot!("conditional_compilation_with_values", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_env() {
+        let rule: Box<dyn Rule> = json5::from_str(
+            r#"{
+            rule: 'conditional_compilation',
+            defines: ['DEBUG'],
+            env: ['DARKLUA_CONDITIONAL_COMPILATION_UNDEFINED_VARIABLE'],
+        }"#,
+        )
+        .unwrap();
+
+        assert_json_snapshot!("conditional_compilation_with_env", rule);
+    }
+
+    #[test]
+    fn parse_if_directive() {
+        pretty_assertions::assert_eq!(
+            Directive::parse("--#if DEBUG and not TEST"),
+            Some(Ok(Directive::If("DEBUG and not TEST".to_owned())))
+        );
+    }
+
+    #[test]
+    fn parse_unknown_directive() {
+        pretty_assertions::assert_eq!(Directive::parse("--#region"), None);
+    }
+
+    #[test]
+    fn parse_if_directive_without_condition() {
+        pretty_assertions::assert_eq!(
+            Directive::parse("--#if"),
+            Some(Err("missing condition after `--#if`".to_owned()))
+        );
+    }
+
+    #[test]
+    fn parse_endif_directive_with_content() {
+        pretty_assertions::assert_eq!(
+            Directive::parse("--#endif DEBUG"),
+            Some(Err(
+                "unexpected content after `--#endif`: `DEBUG`".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn configure_with_invalid_define_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'conditional_compilation',
+            defines: ['1DEBUG'],
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'defines': invalid variable name `1DEBUG`"
+        );
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'conditional_compilation',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
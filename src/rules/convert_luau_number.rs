@@ -0,0 +1,106 @@
+use crate::generator::utils::write_number;
+use crate::nodes::{Block, DecimalNumber, HexNumber, NumberExpression};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+// the largest integer that a double can represent without losing precision
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+
+struct Processor<'a> {
+    original_code: &'a str,
+}
+
+impl<'a> Processor<'a> {
+    fn new(original_code: &'a str) -> Self {
+        Self { original_code }
+    }
+}
+
+impl NodeProcessor for Processor<'_> {
+    fn process_number_expression(&mut self, number: &mut NumberExpression) {
+        if let NumberExpression::Binary(binary) = number {
+            let value = binary.get_raw_value();
+            let mut new_number: NumberExpression = if value <= MAX_EXACT_INTEGER {
+                DecimalNumber::new(value as f64).into()
+            } else {
+                HexNumber::new(value, false).into()
+            };
+
+            if let Some(mut token) = number.get_token().cloned() {
+                token.replace_with_content(write_number(&new_number));
+                new_number.set_token(token);
+            }
+
+            *number = new_number;
+        } else if let Some(token) = number.mutate_token() {
+            let content = token.read(self.original_code);
+            if content.contains('_') {
+                let content = content.replace('_', "");
+                token.replace_with_content(content);
+            }
+        }
+    }
+}
+
+pub const CONVERT_LUAU_NUMBER_RULE_NAME: &str = "convert_luau_number";
+
+/// A rule that converts Luau number literals (binary literals and digit
+/// separators) into decimal or hexadecimal numbers.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConvertLuauNumber {}
+
+impl FlawlessRule for ConvertLuauNumber {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = Processor::new(context.original_code());
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ConvertLuauNumber {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONVERT_LUAU_NUMBER_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConvertLuauNumber {
+        ConvertLuauNumber::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_luau_number", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'convert_luau_number',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
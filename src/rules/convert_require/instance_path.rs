@@ -1,7 +1,28 @@
-use crate::nodes::{FieldExpression, FunctionCall, Identifier, Prefix, StringExpression};
+use std::fmt;
+
+use crate::nodes::{
+    Arguments, Expression, FieldExpression, FunctionCall, Identifier, IndexExpression, Prefix,
+    StringExpression,
+};
 
 use super::RobloxIndexStyle;
 
+const PARENT_PROPERTY: &str = "Parent";
+const FIND_FIRST_CHILD_METHOD: &str = "FindFirstChild";
+const WAIT_FOR_CHILD_METHOD: &str = "WaitForChild";
+const GET_SERVICE_METHOD: &str = "GetService";
+
+/// Returns the instance path given to a require call like `require(script.Parent.Module)`.
+pub(crate) fn match_roblox_require_call(call: &FunctionCall) -> Option<InstancePath> {
+    match call.get_arguments() {
+        Arguments::Tuple(tuple) if tuple.len() == 1 => tuple
+            .iter_values()
+            .next()
+            .and_then(InstancePath::from_expression),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct InstancePath {
     root: InstancePathRoot,
@@ -32,6 +53,120 @@ impl InstancePath {
             .push(InstancePathComponent::Child(child_name.into()));
     }
 
+    /// Reads an instance path from an expression like `script.Parent.Module`,
+    /// `script.Parent:FindFirstChild("Module")` or `game:GetService("ReplicatedStorage").Module`.
+    /// Returns `None` if the expression is not an instance path.
+    pub(crate) fn from_expression(expression: &Expression) -> Option<Self> {
+        match expression {
+            Expression::Call(call) => Self::from_call(call),
+            Expression::Identifier(identifier) => Self::from_identifier(identifier),
+            Expression::Field(field) => Self::from_field(field),
+            Expression::Index(index) => Self::from_index(index),
+            Expression::Parenthese(parenthese) => {
+                Self::from_expression(parenthese.inner_expression())
+            }
+            _ => None,
+        }
+    }
+
+    fn from_prefix(prefix: &Prefix) -> Option<Self> {
+        match prefix {
+            Prefix::Identifier(identifier) => Self::from_identifier(identifier),
+            Prefix::Field(field) => Self::from_field(field),
+            Prefix::Index(index) => Self::from_index(index),
+            Prefix::Parenthese(parenthese) => Self::from_expression(parenthese.inner_expression()),
+            Prefix::Call(call) => Self::from_call(call),
+        }
+    }
+
+    fn from_identifier(identifier: &Identifier) -> Option<Self> {
+        let name = identifier.get_name();
+        if name == script_identifier().get_name() {
+            Some(Self::from_script())
+        } else if name == datamodel_identifier().get_name() {
+            Some(Self::from_root())
+        } else {
+            None
+        }
+    }
+
+    fn from_call(call: &FunctionCall) -> Option<Self> {
+        let method = call.get_method()?.get_name().as_str();
+
+        let mut arguments = match call.get_arguments() {
+            Arguments::Tuple(tuple) => tuple.iter_values(),
+            _ => return None,
+        };
+        let child_name = match arguments.next() {
+            Some(Expression::String(string)) => string.get_value(),
+            _ => return None,
+        };
+        let argument_count = 1 + arguments.count();
+
+        match method {
+            // a second argument to `FindFirstChild` makes the search recursive
+            FIND_FIRST_CHILD_METHOD if argument_count == 1 => {
+                let mut instance_path = Self::from_prefix(call.get_prefix())?;
+                instance_path.child(child_name);
+                Some(instance_path)
+            }
+            // the second argument of `WaitForChild` is a timeout
+            WAIT_FOR_CHILD_METHOD if argument_count <= 2 => {
+                let mut instance_path = Self::from_prefix(call.get_prefix())?;
+                instance_path.child(child_name);
+                Some(instance_path)
+            }
+            GET_SERVICE_METHOD if argument_count == 1 => match call.get_prefix() {
+                Prefix::Identifier(identifier)
+                    if identifier.get_name() == datamodel_identifier().get_name() =>
+                {
+                    let mut instance_path = Self::from_root();
+                    instance_path.child(child_name);
+                    Some(instance_path)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn from_field(field: &FieldExpression) -> Option<Self> {
+        let mut instance_path = Self::from_prefix(field.get_prefix())?;
+        instance_path.index(field.get_field().get_name());
+        Some(instance_path)
+    }
+
+    fn from_index(index: &IndexExpression) -> Option<Self> {
+        let mut instance_path = Self::from_prefix(index.get_prefix())?;
+        match index.get_index() {
+            Expression::String(string) => {
+                instance_path.index(string.get_value());
+                Some(instance_path)
+            }
+            _ => None,
+        }
+    }
+
+    fn index(&mut self, name: &str) {
+        if name == PARENT_PROPERTY {
+            self.parent();
+        } else {
+            self.child(name);
+        }
+    }
+
+    pub(crate) fn is_from_root(&self) -> bool {
+        self.root == InstancePathRoot::Root
+    }
+
+    pub(crate) fn root(&self) -> &InstancePathRoot {
+        &self.root
+    }
+
+    pub(crate) fn iter_components(&self) -> impl Iterator<Item = &InstancePathComponent> {
+        self.components.iter()
+    }
+
     pub(crate) fn convert(&self, index_style: &RobloxIndexStyle) -> Prefix {
         let mut components_iter = self.components.iter();
 
@@ -40,7 +175,7 @@ impl InstancePath {
                 let mut prefix: Prefix = datamodel_identifier().into();
                 if let Some(InstancePathComponent::Child(service_name)) = components_iter.next() {
                     prefix = FunctionCall::from_prefix(prefix)
-                        .with_method("GetService")
+                        .with_method(GET_SERVICE_METHOD)
                         .with_argument(StringExpression::from_value(service_name))
                         .into();
                 }
@@ -64,6 +199,31 @@ impl InstancePath {
     }
 }
 
+impl fmt::Display for InstancePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut components_iter = self.components.iter();
+
+        match &self.root {
+            InstancePathRoot::Root => {
+                write!(f, "{}", datamodel_identifier().get_name())?;
+                if let Some(InstancePathComponent::Child(service_name)) = components_iter.next() {
+                    write!(f, ":{}({:?})", GET_SERVICE_METHOD, service_name)?;
+                }
+            }
+            InstancePathRoot::Script => write!(f, "{}", script_identifier().get_name())?,
+        }
+
+        for component in components_iter {
+            match component {
+                InstancePathComponent::Parent => write!(f, ".{}", PARENT_PROPERTY)?,
+                InstancePathComponent::Child(child_name) => write!(f, ".{}", child_name)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum InstancePathRoot {
     Root,
@@ -85,5 +245,5 @@ pub(crate) fn datamodel_identifier() -> Identifier {
 }
 
 pub(crate) fn get_parent_instance(instance: impl Into<Prefix>) -> Prefix {
-    FieldExpression::new(instance.into(), "Parent").into()
+    FieldExpression::new(instance.into(), PARENT_PROPERTY).into()
 }
@@ -8,12 +8,13 @@ use serde::{Deserialize, Serialize};
 use crate::frontend::DarkluaResult;
 use crate::nodes::{Arguments, Block, FunctionCall};
 use crate::process::{DefaultVisitor, IdentifierTracker, NodeProcessor, NodeVisitor};
-use crate::rules::require::{is_require_call, PathRequireMode};
+use crate::rules::require::{is_require_call, ModuleRequireMode, PathRequireMode};
 use crate::rules::{Context, RuleConfiguration, RuleConfigurationError, RuleProperties};
 
-use instance_path::InstancePath;
+pub(crate) use instance_path::{match_roblox_require_call, InstancePath};
 pub use roblox_index_style::RobloxIndexStyle;
 pub use roblox_require_mode::RobloxRequireMode;
+pub(crate) use rojo_sourcemap::RojoSourcemap;
 
 use super::{verify_required_properties, Rule, RuleProcessResult};
 
@@ -27,6 +28,7 @@ use std::str::FromStr;
 pub enum RequireMode {
     Path(PathRequireMode),
     Roblox(RobloxRequireMode),
+    Module(ModuleRequireMode),
 }
 
 impl RequireMode {
@@ -38,6 +40,7 @@ impl RequireMode {
         match self {
             RequireMode::Path(path_mode) => path_mode.find_require(call, context),
             RequireMode::Roblox(roblox_mode) => roblox_mode.find_require(call, context),
+            RequireMode::Module(module_mode) => module_mode.find_require(call, context),
         }
     }
 
@@ -52,6 +55,9 @@ impl RequireMode {
             RequireMode::Roblox(roblox_mode) => {
                 roblox_mode.generate_require(path, current_mode, context)
             }
+            RequireMode::Module(module_mode) => {
+                module_mode.generate_require(path, current_mode, context)
+            }
         }
     }
 
@@ -61,6 +67,7 @@ impl RequireMode {
             RequireMode::Roblox(_roblox_mode) => {
                 matches!(path.file_stem().and_then(OsStr::to_str), Some("init"))
             }
+            RequireMode::Module(module_mode) => module_mode.is_module_folder_name(path),
         }
     }
 
@@ -68,6 +75,7 @@ impl RequireMode {
         match self {
             RequireMode::Roblox(roblox_mode) => roblox_mode.initialize(context),
             RequireMode::Path(path_mode) => path_mode.initialize(context),
+            RequireMode::Module(_) => Ok(()),
         }
     }
 }
@@ -79,6 +87,7 @@ impl FromStr for RequireMode {
         Ok(match s {
             "path" => Self::Path(Default::default()),
             "roblox" => Self::Roblox(Default::default()),
+            "module" => Self::Module(Default::default()),
             _ => return Err(format!("invalid require mode name `{}`", s)),
         })
     }
@@ -10,7 +10,7 @@ use crate::{
 use std::path::{Component, Path, PathBuf};
 
 use super::{
-    instance_path::{get_parent_instance, script_identifier},
+    instance_path::{get_parent_instance, match_roblox_require_call, script_identifier},
     RequireMode, RobloxIndexStyle,
 };
 
@@ -54,13 +54,47 @@ impl RobloxRequireMode {
         Ok(())
     }
 
+    pub(crate) fn rojo_sourcemap(&self) -> Option<&RojoSourcemap> {
+        self.cached_sourcemap.as_ref()
+    }
+
     pub(crate) fn find_require(
         &self,
-        _call: &FunctionCall,
-        _context: &Context,
+        call: &FunctionCall,
+        context: &Context,
     ) -> DarkluaResult<Option<PathBuf>> {
-        Err(DarkluaError::custom("unsupported initial require mode")
-            .context("Roblox require mode cannot be used as the current require mode"))
+        let instance_path = match match_roblox_require_call(call) {
+            Some(instance_path) => instance_path,
+            None => return Ok(None),
+        };
+
+        let sourcemap = self.cached_sourcemap.as_ref().ok_or_else(|| {
+            DarkluaError::custom(format!(
+                "unable to find the file required by `{}`",
+                instance_path
+            ))
+            .context(
+                "Roblox require mode needs a Rojo sourcemap to be used as the current require mode",
+            )
+        })?;
+
+        let source_path = utils::normalize_path(context.current_path());
+        log::trace!(
+            "find Roblox require for `{}` from `{}`",
+            instance_path,
+            source_path.display(),
+        );
+
+        sourcemap
+            .get_file_path(&source_path, &instance_path)
+            .map(|require_path| Some(require_path.to_path_buf()))
+            .ok_or_else(|| {
+                DarkluaError::custom(format!(
+                    "unable to find `{}` in Rojo sourcemap (from `{}`)",
+                    instance_path,
+                    source_path.display()
+                ))
+            })
     }
 
     pub(crate) fn generate_require(
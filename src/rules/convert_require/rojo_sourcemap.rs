@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{utils, DarkluaError};
 
+use super::instance_path::{InstancePathComponent, InstancePathRoot};
 use super::InstancePath;
 
 type NodeId = usize;
@@ -66,6 +67,33 @@ impl RojoSourcemapNode {
     fn is_root(&self) -> bool {
         self.id == self.parent_id
     }
+
+    fn get_child_by_name(&self, name: &str) -> Option<&RojoSourcemapNode> {
+        self.children.iter().find(|node| node.name == name)
+    }
+
+    /// Returns the file that defines the content of the node, ignoring the
+    /// project and meta files.
+    fn get_module_file_path(&self) -> Option<&Path> {
+        self.file_paths
+            .iter()
+            .find(|path| {
+                matches!(
+                    path.extension().and_then(|extension| extension.to_str()),
+                    Some("lua") | Some("luau")
+                )
+            })
+            .or_else(|| {
+                self.file_paths.iter().find(|path| {
+                    !path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .map(|name| name.ends_with(".project.json") || name.ends_with(".meta.json"))
+                        .unwrap_or(false)
+                })
+            })
+            .map(PathBuf::as_path)
+    }
 }
 
 struct RojoSourcemapNodeIterator<'a> {
@@ -181,6 +209,39 @@ impl RojoSourcemap {
         }
     }
 
+    /// Finds the file associated with the instance at the given path, starting from
+    /// the instance of the `from_file` script.
+    pub(crate) fn get_file_path(
+        &self,
+        from_file: impl AsRef<Path>,
+        instance_path: &InstancePath,
+    ) -> Option<&Path> {
+        let mut node = match instance_path.root() {
+            InstancePathRoot::Script => self.find_node(from_file.as_ref())?,
+            InstancePathRoot::Root => {
+                if self.is_datamodel {
+                    &self.root_node
+                } else {
+                    return None;
+                }
+            }
+        };
+
+        for component in instance_path.iter_components() {
+            node = match component {
+                InstancePathComponent::Parent => {
+                    if node.is_root() {
+                        return None;
+                    }
+                    self.root_node.get_descendant(node.parent_id())?
+                }
+                InstancePathComponent::Child(child_name) => node.get_child_by_name(child_name)?,
+            };
+        }
+
+        node.get_module_file_path()
+    }
+
     fn index_descendants<'a>(
         &self,
         mut instance_path: InstancePath,
@@ -230,6 +291,115 @@ mod test {
         RojoSourcemap::parse(content, "").expect("unable to parse sourcemap")
     }
 
+    mod file_paths {
+        use super::*;
+
+        const NESTED_PROJECT: &str = r#"{
+            "name": "Project",
+            "className": "ModuleScript",
+            "filePaths": ["src/init.lua", "default.project.json"],
+            "children": [
+                {
+                    "name": "main",
+                    "className": "ModuleScript",
+                    "filePaths": ["src/main.lua"]
+                },
+                {
+                    "name": "Lib",
+                    "className": "Folder",
+                    "children": [
+                        {
+                            "name": "format",
+                            "className": "ModuleScript",
+                            "filePaths": ["src/Lib/format.luau"]
+                        },
+                        {
+                            "name": "data",
+                            "className": "ModuleScript",
+                            "filePaths": ["src/Lib/data.json"]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        fn get_file_path(from_file: &str, components: &[&'static str]) -> Option<PathBuf> {
+            let instance_path =
+                components
+                    .iter()
+                    .fold(InstancePath::from_script(), |mut path, component| {
+                        match *component {
+                            "parent" => {
+                                path.parent();
+                            }
+                            child_name => {
+                                path.child(child_name);
+                            }
+                        }
+                        path
+                    });
+
+            new_sourcemap(NESTED_PROJECT)
+                .get_file_path(from_file, &instance_path)
+                .map(Path::to_path_buf)
+        }
+
+        #[test]
+        fn from_sibling_to_nested_module() {
+            pretty_assertions::assert_eq!(
+                get_file_path("src/main.lua", &["parent", "Lib", "format"]),
+                Some(PathBuf::from("src/Lib/format.luau"))
+            );
+        }
+
+        #[test]
+        fn from_init_to_child_module() {
+            pretty_assertions::assert_eq!(
+                get_file_path("src/init.lua", &["main"]),
+                Some(PathBuf::from("src/main.lua"))
+            );
+        }
+
+        #[test]
+        fn from_nested_module_to_root_module() {
+            pretty_assertions::assert_eq!(
+                get_file_path("src/Lib/format.luau", &["parent", "parent"]),
+                Some(PathBuf::from("src/init.lua"))
+            );
+        }
+
+        #[test]
+        fn from_nested_module_to_json_module() {
+            pretty_assertions::assert_eq!(
+                get_file_path("src/Lib/format.luau", &["parent", "data"]),
+                Some(PathBuf::from("src/Lib/data.json"))
+            );
+        }
+
+        #[test]
+        fn folder_without_file_is_not_found() {
+            pretty_assertions::assert_eq!(get_file_path("src/main.lua", &["parent", "Lib"]), None);
+        }
+
+        #[test]
+        fn unknown_child_is_not_found() {
+            pretty_assertions::assert_eq!(
+                get_file_path("src/main.lua", &["parent", "Unknown"]),
+                None
+            );
+        }
+
+        #[test]
+        fn parent_of_root_is_not_found() {
+            pretty_assertions::assert_eq!(get_file_path("src/init.lua", &["parent"]), None);
+        }
+
+        #[test]
+        fn unknown_source_file_is_not_found() {
+            pretty_assertions::assert_eq!(get_file_path("src/unknown.lua", &["main"]), None);
+        }
+    }
+
     mod instance_paths {
         use super::*;
 
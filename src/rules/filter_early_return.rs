@@ -32,7 +32,9 @@ impl Processor {
                 | Statement::CompoundAssign(_)
                 | Statement::Function(_)
                 | Statement::GenericFor(_)
+                | Statement::Goto(_)
                 | Statement::If(_)
+                | Statement::Label(_)
                 | Statement::LocalAssign(_)
                 | Statement::LocalFunction(_)
                 | Statement::TypeFunction(_)
@@ -42,6 +44,13 @@ impl Processor {
                 | Statement::While(_)
                 | Statement::TypeDeclaration(_) => None,
             })
+            .filter(|remove_after| {
+                // a label after the early return can still be reached with a goto
+                !block
+                    .iter_statements()
+                    .skip(remove_after + 1)
+                    .any(|statement| matches!(statement, Statement::Label(_)))
+            })
     }
 }
 
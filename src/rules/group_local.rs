@@ -55,6 +55,10 @@ impl GroupLocalProcessor {
     }
 
     fn should_merge(&self, first: &LocalAssignStatement, next: &mut LocalAssignStatement) -> bool {
+        if first.has_attributes() || next.has_attributes() {
+            return false;
+        }
+
         let first_value_count = first.values_len();
 
         if first.variables_len() > first_value_count && first_value_count != 0 {
@@ -0,0 +1,471 @@
+use std::cell::RefCell;
+use std::mem;
+
+use crate::nodes::{
+    Arguments, AssignStatement, Block, DecimalNumber, Expression, FunctionCall, Identifier,
+    LastStatement, Prefix, ReturnStatement, Statement, Trivia, Variable,
+};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::utils::lines;
+
+use super::remove_comments::FilterCommentProcessor;
+
+const IGNORE_NEXT_LINE_DIRECTIVE: &str = "darklua-ignore-next-line";
+const DISABLE_DIRECTIVE: &str = "darklua-disable";
+const ENABLE_DIRECTIVE: &str = "darklua-enable";
+
+// a global function name used to mark the location of the statements
+// hidden from a rule, so they can be put back once the rule is applied.
+// The placeholders also read and assign the identifiers used by the hidden
+// statements, so rules can't assume these variables are unused or constant
+const EXCLUDED_STATEMENT_PLACEHOLDER: &str = "__DARKLUA_EXCLUDED_STATEMENT";
+
+/// Returns `true` if the given code may contain ignore directives. This can be used
+/// to avoid preserving tokens when parsing files without any directive.
+pub(crate) fn may_contain_ignore_directives(code: &str) -> bool {
+    code.contains("darklua-")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DirectiveKind {
+    IgnoreNextLine,
+    Disable,
+    Enable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Directive {
+    line: usize,
+    kind: DirectiveKind,
+    // an empty list applies the directive to all rules
+    rules: Vec<String>,
+}
+
+impl Directive {
+    fn parse(comment: &str, line: usize) -> Option<Self> {
+        let content = comment.strip_prefix("--")?.trim();
+
+        let (kind, rest) = [
+            (IGNORE_NEXT_LINE_DIRECTIVE, DirectiveKind::IgnoreNextLine),
+            (DISABLE_DIRECTIVE, DirectiveKind::Disable),
+            (ENABLE_DIRECTIVE, DirectiveKind::Enable),
+        ]
+        .iter()
+        .find_map(|(directive, kind)| {
+            content
+                .strip_prefix(directive)
+                .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+                .map(|rest| (kind.clone(), rest))
+        })?;
+
+        let rules = rest
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| !name.is_empty())
+            .map(ToOwned::to_owned)
+            .collect();
+
+        Some(Self {
+            // the directive applies to the line after the last line of the comment
+            line: line + comment.matches('\n').count(),
+            kind,
+            rules,
+        })
+    }
+
+    fn applies_to(&self, rule_name: &str) -> bool {
+        self.rules.is_empty() || self.rules.iter().any(|name| name == rule_name)
+    }
+}
+
+/// The ignore directives found in the comments of a file. These comments can disable
+/// rules on specific lines:
+///
+/// - `-- darklua-ignore-next-line [rules...]` excludes the statement on the next line
+/// - `-- darklua-disable [rules...]` excludes the statements until the end of the file
+///   or until a `-- darklua-enable [rules...]` comment
+///
+/// When no rule name is given, the directive applies to all rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct IgnoreDirectives {
+    directives: Vec<Directive>,
+}
+
+impl IgnoreDirectives {
+    /// Collects the ignore directives from the comments of a block. The block must be parsed
+    /// with tokens preserved, otherwise no directive can be found.
+    pub(crate) fn from_block(block: &mut Block, original_code: &str) -> Self {
+        let directives = RefCell::new(Vec::new());
+
+        let mut processor = FilterCommentProcessor::new(|trivia: &Trivia| {
+            if let Some(line) = trivia.get_line_number() {
+                if let Some(directive) = Directive::parse(trivia.read(original_code), line) {
+                    directives.borrow_mut().push(directive);
+                }
+            }
+            true
+        });
+        DefaultVisitor::visit_block(block, &mut processor);
+
+        let mut directives = directives.into_inner();
+        directives.sort_by_key(|directive| directive.line);
+
+        Self { directives }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    /// Returns an iterator over the rule names found in the directives.
+    pub(crate) fn iter_rule_names(&self) -> impl Iterator<Item = &str> {
+        self.directives
+            .iter()
+            .flat_map(|directive| directive.rules.iter().map(String::as_str))
+    }
+
+    /// Computes the lines excluded for the given rule.
+    pub(crate) fn excluded_lines(&self, rule_name: &str) -> ExcludedLines {
+        let mut ranges = Vec::new();
+        let mut disabled_since = None;
+
+        for directive in self
+            .directives
+            .iter()
+            .filter(|directive| directive.applies_to(rule_name))
+        {
+            match directive.kind {
+                DirectiveKind::IgnoreNextLine => {
+                    ranges.push((directive.line + 1, directive.line + 1));
+                }
+                DirectiveKind::Disable => {
+                    disabled_since.get_or_insert(directive.line);
+                }
+                DirectiveKind::Enable => {
+                    if let Some(start) = disabled_since.take() {
+                        ranges.push((start, directive.line));
+                    }
+                }
+            }
+        }
+
+        if let Some(start) = disabled_since {
+            ranges.push((start, usize::MAX));
+        }
+
+        ExcludedLines { ranges }
+    }
+}
+
+/// Line ranges (inclusive) where a rule should not be applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ExcludedLines {
+    ranges: Vec<(usize, usize)>,
+}
+
+impl ExcludedLines {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub(crate) fn contains(&self, line: usize) -> bool {
+        self.ranges
+            .iter()
+            .any(|(start, end)| *start <= line && line <= *end)
+    }
+
+    pub(crate) fn contains_statement(&self, statement: &Statement) -> bool {
+        lines::statement_line(statement)
+            .map(|line| self.contains(line))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn contains_last_statement(&self, statement: &LastStatement) -> bool {
+        lines::last_statement_line(statement)
+            .map(|line| self.contains(line))
+            .unwrap_or(false)
+    }
+}
+
+fn placeholder(index: usize, identifiers: &[String]) -> FunctionCall {
+    identifiers.iter().fold(
+        FunctionCall::from_name(EXCLUDED_STATEMENT_PLACEHOLDER)
+            .with_argument(DecimalNumber::new(index as f64)),
+        |call, name| call.with_argument(Identifier::new(name)),
+    )
+}
+
+fn statement_placeholder(index: usize, identifiers: &[String]) -> Statement {
+    let call = placeholder(index, identifiers);
+
+    if identifiers.is_empty() {
+        call.into()
+    } else {
+        AssignStatement::new(
+            identifiers
+                .iter()
+                .map(|name| Variable::new(name.as_str()))
+                .collect(),
+            vec![call.into()],
+        )
+        .into()
+    }
+}
+
+fn statement_placeholder_call(statement: &Statement) -> Option<&FunctionCall> {
+    match statement {
+        Statement::Call(call) => Some(call),
+        Statement::Assign(assign) if assign.values_len() == 1 => {
+            match assign.iter_values().next() {
+                Some(Expression::Call(call)) => Some(call),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Collects the names of the identifiers used in a statement, in the order they appear.
+#[derive(Default)]
+struct CollectIdentifiers {
+    names: Vec<String>,
+}
+
+impl CollectIdentifiers {
+    fn from_statement(statement: &mut Statement) -> Vec<String> {
+        let mut collector = Self::default();
+        DefaultVisitor::visit_statement(statement, &mut collector);
+        collector.names
+    }
+
+    fn from_last_statement(statement: &mut LastStatement) -> Vec<String> {
+        let mut collector = Self::default();
+        DefaultVisitor::visit_last_statement(statement, &mut collector);
+        collector.names
+    }
+}
+
+impl NodeProcessor for CollectIdentifiers {
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        let name = identifier.get_name();
+        if !self.names.iter().any(|existing| existing == name) {
+            self.names.push(name.to_owned());
+        }
+    }
+}
+
+fn placeholder_index(call: &FunctionCall) -> Option<usize> {
+    match (call.get_prefix(), call.get_arguments()) {
+        (Prefix::Identifier(identifier), Arguments::Tuple(arguments))
+            if identifier.get_name() == EXCLUDED_STATEMENT_PLACEHOLDER
+                && call.get_method().is_none() =>
+        {
+            match arguments.iter_values().next() {
+                Some(Expression::Number(number)) => Some(number.compute_value() as usize),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+enum ExcludedStatement {
+    Statement(Box<Statement>),
+    LastStatement(LastStatement),
+}
+
+/// Statements hidden from a rule, replaced by placeholders in the block.
+#[derive(Default)]
+pub(crate) struct DetachedStatements {
+    statements: Vec<Option<ExcludedStatement>>,
+}
+
+impl DetachedStatements {
+    /// Replaces the statements starting on an excluded line with placeholders.
+    pub(crate) fn detach(block: &mut Block, excluded_lines: &ExcludedLines) -> Self {
+        let mut processor = DetachProcessor {
+            excluded_lines,
+            detached: Self::default(),
+        };
+        DefaultVisitor::visit_block(block, &mut processor);
+        processor.detached
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Puts back the detached statements in place of their placeholders.
+    pub(crate) fn restore(self, block: &mut Block) {
+        let mut processor = RestoreProcessor { detached: self };
+        DefaultVisitor::visit_block(block, &mut processor);
+
+        let missing = processor
+            .detached
+            .statements
+            .iter()
+            .filter(|statement| statement.is_some())
+            .count();
+        if missing != 0 {
+            log::debug!(
+                "{} excluded statement{} removed while applying a rule",
+                missing,
+                if missing == 1 { " was" } else { "s were" }
+            );
+        }
+    }
+
+    fn push(&mut self, statement: ExcludedStatement) -> usize {
+        self.statements.push(Some(statement));
+        self.statements.len() - 1
+    }
+
+    fn take(&mut self, call: &FunctionCall) -> Option<ExcludedStatement> {
+        placeholder_index(call)
+            .and_then(|index| self.statements.get_mut(index))
+            .and_then(Option::take)
+    }
+}
+
+struct DetachProcessor<'a> {
+    excluded_lines: &'a ExcludedLines,
+    detached: DetachedStatements,
+}
+
+impl NodeProcessor for DetachProcessor<'_> {
+    fn process_block(&mut self, block: &mut Block) {
+        for statement in block.iter_mut_statements() {
+            if self.excluded_lines.contains_statement(statement) {
+                let identifiers = CollectIdentifiers::from_statement(statement);
+                let original = mem::replace(statement, placeholder(0, &[]).into());
+                let index = self
+                    .detached
+                    .push(ExcludedStatement::Statement(Box::new(original)));
+                *statement = statement_placeholder(index, &identifiers);
+            }
+        }
+
+        if let Some(last_statement) = block.mutate_last_statement() {
+            if self.excluded_lines.contains_last_statement(last_statement) {
+                let identifiers = CollectIdentifiers::from_last_statement(last_statement);
+                let original = mem::replace(last_statement, LastStatement::new_break());
+                let index = self
+                    .detached
+                    .push(ExcludedStatement::LastStatement(original));
+                *last_statement = ReturnStatement::one(placeholder(index, &identifiers)).into();
+            }
+        }
+    }
+}
+
+struct RestoreProcessor {
+    detached: DetachedStatements,
+}
+
+impl NodeProcessor for RestoreProcessor {
+    fn process_block(&mut self, block: &mut Block) {
+        for statement in block.iter_mut_statements() {
+            let original = match statement_placeholder_call(statement)
+                .and_then(|call| self.detached.take(call))
+            {
+                Some(ExcludedStatement::Statement(original)) => *original,
+                _ => continue,
+            };
+            *statement = original;
+        }
+
+        let restored = match block.get_last_statement() {
+            Some(LastStatement::Return(statement)) if statement.len() == 1 => {
+                match statement.iter_expressions().next() {
+                    Some(Expression::Call(call)) => match self.detached.take(call) {
+                        Some(ExcludedStatement::LastStatement(original)) => Some(original),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(original) = restored {
+            block.replace_last_statement(original);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Parser;
+
+    fn parse_directives(code: &str) -> IgnoreDirectives {
+        let mut block = Parser::default().preserve_tokens().parse(code).unwrap();
+        IgnoreDirectives::from_block(&mut block, code)
+    }
+
+    #[test]
+    fn ignore_next_line_for_all_rules() {
+        let directives = parse_directives("local a\n-- darklua-ignore-next-line\nlocal b\n");
+
+        pretty_assertions::assert_eq!(
+            directives.excluded_lines("rename_variables"),
+            ExcludedLines {
+                ranges: vec![(3, 3)]
+            }
+        );
+    }
+
+    #[test]
+    fn ignore_next_line_for_a_rule() {
+        let directives =
+            parse_directives("-- darklua-ignore-next-line rename_variables\nlocal b\n");
+
+        pretty_assertions::assert_eq!(
+            directives.excluded_lines("rename_variables"),
+            ExcludedLines {
+                ranges: vec![(2, 2)]
+            }
+        );
+        assert!(directives.excluded_lines("remove_types").is_empty());
+    }
+
+    #[test]
+    fn disable_and_enable_rules() {
+        let directives = parse_directives(
+            "-- darklua-disable remove_types, remove_spaces\nlocal a\n-- darklua-enable remove_types\nlocal b\n",
+        );
+
+        pretty_assertions::assert_eq!(
+            directives.excluded_lines("remove_types"),
+            ExcludedLines {
+                ranges: vec![(1, 3)]
+            }
+        );
+        pretty_assertions::assert_eq!(
+            directives.excluded_lines("remove_spaces"),
+            ExcludedLines {
+                ranges: vec![(1, usize::MAX)]
+            }
+        );
+    }
+
+    #[test]
+    fn enable_all_rules() {
+        let directives = parse_directives(
+            "-- darklua-disable remove_types\nlocal a\n-- darklua-enable\nlocal b\n",
+        );
+
+        pretty_assertions::assert_eq!(
+            directives.excluded_lines("remove_types"),
+            ExcludedLines {
+                ranges: vec![(1, 3)]
+            }
+        );
+    }
+
+    #[test]
+    fn ignore_unrelated_comments() {
+        let directives = parse_directives("-- darklua-disabled\n-- darklua\nlocal a\n");
+
+        assert!(directives.is_empty());
+    }
+}
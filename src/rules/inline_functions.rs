@@ -0,0 +1,610 @@
+use std::collections::{HashMap, HashSet};
+use std::ops;
+
+use crate::nodes::{
+    Block, DoStatement, Expression, FunctionCall, FunctionExpression, FunctionStatement,
+    GotoStatement, Identifier, LabelStatement, LastStatement, LocalAssignStatement,
+    LocalFunctionStatement, ParentheseExpression, Prefix, Statement, TypedIdentifier,
+};
+use crate::process::{
+    DefaultPostVisitor, DefaultVisitor, Evaluator, IdentifierTracker, NodePostProcessor,
+    NodePostVisitor, NodeProcessor, NodeVisitor, Scope, ScopePostVisitor, ScopeVisitor,
+    VariableTracker,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+use crate::utils::expressions_as_statement;
+
+use super::verify_no_rule_properties;
+
+// the maximum number of statements (including nested statements) that a
+// function can have to be inlined
+const MAX_INLINED_STATEMENTS: usize = 8;
+
+/// Inspects the body of a function to find out if it can be inlined.
+#[derive(Default)]
+struct FunctionBodyInspector {
+    function_depth: usize,
+    statement_count: usize,
+    return_count: usize,
+    has_labels: bool,
+}
+
+impl FunctionBodyInspector {
+    fn can_inline(block: &mut Block) -> bool {
+        let mut inspector = Self::default();
+        DefaultPostVisitor::visit_block(block, &mut inspector);
+
+        let tail_return = matches!(block.get_last_statement(), Some(LastStatement::Return(_)));
+
+        inspector.statement_count <= MAX_INLINED_STATEMENTS
+            && !inspector.has_labels
+            // a return statement can only be inlined when it is at the end of the function
+            && inspector.return_count == usize::from(tail_return)
+    }
+}
+
+impl NodeProcessor for FunctionBodyInspector {
+    fn process_statement(&mut self, _: &mut Statement) {
+        self.statement_count += 1;
+    }
+
+    fn process_last_statement(&mut self, statement: &mut LastStatement) {
+        self.statement_count += 1;
+        if self.function_depth == 0 && matches!(statement, LastStatement::Return(_)) {
+            self.return_count += 1;
+        }
+    }
+
+    fn process_goto_statement(&mut self, _: &mut GotoStatement) {
+        self.has_labels = true;
+    }
+
+    fn process_label_statement(&mut self, _: &mut LabelStatement) {
+        self.has_labels = true;
+    }
+
+    fn process_function_expression(&mut self, _: &mut FunctionExpression) {
+        self.function_depth += 1;
+    }
+
+    fn process_function_statement(&mut self, _: &mut FunctionStatement) {
+        self.function_depth += 1;
+    }
+
+    fn process_local_function_statement(&mut self, _: &mut LocalFunctionStatement) {
+        self.function_depth += 1;
+    }
+}
+
+impl NodePostProcessor for FunctionBodyInspector {
+    fn process_after_function_expression(&mut self, _: &mut FunctionExpression) {
+        self.function_depth -= 1;
+    }
+
+    fn process_after_function_statement(&mut self, _: &mut FunctionStatement) {
+        self.function_depth -= 1;
+    }
+
+    fn process_after_local_function_statement(&mut self, _: &mut LocalFunctionStatement) {
+        self.function_depth -= 1;
+    }
+}
+
+/// Finds the identifiers used in a function that are not defined by the function.
+#[derive(Default)]
+struct FindFreeIdentifiers {
+    identifier_tracker: IdentifierTracker,
+    free_identifiers: HashSet<String>,
+}
+
+impl FindFreeIdentifiers {
+    fn find(function: &LocalFunctionStatement) -> HashSet<String> {
+        let mut finder = Self::default();
+        let mut block = function.get_block().clone();
+
+        finder.push();
+        for parameter in function.iter_parameters() {
+            finder.insert(&mut parameter.get_name().to_owned());
+        }
+        ScopeVisitor::visit_block(&mut block, &mut finder);
+        finder.pop();
+
+        finder.free_identifiers
+    }
+}
+
+impl ops::Deref for FindFreeIdentifiers {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl ops::DerefMut for FindFreeIdentifiers {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for FindFreeIdentifiers {
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        let name = identifier.get_name();
+        if !self.is_identifier_used(name) {
+            self.free_identifiers.insert(name.to_owned());
+        }
+    }
+}
+
+/// Finds the parts of an expression that are not evaluated right away: function calls
+/// can observe or change variables, and functions capture variables by reference.
+#[derive(Default)]
+struct FindDeferredEvaluation {
+    has_calls: bool,
+    has_functions: bool,
+}
+
+impl NodeProcessor for FindDeferredEvaluation {
+    fn process_function_call(&mut self, _: &mut FunctionCall) {
+        self.has_calls = true;
+    }
+
+    fn process_function_expression(&mut self, _: &mut FunctionExpression) {
+        self.has_functions = true;
+    }
+}
+
+#[derive(Debug, Clone)]
+struct InlineCandidate {
+    parameters: Vec<Identifier>,
+    block: Block,
+    // the identifiers used by the function that are defined outside of it, with the
+    // index of the variable they refer to (or None for globals)
+    free_identifiers: Vec<(String, Option<usize>)>,
+    calls: usize,
+    usages: usize,
+}
+
+impl InlineCandidate {
+    fn is_called_only(&self) -> bool {
+        self.calls != 0 && self.calls == self.usages
+    }
+}
+
+#[derive(Default)]
+struct FunctionCollector {
+    variables: VariableTracker,
+    candidates: HashMap<usize, InlineCandidate>,
+}
+
+impl FunctionCollector {
+    fn into_candidates(self) -> HashMap<usize, InlineCandidate> {
+        self.candidates
+            .into_iter()
+            .filter(|(_, candidate)| candidate.is_called_only())
+            .collect()
+    }
+
+    fn find_candidate(&mut self, identifier: &str) -> Option<&mut InlineCandidate> {
+        let index = self.variables.resolve(identifier)?;
+        self.candidates.get_mut(&index)
+    }
+}
+
+impl Scope for FunctionCollector {
+    fn push(&mut self) {
+        self.variables.push();
+    }
+
+    fn pop(&mut self) {
+        self.variables.pop();
+    }
+
+    fn insert(&mut self, identifier: &mut String) {
+        self.variables.insert(identifier);
+    }
+
+    fn insert_self(&mut self) {
+        self.variables.insert_self();
+    }
+
+    fn insert_local(&mut self, identifier: &mut String, value: Option<&mut Expression>) {
+        self.variables.insert_local(identifier, value);
+    }
+
+    fn insert_local_function(&mut self, function: &mut LocalFunctionStatement) {
+        let free_identifiers = if function.is_variadic()
+            || !FunctionBodyInspector::can_inline(function.mutate_block())
+        {
+            None
+        } else {
+            let free_identifiers = FindFreeIdentifiers::find(function);
+
+            // a recursive function would use its own name
+            if free_identifiers.contains(function.get_name()) {
+                None
+            } else {
+                Some(
+                    free_identifiers
+                        .into_iter()
+                        .map(|identifier| {
+                            let index = self.variables.resolve(&identifier);
+                            (identifier, index)
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        let index = self.variables.declare(function.get_name());
+
+        if let Some(free_identifiers) = free_identifiers {
+            self.candidates.insert(
+                index,
+                InlineCandidate {
+                    parameters: function
+                        .iter_parameters()
+                        .map(TypedIdentifier::get_identifier)
+                        .cloned()
+                        .collect(),
+                    block: function.get_block().clone(),
+                    free_identifiers,
+                    calls: 0,
+                    usages: 0,
+                },
+            );
+        }
+    }
+}
+
+impl NodeProcessor for FunctionCollector {
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        if call.get_method().is_some() {
+            return;
+        }
+        if let Prefix::Identifier(identifier) = call.get_prefix() {
+            if let Some(candidate) = self.find_candidate(identifier.get_name()) {
+                candidate.calls += 1;
+            }
+        }
+    }
+
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        if let Some(candidate) = self.find_candidate(identifier.get_name()) {
+            candidate.usages += 1;
+        }
+    }
+}
+
+/// Replaces the parameters of a function with the arguments of a call.
+struct ReplaceParameters {
+    identifier_tracker: IdentifierTracker,
+    arguments: HashMap<String, Expression>,
+    argument_identifiers: Vec<String>,
+    captured: bool,
+}
+
+impl ReplaceParameters {
+    fn new(arguments: HashMap<String, Expression>) -> Self {
+        let argument_identifiers = arguments
+            .values()
+            .filter_map(|argument| match argument {
+                Expression::Identifier(identifier) => Some(identifier.get_name().to_owned()),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            arguments,
+            argument_identifiers,
+            captured: false,
+        }
+    }
+
+    fn find_argument(&mut self, identifier: &Identifier) -> Option<Expression> {
+        let name = identifier.get_name();
+        if self.is_identifier_used(name) {
+            return None;
+        }
+        let argument = self.arguments.get(name)?.clone();
+
+        if self
+            .argument_identifiers
+            .iter()
+            .any(|identifier| self.is_identifier_used(identifier))
+        {
+            // an argument would refer to a variable defined in the function
+            self.captured = true;
+            None
+        } else {
+            Some(argument)
+        }
+    }
+}
+
+impl ops::Deref for ReplaceParameters {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl ops::DerefMut for ReplaceParameters {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for ReplaceParameters {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Identifier(identifier) = expression {
+            if let Some(argument) = self.find_argument(identifier) {
+                *expression = argument;
+            }
+        }
+    }
+
+    fn process_prefix_expression(&mut self, prefix: &mut Prefix) {
+        if let Prefix::Identifier(identifier) = prefix {
+            if let Some(argument) = self.find_argument(identifier) {
+                *prefix = match argument {
+                    Expression::Identifier(identifier) => identifier.into(),
+                    argument => ParentheseExpression::new(argument).into(),
+                };
+            }
+        }
+    }
+}
+
+struct Inliner {
+    variables: VariableTracker,
+    candidates: HashMap<usize, InlineCandidate>,
+    evaluator: Evaluator,
+}
+
+impl Inliner {
+    fn new(candidates: HashMap<usize, InlineCandidate>) -> Self {
+        Self {
+            variables: VariableTracker::default(),
+            candidates,
+            evaluator: Evaluator::default(),
+        }
+    }
+
+    fn find_candidate(&self, call: &FunctionCall) -> Option<&InlineCandidate> {
+        if call.get_method().is_some() {
+            return None;
+        }
+        let identifier = match call.get_prefix() {
+            Prefix::Identifier(identifier) => identifier,
+            _ => return None,
+        };
+        let index = self.resolve(identifier.get_name())?;
+        let candidate = self.candidates.get(&index)?;
+
+        // verify that the identifiers used by the function refer to the same
+        // variables at the call site
+        if candidate
+            .free_identifiers
+            .iter()
+            .all(|(identifier, index)| self.resolve(identifier) == *index)
+        {
+            Some(candidate)
+        } else {
+            None
+        }
+    }
+
+    fn inline_statement(&self, call: &FunctionCall) -> Option<Statement> {
+        let candidate = self.find_candidate(call)?;
+        let arguments = call.get_arguments().clone().to_expressions();
+
+        let mut block = candidate.block.clone();
+        let mut statements = Vec::new();
+
+        if !candidate.parameters.is_empty() {
+            statements.push(
+                LocalAssignStatement::new(
+                    candidate
+                        .parameters
+                        .iter()
+                        .cloned()
+                        .map(TypedIdentifier::from)
+                        .collect(),
+                    arguments,
+                )
+                .into(),
+            );
+        } else {
+            let arguments: Vec<_> = arguments
+                .into_iter()
+                .filter(|argument| self.evaluator.has_side_effects(argument))
+                .collect();
+            if !arguments.is_empty() {
+                statements.push(expressions_as_statement(arguments));
+            }
+        }
+
+        statements.extend(block.take_statements());
+
+        if let Some(LastStatement::Return(return_statement)) = block.take_last_statement() {
+            let values: Vec<_> = return_statement
+                .into_iter_expressions()
+                .filter(|value| self.evaluator.has_side_effects(value))
+                .collect();
+            if !values.is_empty() {
+                statements.push(expressions_as_statement(values));
+            }
+        }
+
+        Some(DoStatement::new(Block::new(statements, None)).into())
+    }
+
+    fn inline_expression(&self, call: &FunctionCall) -> Option<Expression> {
+        let candidate = self.find_candidate(call)?;
+
+        if candidate.block.statements_len() != 0 {
+            return None;
+        }
+        let mut value = match candidate.block.get_last_statement() {
+            Some(LastStatement::Return(return_statement)) if return_statement.len() == 1 => {
+                return_statement.iter_expressions().next()?.clone()
+            }
+            _ => return None,
+        };
+
+        let mut find_deferred = FindDeferredEvaluation::default();
+        DefaultVisitor::visit_expression(&mut value.clone(), &mut find_deferred);
+
+        let arguments = call.get_arguments().clone().to_expressions();
+
+        // arguments are evaluated before the function body, so they can only be moved
+        // into the expression when their value can't change. A variable moved into a
+        // function would be read when that function runs instead of when it is created
+        let can_move_arguments = arguments.iter().all(|argument| match argument {
+            Expression::Identifier(_) => !find_deferred.has_calls && !find_deferred.has_functions,
+            _ => {
+                !self.evaluator.has_side_effects(argument)
+                    && self.evaluator.evaluate(argument).to_expression().is_some()
+            }
+        });
+        if !can_move_arguments {
+            return None;
+        }
+
+        let mut arguments = arguments.into_iter();
+        let arguments = candidate
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let argument = match arguments.next() {
+                    Some(argument @ (Expression::Binary(_) | Expression::Unary(_))) => {
+                        ParentheseExpression::new(argument).into()
+                    }
+                    Some(argument) => argument,
+                    None => Expression::nil(),
+                };
+                (parameter.get_name().to_owned(), argument)
+            })
+            .collect();
+
+        let mut replace_parameters = ReplaceParameters::new(arguments);
+        ScopeVisitor::visit_expression(&mut value, &mut replace_parameters);
+
+        if replace_parameters.captured {
+            return None;
+        }
+
+        Some(match value {
+            Expression::Binary(_)
+            | Expression::Unary(_)
+            | Expression::If(_)
+            | Expression::TypeCast(_) => ParentheseExpression::new(value).into(),
+            _ => value,
+        })
+    }
+}
+
+impl ops::Deref for Inliner {
+    type Target = VariableTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.variables
+    }
+}
+
+impl ops::DerefMut for Inliner {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.variables
+    }
+}
+
+impl NodeProcessor for Inliner {}
+
+impl NodePostProcessor for Inliner {
+    fn process_after_statement(&mut self, statement: &mut Statement) {
+        if let Statement::Call(call) = statement {
+            if let Some(inlined) = self.inline_statement(call) {
+                *statement = inlined;
+            }
+        }
+    }
+
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(inlined) = self.inline_expression(call) {
+                *expression = inlined;
+            }
+        }
+    }
+}
+
+pub const INLINE_FUNCTIONS_RULE_NAME: &str = "inline_functions";
+
+/// A rule that inlines calls to small local functions.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InlineFunctions {}
+
+impl FlawlessRule for InlineFunctions {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut collector = FunctionCollector::default();
+        ScopeVisitor::visit_block(block, &mut collector);
+
+        let candidates = collector.into_candidates();
+        if candidates.is_empty() {
+            return;
+        }
+
+        let mut inliner = Inliner::new(candidates);
+        ScopePostVisitor::visit_block(block, &mut inliner);
+    }
+}
+
+impl RuleConfiguration for InlineFunctions {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        INLINE_FUNCTIONS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> InlineFunctions {
+        InlineFunctions::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_inline_functions", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'inline_functions',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
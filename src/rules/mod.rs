@@ -4,15 +4,21 @@ mod append_text_comment;
 pub mod bundle;
 mod call_parens;
 mod compute_expression;
+mod conditional_compilation;
 mod configuration_error;
 mod convert_index_to_field;
+mod convert_luau_number;
 mod convert_require;
 mod empty_do;
 mod filter_early_return;
 mod group_local;
+pub(crate) mod ignore_directives;
 mod inject_value;
+mod inline_functions;
 mod method_def;
 mod no_local_function;
+mod path_filter;
+mod propagate_constants;
 mod remove_assertions;
 mod remove_call_match;
 mod remove_comments;
@@ -20,6 +26,8 @@ mod remove_compound_assign;
 mod remove_continue;
 mod remove_debug_profiling;
 mod remove_floor_division;
+mod remove_function_calls;
+mod remove_generalized_iteration;
 mod remove_if_expression;
 mod remove_interpolated_string;
 mod remove_nil_declarations;
@@ -37,21 +45,27 @@ mod unused_while;
 pub use append_text_comment::*;
 pub use call_parens::*;
 pub use compute_expression::*;
+pub use conditional_compilation::*;
 pub use configuration_error::RuleConfigurationError;
 pub use convert_index_to_field::*;
+pub use convert_luau_number::*;
 pub use convert_require::*;
 pub use empty_do::*;
 pub use filter_early_return::*;
 pub use group_local::*;
 pub use inject_value::*;
+pub use inline_functions::*;
 pub use method_def::*;
 pub use no_local_function::*;
+pub use propagate_constants::*;
 pub use remove_assertions::*;
 pub use remove_comments::*;
 pub use remove_compound_assign::*;
 pub use remove_continue::*;
 pub use remove_debug_profiling::*;
 pub use remove_floor_division::*;
+pub use remove_function_calls::*;
+pub use remove_generalized_iteration::*;
 pub use remove_if_expression::*;
 pub use remove_interpolated_string::*;
 pub use remove_nil_declarations::*;
@@ -65,8 +79,11 @@ pub(crate) use shift_token_line::*;
 pub use unused_if_branch::*;
 pub use unused_while::*;
 
+use crate::frontend::SourceRange;
 use crate::nodes::Block;
 use crate::Resources;
+use ignore_directives::ExcludedLines;
+use path_filter::{PathFilter, PathFilteredRule};
 
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeMap;
@@ -83,6 +100,7 @@ pub struct ContextBuilder<'a, 'resources, 'code> {
     original_code: &'code str,
     blocks: HashMap<PathBuf, &'a Block>,
     project_location: Option<PathBuf>,
+    excluded_lines: ExcludedLines,
 }
 
 impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
@@ -97,6 +115,7 @@ impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
             original_code,
             blocks: Default::default(),
             project_location: None,
+            excluded_lines: Default::default(),
         }
     }
 
@@ -105,6 +124,11 @@ impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
         self
     }
 
+    pub(crate) fn with_excluded_lines(mut self, excluded_lines: ExcludedLines) -> Self {
+        self.excluded_lines = excluded_lines;
+        self
+    }
+
     pub fn build(self) -> Context<'a, 'resources, 'code> {
         Context {
             path: self.path,
@@ -112,7 +136,9 @@ impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
             original_code: self.original_code,
             blocks: self.blocks,
             project_location: self.project_location,
+            excluded_lines: self.excluded_lines,
             dependencies: Default::default(),
+            source_ranges: Default::default(),
         }
     }
 
@@ -129,7 +155,9 @@ pub struct Context<'a, 'resources, 'code> {
     original_code: &'code str,
     blocks: HashMap<PathBuf, &'a Block>,
     project_location: Option<PathBuf>,
+    excluded_lines: ExcludedLines,
     dependencies: std::cell::RefCell<Vec<PathBuf>>,
+    source_ranges: std::cell::RefCell<Vec<SourceRange>>,
 }
 
 impl Context<'_, '_, '_> {
@@ -141,6 +169,16 @@ impl Context<'_, '_, '_> {
         self.path.as_ref()
     }
 
+    /// Returns `true` if the current rule should not be applied on the given line, because
+    /// of an ignore directive comment (like `-- darklua-ignore-next-line`).
+    pub fn is_line_excluded(&self, line: usize) -> bool {
+        self.excluded_lines.contains(line)
+    }
+
+    fn excluded_lines(&self) -> &ExcludedLines {
+        &self.excluded_lines
+    }
+
     pub fn add_file_dependency(&self, path: PathBuf) {
         if let Ok(mut dependencies) = self.dependencies.try_borrow_mut() {
             log::trace!("add file dependency {}", path.display());
@@ -150,6 +188,20 @@ impl Context<'_, '_, '_> {
         }
     }
 
+    /// Records the file that the tokens of a range of lines were parsed from, so that
+    /// source maps of bundled files can refer to each inlined module.
+    pub(crate) fn add_source_range(&self, source_range: SourceRange) {
+        if let Ok(mut source_ranges) = self.source_ranges.try_borrow_mut() {
+            source_ranges.push(source_range);
+        } else {
+            log::warn!("unable to submit source range (internal error)");
+        }
+    }
+
+    pub(crate) fn take_source_ranges(&self) -> Vec<SourceRange> {
+        self.source_ranges.take()
+    }
+
     pub fn into_dependencies(self) -> impl Iterator<Item = PathBuf> {
         self.dependencies.into_inner().into_iter()
     }
@@ -189,6 +241,31 @@ pub trait Rule: RuleConfiguration + fmt::Debug {
     fn require_content(&self, _current_source: &Path, _current_block: &Block) -> Vec<PathBuf> {
         Vec::new()
     }
+
+    /// Return `true` if this rule needs the parser to preserve tokens (with their comments and
+    /// line numbers) to be applied.
+    fn require_tokens(&self) -> bool {
+        false
+    }
+
+    /// Return `true` if this rule reads the lines excluded by ignore directives from the context
+    /// (with [`Context::is_line_excluded`]). Otherwise, the excluded statements are hidden from
+    /// the rule while it is applied.
+    fn handles_excluded_lines(&self) -> bool {
+        false
+    }
+
+    /// Return `false` if this rule should not be applied to the file at the given path. The path
+    /// is relative to the location of the configuration file.
+    fn applies_to(&self, _path: &Path) -> bool {
+        true
+    }
+
+    /// Return `true` if this rule should also be applied to each file inlined by the bundler,
+    /// before the requires of the file are resolved.
+    fn applies_before_bundling(&self) -> bool {
+        false
+    }
 }
 
 pub trait RuleConfiguration {
@@ -208,6 +285,11 @@ pub trait RuleConfiguration {
 
 pub trait FlawlessRule {
     fn flawless_process(&self, block: &mut Block, context: &Context);
+
+    /// See [`Rule::handles_excluded_lines`].
+    fn handles_excluded_lines(&self) -> bool {
+        false
+    }
 }
 
 impl<T: FlawlessRule + RuleConfiguration + fmt::Debug> Rule for T {
@@ -215,6 +297,10 @@ impl<T: FlawlessRule + RuleConfiguration + fmt::Debug> Rule for T {
         self.flawless_process(block, context);
         Ok(())
     }
+
+    fn handles_excluded_lines(&self) -> bool {
+        FlawlessRule::handles_excluded_lines(self)
+    }
 }
 
 /// A function to get the default rule stack for darklua. All the rules here must preserve all the
@@ -242,18 +328,24 @@ pub fn get_all_rule_names() -> Vec<&'static str> {
     vec![
         APPEND_TEXT_COMMENT_RULE_NAME,
         COMPUTE_EXPRESSIONS_RULE_NAME,
+        CONDITIONAL_COMPILATION_RULE_NAME,
         CONVERT_INDEX_TO_FIELD_RULE_NAME,
         CONVERT_LOCAL_FUNCTION_TO_ASSIGN_RULE_NAME,
+        CONVERT_LUAU_NUMBER_RULE_NAME,
         CONVERT_REQUIRE_RULE_NAME,
         FILTER_AFTER_EARLY_RETURN_RULE_NAME,
         GROUP_LOCAL_ASSIGNMENT_RULE_NAME,
         INJECT_GLOBAL_VALUE_RULE_NAME,
+        INLINE_FUNCTIONS_RULE_NAME,
+        PROPAGATE_CONSTANTS_RULE_NAME,
         REMOVE_ASSERTIONS_RULE_NAME,
         REMOVE_COMMENTS_RULE_NAME,
         REMOVE_COMPOUND_ASSIGNMENT_RULE_NAME,
         REMOVE_DEBUG_PROFILING_RULE_NAME,
         REMOVE_EMPTY_DO_RULE_NAME,
         REMOVE_FUNCTION_CALL_PARENS_RULE_NAME,
+        REMOVE_FUNCTION_CALLS_RULE_NAME,
+        REMOVE_GENERALIZED_ITERATION_RULE_NAME,
         REMOVE_INTERPOLATED_STRING_RULE_NAME,
         REMOVE_METHOD_DEFINITION_RULE_NAME,
         REMOVE_NIL_DECLARATION_RULE_NAME,
@@ -275,14 +367,18 @@ impl FromStr for Box<dyn Rule> {
         let rule: Box<dyn Rule> = match string {
             APPEND_TEXT_COMMENT_RULE_NAME => Box::<AppendTextComment>::default(),
             COMPUTE_EXPRESSIONS_RULE_NAME => Box::<ComputeExpression>::default(),
+            CONDITIONAL_COMPILATION_RULE_NAME => Box::<ConditionalCompilation>::default(),
             CONVERT_INDEX_TO_FIELD_RULE_NAME => Box::<ConvertIndexToField>::default(),
             CONVERT_LOCAL_FUNCTION_TO_ASSIGN_RULE_NAME => {
                 Box::<ConvertLocalFunctionToAssign>::default()
             }
+            CONVERT_LUAU_NUMBER_RULE_NAME => Box::<ConvertLuauNumber>::default(),
             CONVERT_REQUIRE_RULE_NAME => Box::<ConvertRequire>::default(),
             FILTER_AFTER_EARLY_RETURN_RULE_NAME => Box::<FilterAfterEarlyReturn>::default(),
             GROUP_LOCAL_ASSIGNMENT_RULE_NAME => Box::<GroupLocalAssignment>::default(),
             INJECT_GLOBAL_VALUE_RULE_NAME => Box::<InjectGlobalValue>::default(),
+            INLINE_FUNCTIONS_RULE_NAME => Box::<InlineFunctions>::default(),
+            PROPAGATE_CONSTANTS_RULE_NAME => Box::<PropagateConstants>::default(),
             REMOVE_ASSERTIONS_RULE_NAME => Box::<RemoveAssertions>::default(),
             REMOVE_COMMENTS_RULE_NAME => Box::<RemoveComments>::default(),
             REMOVE_COMPOUND_ASSIGNMENT_RULE_NAME => Box::<RemoveCompoundAssignment>::default(),
@@ -290,6 +386,8 @@ impl FromStr for Box<dyn Rule> {
             REMOVE_EMPTY_DO_RULE_NAME => Box::<RemoveEmptyDo>::default(),
             REMOVE_FLOOR_DIVISION_RULE_NAME => Box::<RemoveFloorDivision>::default(),
             REMOVE_FUNCTION_CALL_PARENS_RULE_NAME => Box::<RemoveFunctionCallParens>::default(),
+            REMOVE_FUNCTION_CALLS_RULE_NAME => Box::<RemoveFunctionCalls>::default(),
+            REMOVE_GENERALIZED_ITERATION_RULE_NAME => Box::<RemoveGeneralizedIteration>::default(),
             REMOVE_INTERPOLATED_STRING_RULE_NAME => Box::<RemoveInterpolatedString>::default(),
             REMOVE_METHOD_DEFINITION_RULE_NAME => Box::<RemoveMethodDefinition>::default(),
             REMOVE_NIL_DECLARATION_RULE_NAME => Box::<RemoveNilDeclaration>::default(),
@@ -390,9 +488,12 @@ impl<'de> Deserialize<'de> for Box<dyn Rule> {
                     let mut rule: Self::Value =
                         FromStr::from_str(&rule_name).map_err(de::Error::custom)?;
 
+                    let path_filter =
+                        PathFilter::from_properties(&mut properties).map_err(de::Error::custom)?;
+
                     rule.configure(properties).map_err(de::Error::custom)?;
 
-                    Ok(rule)
+                    Ok(PathFilteredRule::wrap(rule, path_filter))
                 } else {
                     Err(de::Error::missing_field("rule"))
                 }
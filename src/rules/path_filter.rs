@@ -0,0 +1,258 @@
+use std::path::{Path, PathBuf};
+
+use wax::Pattern;
+
+use crate::nodes::Block;
+
+use super::{
+    Context, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult, RuleProperties,
+    RulePropertyValue,
+};
+
+const INCLUDE_PROPERTY: &str = "include";
+const EXCLUDE_PROPERTY: &str = "exclude";
+
+#[derive(Debug)]
+struct GlobList {
+    patterns: Vec<String>,
+    matcher: wax::Any<'static>,
+}
+
+impl GlobList {
+    fn from_property(
+        value: RulePropertyValue,
+        key: &str,
+    ) -> Result<Option<Self>, RuleConfigurationError> {
+        let patterns = value.expect_string_list(key)?;
+
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        let globs = patterns
+            .iter()
+            .map(|pattern| {
+                wax::Glob::new(pattern)
+                    .map(wax::Glob::into_owned)
+                    .map_err(|err| RuleConfigurationError::UnexpectedValue {
+                        property: key.to_owned(),
+                        message: format!("invalid glob provided `{}`\n  {}", pattern, err),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let matcher = wax::any::<wax::Glob, _>(globs).map_err(|err| {
+            RuleConfigurationError::UnexpectedValue {
+                property: key.to_owned(),
+                message: err.to_string(),
+            }
+        })?;
+
+        Ok(Some(Self { patterns, matcher }))
+    }
+
+    fn is_match(&self, path: &Path) -> bool {
+        self.matcher.is_match(path)
+    }
+}
+
+/// The `include` and `exclude` glob patterns that restrict the files where a rule is applied.
+#[derive(Debug, Default)]
+pub(crate) struct PathFilter {
+    include: Option<GlobList>,
+    exclude: Option<GlobList>,
+}
+
+impl PathFilter {
+    /// Removes the `include` and `exclude` properties from the given properties to
+    /// build the filter.
+    pub(crate) fn from_properties(
+        properties: &mut RuleProperties,
+    ) -> Result<Self, RuleConfigurationError> {
+        let include = properties
+            .remove(INCLUDE_PROPERTY)
+            .map(|value| GlobList::from_property(value, INCLUDE_PROPERTY))
+            .transpose()?
+            .flatten();
+        let exclude = properties
+            .remove(EXCLUDE_PROPERTY)
+            .map(|value| GlobList::from_property(value, EXCLUDE_PROPERTY))
+            .transpose()?
+            .flatten();
+
+        Ok(Self { include, exclude })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none()
+    }
+
+    pub(crate) fn is_match(&self, path: &Path) -> bool {
+        self.include
+            .as_ref()
+            .map(|include| include.is_match(path))
+            .unwrap_or(true)
+            && !self
+                .exclude
+                .as_ref()
+                .map(|exclude| exclude.is_match(path))
+                .unwrap_or(false)
+    }
+
+    fn serialize_into(&self, properties: &mut RuleProperties) {
+        for (key, globs) in [
+            (INCLUDE_PROPERTY, &self.include),
+            (EXCLUDE_PROPERTY, &self.exclude),
+        ]
+        .iter()
+        {
+            if let Some(globs) = globs {
+                properties.insert(
+                    key.to_string(),
+                    RulePropertyValue::StringList(globs.patterns.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// A rule wrapper that only applies the inner rule to the files that match its path filter.
+#[derive(Debug)]
+pub(crate) struct PathFilteredRule {
+    rule: Box<dyn Rule>,
+    filter: PathFilter,
+}
+
+impl PathFilteredRule {
+    /// Wraps the rule if the filter is not empty, otherwise returns the rule unchanged.
+    pub(crate) fn wrap(rule: Box<dyn Rule>, filter: PathFilter) -> Box<dyn Rule> {
+        if filter.is_empty() {
+            rule
+        } else {
+            Box::new(Self { rule, filter })
+        }
+    }
+}
+
+impl RuleConfiguration for PathFilteredRule {
+    fn configure(&mut self, mut properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        self.filter = PathFilter::from_properties(&mut properties)?;
+        self.rule.configure(properties)
+    }
+
+    fn get_name(&self) -> &'static str {
+        self.rule.get_name()
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = self.rule.serialize_to_properties();
+        self.filter.serialize_into(&mut properties);
+        properties
+    }
+}
+
+impl Rule for PathFilteredRule {
+    fn process(&self, block: &mut Block, context: &Context) -> RuleProcessResult {
+        self.rule.process(block, context)
+    }
+
+    fn require_content(&self, current_source: &Path, current_block: &Block) -> Vec<PathBuf> {
+        self.rule.require_content(current_source, current_block)
+    }
+
+    fn require_tokens(&self) -> bool {
+        self.rule.require_tokens()
+    }
+
+    fn handles_excluded_lines(&self) -> bool {
+        self.rule.handles_excluded_lines()
+    }
+
+    fn applies_to(&self, path: &Path) -> bool {
+        self.filter.is_match(path) && self.rule.applies_to(path)
+    }
+
+    fn applies_before_bundling(&self) -> bool {
+        self.rule.applies_before_bundling()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::{RemoveTypes, RenameVariables};
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule(json: &str) -> Box<dyn Rule> {
+        json5::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn rule_without_filter_is_not_wrapped() {
+        let rule = new_rule("{ rule: 'remove_types' }");
+
+        assert!(rule.applies_to(Path::new("vendor/module.lua")));
+        assert_eq!(
+            format!("{:?}", rule),
+            format!("{:?}", RemoveTypes::default())
+        );
+    }
+
+    #[test]
+    fn include_matches_path() {
+        let rule = new_rule("{ rule: 'remove_types', include: ['src/**'] }");
+
+        assert!(rule.applies_to(Path::new("src/main.lua")));
+        assert!(rule.applies_to(Path::new("src/nested/module.lua")));
+        assert!(!rule.applies_to(Path::new("vendor/module.lua")));
+    }
+
+    #[test]
+    fn exclude_matches_path() {
+        let rule = new_rule("{ rule: 'rename_variables', exclude: ['vendor/**'] }");
+
+        assert!(rule.applies_to(Path::new("src/main.lua")));
+        assert!(!rule.applies_to(Path::new("vendor/module.lua")));
+    }
+
+    #[test]
+    fn exclude_has_priority_over_include() {
+        let rule = new_rule(
+            "{ rule: 'remove_types', include: ['src/**'], exclude: ['src/generated/**'] }",
+        );
+
+        assert!(rule.applies_to(Path::new("src/main.lua")));
+        assert!(!rule.applies_to(Path::new("src/generated/module.lua")));
+    }
+
+    #[test]
+    fn forwards_properties_to_rule() {
+        let rule = new_rule(
+            "{ rule: 'rename_variables', include_functions: true, exclude: ['vendor/**'] }",
+        );
+
+        assert_eq!(rule.get_name(), RenameVariables::default().get_name());
+        assert_json_snapshot!("path_filtered_rename_variables", rule);
+    }
+
+    #[test]
+    fn configure_with_invalid_glob_errors() {
+        let result: Result<Box<dyn Rule>, _> =
+            json5::from_str("{ rule: 'remove_types', include: ['src/**/**{'] }");
+
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'include': invalid glob provided `src/**/**{`\n  \
+            failed to parse glob expression"
+        );
+    }
+
+    #[test]
+    fn configure_with_string_instead_of_list_errors() {
+        let result: Result<Box<dyn Rule>, _> =
+            json5::from_str("{ rule: 'remove_types', include: 'src/**' }");
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use std::ops;
+
+use crate::nodes::{
+    AssignStatement, Block, CompoundAssignStatement, Expression, FunctionStatement,
+    LocalFunctionStatement, ParentheseExpression, Prefix, Variable,
+};
+use crate::process::{
+    Evaluator, NodePostProcessor, NodePostVisitor, NodeProcessor, NodeVisitor, Scope,
+    ScopePostVisitor, ScopeVisitor, VariableTracker,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::compute_expression::Computer;
+use super::verify_no_rule_properties;
+
+#[derive(Debug, Default)]
+struct ConstantCollector {
+    variables: VariableTracker,
+    evaluator: Evaluator,
+    values: Vec<Option<Expression>>,
+}
+
+impl ConstantCollector {
+    fn declare(&mut self, identifier: &str, value: Option<Expression>) {
+        let index = self.variables.declare(identifier);
+        debug_assert_eq!(index, self.values.len());
+        self.values.push(value);
+    }
+
+    fn mark_reassigned(&mut self, identifier: &str) {
+        if let Some(index) = self.variables.resolve(identifier) {
+            self.values[index] = None;
+        }
+    }
+
+    fn mark_reassigned_variable(&mut self, variable: &Variable) {
+        if let Variable::Identifier(identifier) = variable {
+            self.mark_reassigned(identifier.get_name());
+        }
+    }
+
+    fn into_constants(self) -> Vec<Option<Expression>> {
+        self.values
+    }
+}
+
+impl Scope for ConstantCollector {
+    fn push(&mut self) {
+        self.variables.push();
+    }
+
+    fn pop(&mut self) {
+        self.variables.pop();
+    }
+
+    fn insert(&mut self, identifier: &mut String) {
+        self.declare(identifier, None);
+    }
+
+    fn insert_self(&mut self) {
+        self.declare("self", None);
+    }
+
+    fn insert_local(&mut self, identifier: &mut String, value: Option<&mut Expression>) {
+        let constant = value.and_then(|value| {
+            if self.evaluator.has_side_effects(value) {
+                None
+            } else {
+                self.evaluator.evaluate(value).to_expression()
+            }
+        });
+        self.declare(identifier, constant);
+    }
+
+    fn insert_local_function(&mut self, function: &mut LocalFunctionStatement) {
+        self.declare(function.get_name(), None);
+    }
+}
+
+impl NodeProcessor for ConstantCollector {
+    fn process_assign_statement(&mut self, assign: &mut AssignStatement) {
+        for variable in assign.get_variables() {
+            self.mark_reassigned_variable(variable);
+        }
+    }
+
+    fn process_compound_assign_statement(&mut self, assign: &mut CompoundAssignStatement) {
+        self.mark_reassigned_variable(assign.get_variable());
+    }
+
+    fn process_function_statement(&mut self, function: &mut FunctionStatement) {
+        let name = function.get_name();
+        if name.get_field_names().is_empty() && !name.has_method() {
+            self.mark_reassigned(name.get_name().get_name());
+        }
+    }
+}
+
+struct ConstantPropagator {
+    variables: VariableTracker,
+    constants: Vec<Option<Expression>>,
+    computer: Computer,
+    inlined_count: usize,
+    expression_stack: Vec<usize>,
+}
+
+impl ConstantPropagator {
+    fn new(constants: Vec<Option<Expression>>) -> Self {
+        Self {
+            variables: VariableTracker::default(),
+            constants,
+            computer: Computer::default(),
+            inlined_count: 0,
+            expression_stack: Vec::new(),
+        }
+    }
+
+    fn find_constant(&mut self, identifier: &str) -> Option<Expression> {
+        let constant = self
+            .variables
+            .resolve(identifier)
+            .and_then(|index| self.constants.get(index))
+            .and_then(Option::as_ref)
+            .cloned();
+
+        if constant.is_some() {
+            self.inlined_count += 1;
+        }
+
+        constant
+    }
+
+    fn has_inlined(&self) -> bool {
+        self.inlined_count != 0
+    }
+}
+
+impl ops::Deref for ConstantPropagator {
+    type Target = VariableTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.variables
+    }
+}
+
+impl ops::DerefMut for ConstantPropagator {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.variables
+    }
+}
+
+impl NodeProcessor for ConstantPropagator {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        self.expression_stack.push(self.inlined_count);
+
+        if let Expression::Identifier(identifier) = expression {
+            if let Some(constant) = self.find_constant(identifier.get_name()) {
+                *expression = constant;
+            }
+        }
+    }
+
+    fn process_prefix_expression(&mut self, prefix: &mut Prefix) {
+        if let Prefix::Identifier(identifier) = prefix {
+            if let Some(constant) = self.find_constant(identifier.get_name()) {
+                *prefix = ParentheseExpression::new(constant).into();
+            }
+        }
+    }
+}
+
+impl NodePostProcessor for ConstantPropagator {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        let inlined_count = self
+            .expression_stack
+            .pop()
+            .expect("expression stack should not be empty");
+
+        // only fold expressions where a constant was inlined, so that
+        // the rest of the code is left untouched
+        if inlined_count != self.inlined_count {
+            if let Some(replace_with) = self.computer.replace_with(expression) {
+                *expression = replace_with;
+            }
+        }
+    }
+}
+
+pub const PROPAGATE_CONSTANTS_RULE_NAME: &str = "propagate_constants";
+
+/// A rule that replaces local variables assigned to a constant value (and never
+/// reassigned) with their value.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PropagateConstants {}
+
+impl FlawlessRule for PropagateConstants {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        loop {
+            let mut collector = ConstantCollector::default();
+            ScopeVisitor::visit_block(block, &mut collector);
+
+            let constants = collector.into_constants();
+            if constants.iter().all(Option::is_none) {
+                break;
+            }
+
+            let mut propagator = ConstantPropagator::new(constants);
+            ScopePostVisitor::visit_block(block, &mut propagator);
+
+            if !propagator.has_inlined() {
+                break;
+            }
+        }
+    }
+}
+
+impl RuleConfiguration for PropagateConstants {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        PROPAGATE_CONSTANTS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> PropagateConstants {
+        PropagateConstants::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_propagate_constants", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'propagate_constants',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
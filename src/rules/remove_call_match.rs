@@ -11,6 +11,15 @@ use crate::utils::{expressions_as_expression, expressions_as_statement};
 pub(crate) trait CallMatch<T> {
     fn matches(&self, identifiers: &IdentifierTracker, prefix: &Prefix) -> bool;
 
+    fn matches_method(
+        &self,
+        _identifiers: &IdentifierTracker,
+        _prefix: &Prefix,
+        _method: &Identifier,
+    ) -> bool {
+        false
+    }
+
     fn compute_result(
         &self,
         _call: &FunctionCall,
@@ -126,6 +135,16 @@ impl<Args, T: CallMatch<Args>> RemoveFunctionCallProcessor<Args, T> {
         }
     }
 
+    fn matches_call(&self, call: &FunctionCall) -> bool {
+        if let Some(method) = call.get_method() {
+            self.matcher
+                .matches_method(&self.identifier_tracker, call.get_prefix(), method)
+        } else {
+            self.matcher
+                .matches(&self.identifier_tracker, call.get_prefix())
+        }
+    }
+
     fn get_reserved_global(&mut self) -> String {
         self.global_counter += 1;
         format!("__DARKLUA_REMOVE_CALL_RESERVED_{}", self.global_counter)
@@ -149,11 +168,7 @@ impl<Args, T: CallMatch<Args>> ops::DerefMut for RemoveFunctionCallProcessor<Arg
 impl<Args, T: CallMatch<Args>> NodeProcessor for RemoveFunctionCallProcessor<Args, T> {
     fn process_statement(&mut self, statement: &mut Statement) {
         if let Statement::Call(call) = statement {
-            if call.get_method().is_none()
-                && self
-                    .matcher
-                    .matches(&self.identifier_tracker, call.get_prefix())
-            {
+            if self.matches_call(call) {
                 *statement = if self.preserve_args_side_effects {
                     expressions_as_statement(self.preserve_side_effects(call.get_arguments()))
                 } else {
@@ -165,11 +180,7 @@ impl<Args, T: CallMatch<Args>> NodeProcessor for RemoveFunctionCallProcessor<Arg
 
     fn process_expression(&mut self, expression: &mut Expression) {
         if let Expression::Call(call) = expression {
-            if call.get_method().is_none()
-                && self
-                    .matcher
-                    .matches(&self.identifier_tracker, call.get_prefix())
-            {
+            if self.matches_call(call) {
                 let insert_globals = self
                     .matcher
                     .reserve_globals()
@@ -70,6 +70,14 @@ impl NodeProcessor for RemoveCommentProcessor {
         repeat.clear_comments();
     }
 
+    fn process_goto_statement(&mut self, goto: &mut GotoStatement) {
+        goto.clear_comments();
+    }
+
+    fn process_label_statement(&mut self, label: &mut LabelStatement) {
+        label.clear_comments();
+    }
+
     fn process_while_statement(&mut self, statement: &mut WhileStatement) {
         statement.clear_comments();
     }
@@ -230,96 +238,92 @@ impl NodeProcessor for RemoveCommentProcessor {
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct FilterCommentProcessor<'a> {
-    original_code: &'a str,
-    except: &'a Vec<Regex>,
+/// A processor that keeps only the comments for which the filter returns `true`.
+pub(crate) struct FilterCommentProcessor<F> {
+    filter: F,
 }
 
-impl<'a> FilterCommentProcessor<'a> {
-    pub(crate) fn new(original_code: &'a str, except: &'a Vec<Regex>) -> Self {
-        Self {
-            original_code,
-            except,
-        }
-    }
-
-    fn ignore_trivia(&self, trivia: &Trivia) -> bool {
-        let content = trivia.read(self.original_code);
-        self.except.iter().any(|pattern| pattern.is_match(content))
+impl<F: Fn(&Trivia) -> bool> FilterCommentProcessor<F> {
+    pub(crate) fn new(filter: F) -> Self {
+        Self { filter }
     }
 }
 
-impl NodeProcessor for FilterCommentProcessor<'_> {
+impl<F: Fn(&Trivia) -> bool> NodeProcessor for FilterCommentProcessor<F> {
     fn process_block(&mut self, block: &mut Block) {
-        block.filter_comments(|trivia| self.ignore_trivia(trivia));
+        block.filter_comments(&self.filter);
     }
 
     fn process_function_call(&mut self, call: &mut FunctionCall) {
-        call.filter_comments(|trivia| self.ignore_trivia(trivia));
-        call.mutate_arguments()
-            .filter_comments(|trivia| self.ignore_trivia(trivia));
+        call.filter_comments(&self.filter);
+        call.mutate_arguments().filter_comments(&self.filter);
     }
 
     fn process_assign_statement(&mut self, assign: &mut AssignStatement) {
-        assign.filter_comments(|trivia| self.ignore_trivia(trivia));
+        assign.filter_comments(&self.filter);
     }
 
     fn process_compound_assign_statement(&mut self, assign: &mut CompoundAssignStatement) {
-        assign.filter_comments(|trivia| self.ignore_trivia(trivia));
+        assign.filter_comments(&self.filter);
     }
 
     fn process_do_statement(&mut self, statement: &mut DoStatement) {
-        statement.filter_comments(|trivia| self.ignore_trivia(trivia));
+        statement.filter_comments(&self.filter);
     }
 
     fn process_function_statement(&mut self, function: &mut FunctionStatement) {
-        function.filter_comments(|trivia| self.ignore_trivia(trivia));
+        function.filter_comments(&self.filter);
     }
 
     fn process_generic_for_statement(&mut self, generic_for: &mut GenericForStatement) {
-        generic_for.filter_comments(|trivia| self.ignore_trivia(trivia));
+        generic_for.filter_comments(&self.filter);
     }
 
     fn process_if_statement(&mut self, if_statement: &mut IfStatement) {
-        if_statement.filter_comments(|trivia| self.ignore_trivia(trivia));
+        if_statement.filter_comments(&self.filter);
     }
 
     fn process_last_statement(&mut self, statement: &mut LastStatement) {
         match statement {
             LastStatement::Break(token) | LastStatement::Continue(token) => {
                 if let Some(token) = token {
-                    token.filter_comments(|trivia| self.ignore_trivia(trivia));
+                    token.filter_comments(&self.filter);
                 }
             }
-            LastStatement::Return(statement) => {
-                statement.filter_comments(|trivia| self.ignore_trivia(trivia))
-            }
+            LastStatement::Return(statement) => statement.filter_comments(&self.filter),
         }
     }
 
     fn process_local_assign_statement(&mut self, assign: &mut LocalAssignStatement) {
-        assign.filter_comments(|trivia| self.ignore_trivia(trivia));
+        assign.filter_comments(&self.filter);
     }
 
     fn process_local_function_statement(&mut self, function: &mut LocalFunctionStatement) {
-        function.filter_comments(|trivia| self.ignore_trivia(trivia));
+        function.filter_comments(&self.filter);
     }
 
     fn process_numeric_for_statement(&mut self, numeric_for: &mut NumericForStatement) {
-        numeric_for.filter_comments(|trivia| self.ignore_trivia(trivia));
+        numeric_for.filter_comments(&self.filter);
     }
 
     fn process_repeat_statement(&mut self, repeat: &mut RepeatStatement) {
-        repeat.filter_comments(|trivia| self.ignore_trivia(trivia));
+        repeat.filter_comments(&self.filter);
+    }
+
+    fn process_goto_statement(&mut self, goto: &mut GotoStatement) {
+        goto.filter_comments(&self.filter);
+    }
+
+    fn process_label_statement(&mut self, label: &mut LabelStatement) {
+        label.filter_comments(&self.filter);
     }
 
     fn process_while_statement(&mut self, statement: &mut WhileStatement) {
-        statement.filter_comments(|trivia| self.ignore_trivia(trivia));
+        statement.filter_comments(&self.filter);
     }
 
     fn process_type_declaration(&mut self, type_declaration: &mut TypeDeclarationStatement) {
-        type_declaration.filter_comments(|trivia| self.ignore_trivia(trivia));
+        type_declaration.filter_comments(&self.filter);
     }
 
     fn process_expression(&mut self, expression: &mut Expression) {
@@ -329,7 +333,7 @@ impl NodeProcessor for FilterCommentProcessor<'_> {
             | Expression::True(token)
             | Expression::VariableArguments(token) => {
                 if let Some(token) = token {
-                    token.filter_comments(|trivia| self.ignore_trivia(trivia))
+                    token.filter_comments(&self.filter)
                 }
             }
             Expression::Binary(_)
@@ -350,58 +354,58 @@ impl NodeProcessor for FilterCommentProcessor<'_> {
     }
 
     fn process_binary_expression(&mut self, binary: &mut BinaryExpression) {
-        binary.filter_comments(|trivia| self.ignore_trivia(trivia));
+        binary.filter_comments(&self.filter);
     }
 
     fn process_field_expression(&mut self, field: &mut FieldExpression) {
-        field.filter_comments(|trivia| self.ignore_trivia(trivia));
+        field.filter_comments(&self.filter);
     }
 
     fn process_function_expression(&mut self, function: &mut FunctionExpression) {
-        function.filter_comments(|trivia| self.ignore_trivia(trivia));
+        function.filter_comments(&self.filter);
     }
 
     fn process_if_expression(&mut self, if_expression: &mut IfExpression) {
-        if_expression.filter_comments(|trivia| self.ignore_trivia(trivia));
+        if_expression.filter_comments(&self.filter);
     }
 
     fn process_variable_expression(&mut self, identifier: &mut Identifier) {
-        identifier.filter_comments(|trivia| self.ignore_trivia(trivia));
+        identifier.filter_comments(&self.filter);
     }
 
     fn process_index_expression(&mut self, index: &mut IndexExpression) {
-        index.filter_comments(|trivia| self.ignore_trivia(trivia));
+        index.filter_comments(&self.filter);
     }
 
     fn process_number_expression(&mut self, number: &mut NumberExpression) {
-        number.filter_comments(|trivia| self.ignore_trivia(trivia));
+        number.filter_comments(&self.filter);
     }
 
     fn process_parenthese_expression(&mut self, expression: &mut ParentheseExpression) {
-        expression.filter_comments(|trivia| self.ignore_trivia(trivia));
+        expression.filter_comments(&self.filter);
     }
 
     fn process_string_expression(&mut self, string: &mut StringExpression) {
-        string.filter_comments(|trivia| self.ignore_trivia(trivia));
+        string.filter_comments(&self.filter);
     }
 
     fn process_table_expression(&mut self, table: &mut TableExpression) {
-        table.filter_comments(|trivia| self.ignore_trivia(trivia));
+        table.filter_comments(&self.filter);
     }
 
     fn process_unary_expression(&mut self, unary: &mut UnaryExpression) {
-        unary.filter_comments(|trivia| self.ignore_trivia(trivia));
+        unary.filter_comments(&self.filter);
     }
 
     fn process_interpolated_string_expression(
         &mut self,
         string: &mut InterpolatedStringExpression,
     ) {
-        string.filter_comments(|trivia| self.ignore_trivia(trivia));
+        string.filter_comments(&self.filter);
     }
 
     fn process_type_cast_expression(&mut self, type_cast: &mut TypeCastExpression) {
-        type_cast.filter_comments(|trivia| self.ignore_trivia(trivia));
+        type_cast.filter_comments(&self.filter);
     }
 
     fn process_prefix_expression(&mut self, _: &mut Prefix) {}
@@ -410,7 +414,7 @@ impl NodeProcessor for FilterCommentProcessor<'_> {
         match r#type {
             Type::True(token) | Type::False(token) | Type::Nil(token) => {
                 if let Some(token) = token {
-                    token.filter_comments(|trivia| self.ignore_trivia(trivia));
+                    token.filter_comments(&self.filter);
                 }
             }
             _ => {}
@@ -418,59 +422,59 @@ impl NodeProcessor for FilterCommentProcessor<'_> {
     }
 
     fn process_type_name(&mut self, type_name: &mut TypeName) {
-        type_name.filter_comments(|trivia| self.ignore_trivia(trivia));
+        type_name.filter_comments(&self.filter);
     }
 
     fn process_type_field(&mut self, type_field: &mut TypeField) {
-        type_field.filter_comments(|trivia| self.ignore_trivia(trivia));
+        type_field.filter_comments(&self.filter);
     }
 
     fn process_string_type(&mut self, string_type: &mut StringType) {
-        string_type.filter_comments(|trivia| self.ignore_trivia(trivia));
+        string_type.filter_comments(&self.filter);
     }
 
     fn process_array_type(&mut self, array: &mut ArrayType) {
-        array.filter_comments(|trivia| self.ignore_trivia(trivia));
+        array.filter_comments(&self.filter);
     }
 
     fn process_table_type(&mut self, table: &mut TableType) {
-        table.filter_comments(|trivia| self.ignore_trivia(trivia));
+        table.filter_comments(&self.filter);
     }
 
     fn process_expression_type(&mut self, expression_type: &mut ExpressionType) {
-        expression_type.filter_comments(|trivia| self.ignore_trivia(trivia));
+        expression_type.filter_comments(&self.filter);
     }
 
     fn process_parenthese_type(&mut self, parenthese_type: &mut ParentheseType) {
-        parenthese_type.filter_comments(|trivia| self.ignore_trivia(trivia));
+        parenthese_type.filter_comments(&self.filter);
     }
 
     fn process_function_type(&mut self, function_type: &mut FunctionType) {
-        function_type.filter_comments(|trivia| self.ignore_trivia(trivia));
+        function_type.filter_comments(&self.filter);
     }
 
     fn process_optional_type(&mut self, optional: &mut OptionalType) {
-        optional.filter_comments(|trivia| self.ignore_trivia(trivia));
+        optional.filter_comments(&self.filter);
     }
 
     fn process_intersection_type(&mut self, intersection: &mut IntersectionType) {
-        intersection.filter_comments(|trivia| self.ignore_trivia(trivia));
+        intersection.filter_comments(&self.filter);
     }
 
     fn process_union_type(&mut self, union: &mut UnionType) {
-        union.filter_comments(|trivia| self.ignore_trivia(trivia));
+        union.filter_comments(&self.filter);
     }
 
     fn process_type_pack(&mut self, type_pack: &mut TypePack) {
-        type_pack.filter_comments(|trivia| self.ignore_trivia(trivia));
+        type_pack.filter_comments(&self.filter);
     }
 
     fn process_generic_type_pack(&mut self, generic_type_pack: &mut GenericTypePack) {
-        generic_type_pack.filter_comments(|trivia| self.ignore_trivia(trivia));
+        generic_type_pack.filter_comments(&self.filter);
     }
 
     fn process_variadic_type_pack(&mut self, variadic_type_pack: &mut VariadicTypePack) {
-        variadic_type_pack.filter_comments(|trivia| self.ignore_trivia(trivia));
+        variadic_type_pack.filter_comments(&self.filter);
     }
 }
 
@@ -488,7 +492,11 @@ impl FlawlessRule for RemoveComments {
             let mut processor = RemoveCommentProcessor::default();
             DefaultVisitor::visit_block(block, &mut processor);
         } else {
-            let mut processor = FilterCommentProcessor::new(context.original_code(), &self.except);
+            let original_code = context.original_code();
+            let mut processor = FilterCommentProcessor::new(|trivia: &Trivia| {
+                let content = trivia.read(original_code);
+                self.except.iter().any(|pattern| pattern.is_match(content))
+            });
             DefaultVisitor::visit_block(block, &mut processor);
         }
     }
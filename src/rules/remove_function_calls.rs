@@ -0,0 +1,295 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::nodes::{Block, Identifier, Prefix};
+use crate::process::utils::is_valid_identifier;
+use crate::process::{IdentifierTracker, NodeVisitor, ScopeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyValue,
+};
+
+use super::remove_call_match::{CallMatch, RemoveFunctionCallProcessor};
+use super::verify_required_properties;
+
+/// A path to a function, like `print`, `Logger.debug` or `Debug:trace`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FunctionPath {
+    root: String,
+    fields: Vec<String>,
+    method: Option<String>,
+}
+
+impl FunctionPath {
+    fn matches_prefix(&self, identifiers: &IdentifierTracker, prefix: &Prefix) -> bool {
+        let mut current = prefix;
+
+        for field_name in self.fields.iter().rev() {
+            match current {
+                Prefix::Field(field) if field.get_field().get_name() == field_name => {
+                    current = field.get_prefix();
+                }
+                _ => return false,
+            }
+        }
+
+        match current {
+            Prefix::Identifier(identifier) => {
+                *identifier.get_name() == self.root && !identifiers.is_identifier_used(&self.root)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for FunctionPath {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let (path_without_method, method) = match path.split_once(':') {
+            Some((path_without_method, method)) => (path_without_method, Some(method)),
+            None => (path, None),
+        };
+
+        let mut names = path_without_method.split('.');
+        let root = names.next().unwrap_or_default();
+
+        for name in std::iter::once(root).chain(names.clone()).chain(method) {
+            if !is_valid_identifier(name) {
+                return Err(format!("invalid function path `{}`", path));
+            }
+        }
+
+        Ok(Self {
+            root: root.to_owned(),
+            fields: names.map(ToOwned::to_owned).collect(),
+            method: method.map(ToOwned::to_owned),
+        })
+    }
+}
+
+impl fmt::Display for FunctionPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root)?;
+        for field in &self.fields {
+            write!(f, ".{}", field)?;
+        }
+        if let Some(method) = &self.method {
+            write!(f, ":{}", method)?;
+        }
+        Ok(())
+    }
+}
+
+struct FunctionPathMatcher<'a> {
+    functions: &'a [FunctionPath],
+}
+
+impl CallMatch<()> for FunctionPathMatcher<'_> {
+    fn matches(&self, identifiers: &IdentifierTracker, prefix: &Prefix) -> bool {
+        self.functions.iter().any(|function| {
+            function.method.is_none() && function.matches_prefix(identifiers, prefix)
+        })
+    }
+
+    fn matches_method(
+        &self,
+        identifiers: &IdentifierTracker,
+        prefix: &Prefix,
+        method: &Identifier,
+    ) -> bool {
+        self.functions.iter().any(|function| {
+            function.method.as_deref() == Some(method.get_name())
+                && function.matches_prefix(identifiers, prefix)
+        })
+    }
+}
+
+pub const REMOVE_FUNCTION_CALLS_RULE_NAME: &str = "remove_function_calls";
+
+/// A rule that removes calls to a given list of functions.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RemoveFunctionCalls {
+    functions: Vec<FunctionPath>,
+    preserve_args_side_effects: bool,
+}
+
+impl Default for RemoveFunctionCalls {
+    fn default() -> Self {
+        Self {
+            functions: Vec::new(),
+            preserve_args_side_effects: true,
+        }
+    }
+}
+
+impl RemoveFunctionCalls {
+    /// Creates a rule that removes calls to the given functions. Each function is
+    /// given as a path, like `print`, `Logger.debug` or `Debug:trace`.
+    pub fn new<I: IntoIterator<Item = S>, S: AsRef<str>>(functions: I) -> Result<Self, String> {
+        Ok(Self {
+            functions: functions
+                .into_iter()
+                .map(|function| function.as_ref().parse())
+                .collect::<Result<_, _>>()?,
+            preserve_args_side_effects: true,
+        })
+    }
+
+    pub fn with_preserve_arguments_side_effects(mut self, preserve: bool) -> Self {
+        self.preserve_args_side_effects = preserve;
+        self
+    }
+}
+
+impl FlawlessRule for RemoveFunctionCalls {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        if self.functions.is_empty() {
+            return;
+        }
+
+        let mut processor = RemoveFunctionCallProcessor::new(
+            self.preserve_args_side_effects,
+            FunctionPathMatcher {
+                functions: &self.functions,
+            },
+        );
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for RemoveFunctionCalls {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_required_properties(&properties, &["functions"])?;
+
+        for (key, value) in properties {
+            match key.as_str() {
+                "functions" => {
+                    self.functions = value
+                        .expect_string_list(&key)?
+                        .iter()
+                        .map(|path| path.parse())
+                        .collect::<Result<_, _>>()
+                        .map_err(|message| RuleConfigurationError::UnexpectedValue {
+                            property: key.clone(),
+                            message,
+                        })?;
+                }
+                "preserve_arguments_side_effects" => {
+                    self.preserve_args_side_effects = value.expect_bool(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REMOVE_FUNCTION_CALLS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        properties.insert(
+            "functions".to_owned(),
+            RulePropertyValue::StringList(self.functions.iter().map(ToString::to_string).collect()),
+        );
+
+        if !self.preserve_args_side_effects {
+            properties.insert("preserve_arguments_side_effects".to_owned(), false.into());
+        }
+
+        properties
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> RemoveFunctionCalls {
+        RemoveFunctionCalls::new(["print", "Logger.debug", "Debug:trace"]).unwrap()
+    }
+
+    #[test]
+    fn serialize_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("remove_function_calls", rule);
+    }
+
+    #[test]
+    fn serialize_rule_without_side_effects() {
+        let rule: Box<dyn Rule> = Box::new(new_rule().with_preserve_arguments_side_effects(false));
+
+        assert_json_snapshot!("remove_function_calls_without_side_effects", rule);
+    }
+
+    #[test]
+    fn parse_function_path() {
+        pretty_assertions::assert_eq!(
+            "Logger.levels.debug".parse::<FunctionPath>(),
+            Ok(FunctionPath {
+                root: "Logger".to_owned(),
+                fields: vec!["levels".to_owned(), "debug".to_owned()],
+                method: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_function_path_with_method() {
+        pretty_assertions::assert_eq!(
+            "Debug:trace".parse::<FunctionPath>(),
+            Ok(FunctionPath {
+                root: "Debug".to_owned(),
+                fields: Vec::new(),
+                method: Some("trace".to_owned()),
+            })
+        );
+    }
+
+    #[test]
+    fn configure_without_functions_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_function_calls',
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "missing required field 'functions'"
+        );
+    }
+
+    #[test]
+    fn configure_with_invalid_function_path_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_function_calls',
+            functions: ['Logger..debug'],
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'functions': invalid function path `Logger..debug`"
+        );
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_function_calls',
+            functions: ['print'],
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
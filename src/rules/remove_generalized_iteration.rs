@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+use std::ops;
+
+use crate::nodes::{
+    BinaryExpression, BinaryOperator, Block, Expression, FieldExpression, FunctionCall,
+    FunctionStatement, GenericForStatement, Identifier, IfStatement, LocalAssignStatement,
+    LocalFunctionStatement, Prefix, ReturnStatement, StringExpression,
+};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodeProcessor, NodeVisitor, Scope, ScopeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+const ITERATOR_HELPER_PREFIX: &str = "__DARKLUA_ITER";
+const DEFAULT_NEXT_IDENTIFIER: &str = "next";
+
+/// Returns true when a generic for loop has a single expression that may be
+/// a value iterated with Luau generalized iteration (like a table).
+pub(crate) fn may_use_generalized_iteration(generic_for: &GenericForStatement) -> bool {
+    generic_for.expressions_len() == 1
+        && !matches!(
+            generic_for.iter_expressions().next(),
+            Some(Expression::Call(_)) | Some(Expression::VariableArguments(_))
+        )
+}
+
+/// Collects the name of every identifier declared or read in a block, to find a name for
+/// the iterator helper that does not shadow or get shadowed by any of them.
+#[derive(Default)]
+struct IdentifierCollector {
+    identifiers: HashSet<String>,
+}
+
+impl NodeProcessor for IdentifierCollector {
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        self.identifiers.insert(identifier.get_name().to_owned());
+    }
+
+    fn process_function_statement(&mut self, function: &mut FunctionStatement) {
+        self.identifiers
+            .insert(function.get_name().get_name().get_name().to_owned());
+    }
+}
+
+impl Scope for IdentifierCollector {
+    fn push(&mut self) {}
+
+    fn pop(&mut self) {}
+
+    fn insert(&mut self, identifier: &mut String) {
+        self.identifiers.insert(identifier.clone());
+    }
+
+    fn insert_self(&mut self) {
+        self.identifiers.insert("self".to_owned());
+    }
+
+    fn insert_local(&mut self, identifier: &mut String, _value: Option<&mut Expression>) {
+        self.identifiers.insert(identifier.clone());
+    }
+
+    fn insert_local_function(&mut self, function: &mut LocalFunctionStatement) {
+        self.identifiers
+            .insert(function.get_identifier().get_name().to_owned());
+    }
+}
+
+fn generate_iterator_helper_identifier(block: &mut Block) -> String {
+    let mut collector = IdentifierCollector::default();
+    ScopeVisitor::visit_block(block, &mut collector);
+
+    let mut identifier_tracker = IdentifierTracker::new();
+    for mut identifier in collector.identifiers {
+        identifier_tracker.insert(&mut identifier);
+    }
+
+    identifier_tracker.generate_identifier_with_prefix(ITERATOR_HELPER_PREFIX)
+}
+
+struct Processor {
+    evaluator: Evaluator,
+    identifier_tracker: IdentifierTracker,
+    iterator_helper_identifier: String,
+    define_iterator_helper: bool,
+}
+
+impl ops::Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl ops::DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl Processor {
+    fn new(iterator_helper_identifier: String) -> Self {
+        Self {
+            evaluator: Evaluator::default(),
+            identifier_tracker: IdentifierTracker::new(),
+            iterator_helper_identifier,
+            define_iterator_helper: false,
+        }
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_generic_for_statement(&mut self, generic_for: &mut GenericForStatement) {
+        if !may_use_generalized_iteration(generic_for) {
+            return;
+        }
+
+        let expressions = generic_for.mutate_expressions();
+        let value = expressions
+            .pop()
+            .expect("generic for should have one expression");
+
+        match self.evaluator.evaluate(&value) {
+            LuaValue::Function => {
+                expressions.push(value);
+            }
+            // a table constructor can't have a metatable, so it can be
+            // iterated directly with `next`
+            LuaValue::Table if !self.is_identifier_used(DEFAULT_NEXT_IDENTIFIER) => {
+                expressions.push(Expression::identifier(DEFAULT_NEXT_IDENTIFIER));
+                expressions.push(value);
+            }
+            _ => {
+                self.define_iterator_helper = true;
+                expressions.push(
+                    FunctionCall::from_name(&self.iterator_helper_identifier)
+                        .with_argument(value)
+                        .into(),
+                );
+            }
+        }
+    }
+}
+
+fn type_equals(value: &str, type_name: &str) -> Expression {
+    BinaryExpression::new(
+        BinaryOperator::Equal,
+        FunctionCall::from_name("type").with_argument(Expression::identifier(value)),
+        StringExpression::from_value(type_name),
+    )
+    .into()
+}
+
+fn metatable_field(field: &str) -> FieldExpression {
+    FieldExpression::new(Prefix::from_name("metatable"), field)
+}
+
+// local function __DARKLUA_ITER(value)
+//     if type(value) == 'table' then
+//         local metatable = getmetatable(value)
+//         if type(metatable) == 'table' then
+//             if metatable.__iter then
+//                 return metatable.__iter(value)
+//             elseif metatable.__call then
+//                 return value
+//             end
+//         end
+//         return next, value
+//     end
+//     return value
+// end
+fn create_iterator_helper(identifier: &str) -> LocalFunctionStatement {
+    let metatable_block = Block::default().with_statement(
+        IfStatement::create(
+            metatable_field("__iter"),
+            ReturnStatement::one(
+                FunctionCall::from_prefix(metatable_field("__iter"))
+                    .with_argument(Expression::identifier("value")),
+            ),
+        )
+        .with_new_branch(
+            metatable_field("__call"),
+            ReturnStatement::one(Expression::identifier("value")),
+        ),
+    );
+
+    let table_block = Block::default()
+        .with_statement(LocalAssignStatement::from_variable("metatable").with_value(
+            FunctionCall::from_name("getmetatable").with_argument(Expression::identifier("value")),
+        ))
+        .with_statement(IfStatement::create(
+            type_equals("metatable", "table"),
+            metatable_block,
+        ))
+        .with_last_statement(
+            ReturnStatement::one(Expression::identifier(DEFAULT_NEXT_IDENTIFIER))
+                .with_expression(Expression::identifier("value")),
+        );
+
+    LocalFunctionStatement::from_name(
+        identifier,
+        Block::default()
+            .with_statement(IfStatement::create(
+                type_equals("value", "table"),
+                table_block,
+            ))
+            .with_last_statement(ReturnStatement::one(Identifier::new("value"))),
+    )
+    .with_parameter("value")
+}
+
+pub const REMOVE_GENERALIZED_ITERATION_RULE_NAME: &str = "remove_generalized_iteration";
+
+/// A rule that removes Luau generalized iteration (`for key, value in object do`).
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemoveGeneralizedIteration {}
+
+impl FlawlessRule for RemoveGeneralizedIteration {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let iterator_helper_identifier = generate_iterator_helper_identifier(block);
+
+        let mut processor = Processor::new(iterator_helper_identifier);
+        ScopeVisitor::visit_block(block, &mut processor);
+
+        if processor.define_iterator_helper {
+            block.insert_statement(
+                0,
+                create_iterator_helper(&processor.iterator_helper_identifier),
+            );
+        }
+    }
+}
+
+impl RuleConfiguration for RemoveGeneralizedIteration {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REMOVE_GENERALIZED_ITERATION_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> RemoveGeneralizedIteration {
+        RemoveGeneralizedIteration::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_remove_generalized_iteration", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_generalized_iteration',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
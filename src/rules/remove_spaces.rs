@@ -70,6 +70,14 @@ impl NodeProcessor for RemoveWhitespacesProcessor {
         repeat.clear_whitespaces();
     }
 
+    fn process_goto_statement(&mut self, goto: &mut GotoStatement) {
+        goto.clear_whitespaces();
+    }
+
+    fn process_label_statement(&mut self, label: &mut LabelStatement) {
+        label.clear_whitespaces();
+    }
+
     fn process_while_statement(&mut self, statement: &mut WhileStatement) {
         statement.clear_whitespaces();
     }
@@ -1,6 +1,7 @@
 use crate::nodes::*;
 use crate::process::processors::FindUsage;
 use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor, ScopeVisitor};
+use crate::rules::ignore_directives::ExcludedLines;
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
 };
@@ -8,25 +9,35 @@ use crate::utils::expressions_as_statement;
 
 use super::verify_no_rule_properties;
 
-#[derive(Default)]
-struct RemoveUnusedVariableProcessor {
+struct RemoveUnusedVariableProcessor<'a> {
     evaluator: Evaluator,
+    excluded_lines: &'a ExcludedLines,
     mutated: bool,
 }
 
-impl RemoveUnusedVariableProcessor {
+impl<'a> RemoveUnusedVariableProcessor<'a> {
+    fn new(excluded_lines: &'a ExcludedLines) -> Self {
+        Self {
+            evaluator: Evaluator::default(),
+            excluded_lines,
+            mutated: false,
+        }
+    }
+
     fn has_mutated(&self) -> bool {
         self.mutated
     }
 }
 
-impl NodeProcessor for RemoveUnusedVariableProcessor {
+impl NodeProcessor for RemoveUnusedVariableProcessor<'_> {
     fn process_scope(&mut self, block: &mut Block, extra: Option<&mut Expression>) {
         let length = block.statements_len();
 
         let assignments = block
             .reverse_iter_statements()
             .enumerate()
+            // declarations excluded by ignore directives are kept
+            .filter(|(_, statement)| !self.excluded_lines.contains_statement(statement))
             .filter_map(|(i, statement)| match statement {
                 Statement::LocalAssign(assignment) => {
                     let identifiers = assignment
@@ -35,13 +46,21 @@ impl NodeProcessor for RemoveUnusedVariableProcessor {
                         .map(TypedIdentifier::get_identifier)
                         .map(Identifier::get_name)
                         .map(ToOwned::to_owned)
+                        // variables with the `<close>` attribute are used when leaving
+                        // their scope
+                        .zip(
+                            assignment
+                                .iter_attributes()
+                                .map(|attribute| attribute.is_some_and(LocalAttribute::is_close)),
+                        )
                         .collect::<Vec<_>>();
 
                     Some((length - i - 1, identifiers))
                 }
-                Statement::LocalFunction(function) => {
-                    Some((length - i - 1, vec![function.get_name().to_owned()]))
-                }
+                Statement::LocalFunction(function) => Some((
+                    length - i - 1,
+                    vec![(function.get_name().to_owned(), false)],
+                )),
                 _ => None,
             })
             .collect::<Vec<_>>();
@@ -49,7 +68,7 @@ impl NodeProcessor for RemoveUnusedVariableProcessor {
         let usages_in_extra = if let Some(expression) = extra {
             let mut found_identifiers = Vec::new();
             for (_, identifiers) in assignments.iter() {
-                for identifier in identifiers {
+                for (identifier, _) in identifiers {
                     let mut find_usage = FindUsage::new(identifier);
                     ScopeVisitor::visit_expression(expression, &mut find_usage);
                     if find_usage.has_found_usage() {
@@ -67,7 +86,11 @@ impl NodeProcessor for RemoveUnusedVariableProcessor {
             .map(|(index, identifiers)| {
                 let usages = identifiers
                     .into_iter()
-                    .map(|identifier| {
+                    .map(|(identifier, is_closed)| {
+                        if is_closed {
+                            return true;
+                        }
+
                         let mut find_usage = FindUsage::new(&identifier);
 
                         block
@@ -121,38 +144,46 @@ impl NodeProcessor for RemoveUnusedVariableProcessor {
                                 true
                             }
                         } else if usages.iter().any(|used| !used) {
-                            let mut assignments: Vec<_> = assign
+                            let declarations: Vec<_> = assign
                                 .iter_variables()
-                                .zip(usages.iter())
-                                .map(|identifier| vec![identifier])
+                                .zip(usages.iter().copied())
+                                .zip(assign.iter_attributes())
+                                .map(|((identifier, used), attribute)| {
+                                    (identifier, used, attribute)
+                                })
+                                .collect();
+
+                            let mut assignments: Vec<_> = declarations
+                                .iter()
+                                .cloned()
+                                .map(|declaration| vec![declaration])
                                 .zip(assign.iter_values())
                                 .collect();
 
                             let length = assignments.len();
                             if let Some((last, value)) = assignments.last_mut() {
                                 if self.evaluator.can_return_multiple_values(value) {
-                                    last.extend(
-                                        assign.iter_variables().zip(usages.iter()).skip(length),
-                                    );
+                                    last.extend(declarations.iter().cloned().skip(length));
                                 }
                             }
 
                             let mut variables = Vec::new();
+                            let mut attributes = Vec::new();
                             let mut values = Vec::new();
 
                             for (mut identifiers, value) in assignments {
                                 if !self.evaluator.has_side_effects(value) {
-                                    while identifiers.last().filter(|(_, used)| !*used).is_some() {
+                                    while identifiers.last().filter(|(_, used, _)| !*used).is_some()
+                                    {
                                         identifiers.pop();
                                     }
                                 }
 
                                 if !identifiers.is_empty() {
-                                    variables.extend(
-                                        identifiers
-                                            .into_iter()
-                                            .map(|(identifier, _)| identifier.clone()),
-                                    );
+                                    for (identifier, _, attribute) in identifiers {
+                                        variables.push(identifier.clone());
+                                        attributes.push(attribute.cloned());
+                                    }
                                     values.push(value.clone());
                                 }
                             }
@@ -168,7 +199,13 @@ impl NodeProcessor for RemoveUnusedVariableProcessor {
                                 }
                             } else {
                                 values.extend(assign.iter_values().skip(length).cloned());
-                                *statement = LocalAssignStatement::new(variables, values).into();
+                                let mut new_assign = LocalAssignStatement::new(variables, values);
+                                for (index, attribute) in attributes.into_iter().enumerate() {
+                                    if let Some(attribute) = attribute {
+                                        new_assign.set_attribute(index, attribute);
+                                    }
+                                }
+                                *statement = new_assign.into();
                                 true
                             }
                         } else {
@@ -205,9 +242,9 @@ pub const REMOVE_UNUSED_VARIABLE_RULE_NAME: &str = "remove_unused_variable";
 pub struct RemoveUnusedVariable {}
 
 impl FlawlessRule for RemoveUnusedVariable {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
         loop {
-            let mut processor = RemoveUnusedVariableProcessor::default();
+            let mut processor = RemoveUnusedVariableProcessor::new(context.excluded_lines());
             processor.process_scope(block, None);
             DefaultVisitor::visit_block(block, &mut processor);
             if !processor.has_mutated() {
@@ -215,6 +252,10 @@ impl FlawlessRule for RemoveUnusedVariable {
             }
         }
     }
+
+    fn handles_excluded_lines(&self) -> bool {
+        true
+    }
 }
 
 impl RuleConfiguration for RemoveUnusedVariable {
@@ -260,4 +301,46 @@ mod test {
         );
         pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
     }
+
+    fn process_lua54(code: &str) -> String {
+        use crate::generator::{DenseLuaGenerator, LuaGenerator};
+        use crate::{LuaVersion, Parser, Resources};
+
+        let mut block = Parser::default()
+            .with_lua_version(LuaVersion::Lua54)
+            .parse(code)
+            .unwrap();
+        let resources = Resources::from_memory();
+        let context = crate::rules::ContextBuilder::new("test.lua", &resources, code).build();
+
+        new_rule().flawless_process(&mut block, &context);
+
+        let mut generator = DenseLuaGenerator::default();
+        generator.write_block(&block);
+        generator.into_string()
+    }
+
+    #[test]
+    fn keep_unused_close_variable() {
+        pretty_assertions::assert_eq!(
+            process_lua54("local f <close> = open()"),
+            "local f<close> =open()"
+        );
+    }
+
+    #[test]
+    fn keep_close_variable_and_remove_unused_variable() {
+        pretty_assertions::assert_eq!(
+            process_lua54("local a, f <close> = true, open()"),
+            "local f<close> =open()"
+        );
+    }
+
+    #[test]
+    fn keep_attribute_of_used_variable() {
+        pretty_assertions::assert_eq!(
+            process_lua54("local a <const>, b = true, false return a"),
+            "local a<const> =true return a"
+        );
+    }
 }
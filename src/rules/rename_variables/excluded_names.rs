@@ -0,0 +1,60 @@
+use crate::nodes::{Identifier, LocalAssignStatement, LocalFunctionStatement, TypedIdentifier};
+use crate::process::NodeProcessor;
+use crate::rules::ignore_directives::ExcludedLines;
+
+/// Collects the names of the local variables declared on lines excluded by
+/// ignore directives.
+#[derive(Debug)]
+pub(crate) struct CollectExcludedNames<'a> {
+    excluded_lines: &'a ExcludedLines,
+    names: Vec<String>,
+}
+
+impl<'a> CollectExcludedNames<'a> {
+    pub(crate) fn new(excluded_lines: &'a ExcludedLines) -> Self {
+        Self {
+            excluded_lines,
+            names: Vec::new(),
+        }
+    }
+
+    fn is_excluded(&self, line: Option<usize>) -> bool {
+        line.map(|line| self.excluded_lines.contains(line))
+            .unwrap_or(false)
+    }
+}
+
+impl From<CollectExcludedNames<'_>> for Vec<String> {
+    fn from(collector: CollectExcludedNames) -> Self {
+        collector.names
+    }
+}
+
+impl NodeProcessor for CollectExcludedNames<'_> {
+    fn process_local_assign_statement(&mut self, assign: &mut LocalAssignStatement) {
+        let line = assign
+            .get_tokens()
+            .and_then(|tokens| tokens.local.get_line_number());
+
+        if self.is_excluded(line) {
+            self.names.extend(
+                assign
+                    .iter_variables()
+                    .map(TypedIdentifier::get_identifier)
+                    .map(Identifier::get_name)
+                    .cloned(),
+            );
+        }
+    }
+
+    fn process_local_function_statement(&mut self, function: &mut LocalFunctionStatement) {
+        let line = function
+            .get_tokens()
+            .and_then(|tokens| tokens.local.get_line_number());
+
+        if self.is_excluded(line) {
+            self.names
+                .push(function.get_identifier().get_name().to_owned());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod excluded_names;
 mod function_names;
 mod globals;
 mod rename_processor;
@@ -94,7 +95,7 @@ impl Default for RenameVariables {
 }
 
 impl FlawlessRule for RenameVariables {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
         let avoid_identifiers = if self.include_functions {
             Vec::new()
         } else {
@@ -103,12 +104,26 @@ impl FlawlessRule for RenameVariables {
             collect_functions.into()
         };
 
+        let kept_identifiers = if context.excluded_lines().is_empty() {
+            Vec::new()
+        } else {
+            let mut collect_excluded =
+                excluded_names::CollectExcludedNames::new(context.excluded_lines());
+            DefaultVisitor::visit_block(block, &mut collect_excluded);
+            collect_excluded.into()
+        };
+
         let mut processor = RenameProcessor::new(
             self.globals.clone().into_iter().chain(avoid_identifiers),
             self.include_functions,
-        );
+        )
+        .with_kept_identifiers(kept_identifiers);
         ScopeVisitor::visit_block(block, &mut processor);
     }
+
+    fn handles_excluded_lines(&self) -> bool {
+        true
+    }
 }
 
 impl RuleConfiguration for RenameVariables {
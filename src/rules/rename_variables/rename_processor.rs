@@ -12,6 +12,7 @@ pub struct RenameProcessor {
     real_to_obfuscated: Vec<HashMap<String, (String, bool)>>,
     permutator: CharPermutator,
     avoid_identifier: HashSet<String>,
+    keep_identifiers: HashSet<String>,
     reuse_identifiers: Vec<String>,
     include_functions: bool,
 }
@@ -25,11 +26,21 @@ impl RenameProcessor {
             real_to_obfuscated: Vec::new(),
             permutator: identifier_permutator(),
             avoid_identifier,
+            keep_identifiers: HashSet::new(),
             reuse_identifiers: Vec::new(),
             include_functions,
         }
     }
 
+    /// Variables declared with one of these names are not renamed.
+    pub fn with_kept_identifiers<I: IntoIterator<Item = String>>(mut self, iter: I) -> Self {
+        for identifier in iter {
+            self.avoid_identifier.insert(identifier.clone());
+            self.keep_identifiers.insert(identifier);
+        }
+        self
+    }
+
     pub fn add(&mut self, real: String, obfuscated: String, reuse: bool) {
         if let Some(dictionary) = self.real_to_obfuscated.last_mut() {
             dictionary.insert(real, (obfuscated, reuse));
@@ -67,6 +78,11 @@ impl RenameProcessor {
     }
 
     fn replace_identifier(&mut self, identifier: &mut String) {
+        if self.keep_identifiers.contains(identifier.as_str()) {
+            self.add(identifier.clone(), identifier.clone(), false);
+            return;
+        }
+
         let original = mem::take(identifier);
         let obfuscated_name = self.generate_identifier();
 
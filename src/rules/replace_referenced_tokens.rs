@@ -78,6 +78,14 @@ impl NodeProcessor for Processor<'_> {
         repeat.replace_referenced_tokens(self.code);
     }
 
+    fn process_goto_statement(&mut self, goto: &mut GotoStatement) {
+        goto.replace_referenced_tokens(self.code);
+    }
+
+    fn process_label_statement(&mut self, label: &mut LabelStatement) {
+        label.replace_referenced_tokens(self.code);
+    }
+
     fn process_while_statement(&mut self, statement: &mut WhileStatement) {
         statement.replace_referenced_tokens(self.code);
     }
@@ -245,11 +253,16 @@ pub(crate) struct ReplaceReferencedTokens {}
 
 impl FlawlessRule for ReplaceReferencedTokens {
     fn flawless_process(&self, block: &mut Block, context: &Context) {
-        let mut processor = Processor::new(context.original_code());
-        DefaultVisitor::visit_block(block, &mut processor);
+        replace_referenced_tokens(block, context.original_code());
     }
 }
 
+/// Replaces the tokens that refer to the original code with tokens holding their content.
+pub(crate) fn replace_referenced_tokens(block: &mut Block, code: &str) {
+    let mut processor = Processor::new(code);
+    DefaultVisitor::visit_block(block, &mut processor);
+}
+
 impl RuleConfiguration for ReplaceReferencedTokens {
     fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
         verify_no_rule_properties(&properties)?;
@@ -1,4 +1,4 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use crate::{
     nodes::{Arguments, Expression, FunctionCall, Prefix},
@@ -23,6 +23,12 @@ pub(crate) fn is_require_call(call: &FunctionCall, identifier_tracker: &Identifi
 }
 
 pub(crate) fn match_path_require_call(call: &FunctionCall) -> Option<PathBuf> {
+    match_string_require_call(call)
+        .map(PathBuf::from)
+        .map(utils::normalize_path_with_current_dir)
+}
+
+pub(crate) fn match_string_require_call(call: &FunctionCall) -> Option<String> {
     match call.get_arguments() {
         Arguments::String(string) => Some(string.get_value()),
         Arguments::Tuple(tuple) if tuple.len() == 1 => {
@@ -35,6 +41,5 @@ pub(crate) fn match_path_require_call(call: &FunctionCall) -> Option<PathBuf> {
         }
         _ => None,
     }
-    .map(Path::new)
-    .map(utils::normalize_path_with_current_dir)
+    .map(ToOwned::to_owned)
 }
@@ -1,8 +1,12 @@
 mod match_require;
+mod module_require_mode;
 mod path_iterator;
 mod path_locator;
 mod path_require_mode;
 
-pub(crate) use match_require::{is_require_call, match_path_require_call};
+pub(crate) use match_require::{
+    is_require_call, match_path_require_call, match_string_require_call,
+};
+pub(crate) use module_require_mode::ModuleRequireMode;
 pub(crate) use path_locator::RequirePathLocator;
 pub(crate) use path_require_mode::PathRequireMode;
@@ -0,0 +1,266 @@
+use serde::{Deserialize, Serialize};
+
+use crate::frontend::DarkluaResult;
+use crate::nodes::{Arguments, FunctionCall, StringExpression};
+use crate::rules::require::match_string_require_call;
+use crate::rules::{Context, RequireMode};
+use crate::{utils, DarkluaError, Resources};
+
+use std::path::{Component, Path, PathBuf};
+
+use super::{PathRequireMode, RequirePathLocator};
+
+const MODULE_NAME_MARKER: char = '?';
+const DEFAULT_PACKAGE_PATH: [&str; 2] = ["./?.lua", "./?/init.lua"];
+
+/// A require mode for dotted module names (like `require("a.b.c")`) that are
+/// resolved using `package.path` templates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct ModuleRequireMode {
+    #[serde(
+        default = "get_default_package_path",
+        skip_serializing_if = "is_default_package_path"
+    )]
+    package_path: Vec<String>,
+}
+
+impl Default for ModuleRequireMode {
+    fn default() -> Self {
+        Self {
+            package_path: get_default_package_path(),
+        }
+    }
+}
+
+fn get_default_package_path() -> Vec<String> {
+    DEFAULT_PACKAGE_PATH
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn is_default_package_path(value: &[String]) -> bool {
+    value
+        .iter()
+        .map(String::as_str)
+        .eq(DEFAULT_PACKAGE_PATH.iter().copied())
+}
+
+impl ModuleRequireMode {
+    pub fn new(package_path: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            package_path: package_path.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub(crate) fn find_require(
+        &self,
+        call: &FunctionCall,
+        context: &Context,
+    ) -> DarkluaResult<Option<PathBuf>> {
+        if let Some(module_name) = match_string_require_call(call) {
+            let required_path = self.find_module_path(
+                &module_name,
+                context.project_location(),
+                context.resources(),
+            )?;
+
+            Ok(Some(required_path))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Finds the file of a module name by trying each template of the package path,
+    /// relative to the project location.
+    pub(crate) fn find_module_path(
+        &self,
+        module_name: &str,
+        project_location: &Path,
+        resources: &Resources,
+    ) -> DarkluaResult<PathBuf> {
+        log::trace!("find module path for `{}`", module_name);
+
+        let path_require_mode = PathRequireMode::default();
+        let locator = RequirePathLocator::new(&path_require_mode, project_location, resources);
+
+        let module_path = module_name.replace('.', "/");
+        let mut tried_paths = Vec::new();
+
+        for template in self.package_path.iter() {
+            let location = utils::normalize_path(
+                project_location.join(template.replace(MODULE_NAME_MARKER, &module_path)),
+            );
+
+            if let Some(path) = locator.find_existing_path(&location)? {
+                return Ok(path);
+            }
+
+            tried_paths.extend(
+                locator
+                    .potential_paths(&location)
+                    .map(|potential_path| potential_path.display().to_string()),
+            );
+        }
+
+        Err(
+            DarkluaError::custom(format!("unable to find module `{}`", module_name))
+                .context(format!("tried `{}`", tried_paths.join("`, `"))),
+        )
+    }
+
+    pub(crate) fn is_module_folder_name(&self, path: &Path) -> bool {
+        matches!(
+            path.file_stem().and_then(std::ffi::OsStr::to_str),
+            Some("init")
+        )
+    }
+
+    pub(crate) fn generate_require(
+        &self,
+        require_path: &Path,
+        _current_mode: &RequireMode,
+        context: &Context,
+    ) -> DarkluaResult<Option<Arguments>> {
+        let module_name = self
+            .get_module_name(require_path, context.project_location())
+            .ok_or_else(|| {
+                DarkluaError::custom(format!(
+                    "unable to find a module name for `{}` with the package path `{}`",
+                    require_path.display(),
+                    self.package_path.join(";"),
+                ))
+            })?;
+
+        log::trace!(
+            "generate module require `{}` for `{}`",
+            module_name,
+            require_path.display()
+        );
+
+        Ok(Some(
+            Arguments::default().with_argument(StringExpression::from_value(module_name)),
+        ))
+    }
+
+    /// Returns the shortest module name from the templates of the package path that
+    /// match the given file path (the first template wins when names have the same length).
+    fn get_module_name(&self, require_path: &Path, project_location: &Path) -> Option<String> {
+        let require_path = path_to_slash_string(require_path)?;
+
+        let module_names = self.package_path.iter().filter_map(|template| {
+            let template = path_to_slash_string(&project_location.join(template))?;
+            let (prefix, suffix) = template.split_once(MODULE_NAME_MARKER)?;
+
+            if suffix.contains(MODULE_NAME_MARKER) {
+                return None;
+            }
+
+            let module_path = require_path.strip_prefix(prefix)?.strip_suffix(suffix)?;
+
+            let parts: Vec<_> = module_path.split('/').collect();
+
+            if parts
+                .iter()
+                .all(|part| !part.is_empty() && !part.contains('.'))
+            {
+                Some(parts)
+            } else {
+                None
+            }
+        });
+
+        module_names
+            .enumerate()
+            .min_by_key(|(index, parts)| (parts.len(), *index))
+            .map(|(_, parts)| parts.join("."))
+    }
+}
+
+// converts a path to a string with `/` separators, without the `.` components
+fn path_to_slash_string(path: &Path) -> Option<String> {
+    let normalized_path = utils::normalize_path(path);
+    let components = normalized_path
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| match component {
+            Component::RootDir => Some(""),
+            _ => component.as_os_str().to_str(),
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(components.join("/"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod get_module_name {
+        use super::*;
+
+        fn get_module_name(require_mode: &ModuleRequireMode, path: &str) -> Option<String> {
+            require_mode.get_module_name(Path::new(path), Path::new("."))
+        }
+
+        #[test]
+        fn default_mode_with_file_at_root() {
+            let require_mode = ModuleRequireMode::default();
+
+            assert_eq!(
+                get_module_name(&require_mode, "module.lua"),
+                Some("module".to_owned())
+            );
+        }
+
+        #[test]
+        fn default_mode_with_nested_file() {
+            let require_mode = ModuleRequireMode::default();
+
+            assert_eq!(
+                get_module_name(&require_mode, "./lib/util/format.lua"),
+                Some("lib.util.format".to_owned())
+            );
+        }
+
+        #[test]
+        fn default_mode_with_init_file() {
+            let require_mode = ModuleRequireMode::default();
+
+            assert_eq!(
+                get_module_name(&require_mode, "lib/util/init.lua"),
+                Some("lib.util".to_owned())
+            );
+        }
+
+        #[test]
+        fn default_mode_with_luau_file_is_none() {
+            let require_mode = ModuleRequireMode::default();
+
+            assert_eq!(get_module_name(&require_mode, "lib/module.luau"), None);
+        }
+
+        #[test]
+        fn default_mode_with_dotted_file_name_is_none() {
+            let require_mode = ModuleRequireMode::default();
+
+            assert_eq!(get_module_name(&require_mode, "lib/module.spec.lua"), None);
+        }
+
+        #[test]
+        fn custom_package_path_with_prefix_folder() {
+            let require_mode = ModuleRequireMode::new(vec!["./src/?.luau", "./vendor/?/init.lua"]);
+
+            assert_eq!(
+                get_module_name(&require_mode, "src/app/main.luau"),
+                Some("app.main".to_owned())
+            );
+            assert_eq!(
+                get_module_name(&require_mode, "vendor/json/init.lua"),
+                Some("json".to_owned())
+            );
+            assert_eq!(get_module_name(&require_mode, "lib/other.luau"), None);
+        }
+    }
+}
@@ -69,27 +69,34 @@ impl<'a, 'b, 'c> RequirePathLocator<'a, 'b, 'c> {
         // else: the path is absolute so darklua should attempt to require it directly
 
         let normalized_path = utils::normalize_path_with_current_dir(&path);
-        for potential_path in path_iterator::find_require_paths(
-            &normalized_path,
-            self.path_require_mode.module_folder_name(),
-        ) {
+
+        self.find_existing_path(&normalized_path)?.ok_or_else(|| {
+            DarkluaError::resource_not_found(&normalized_path).context(format!(
+                "tried `{}`",
+                self.potential_paths(&normalized_path)
+                    .map(|potential_path| potential_path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join("`, `")
+            ))
+        })
+    }
+
+    /// Returns the first file that exists from the potential paths of the given path.
+    pub(crate) fn find_existing_path(&self, path: &Path) -> Result<Option<PathBuf>, DarkluaError> {
+        for potential_path in self.potential_paths(path) {
             if self.resources.is_file(&potential_path)? {
-                return Ok(utils::normalize_path_with_current_dir(potential_path));
+                return Ok(Some(utils::normalize_path_with_current_dir(potential_path)));
             }
         }
+        Ok(None)
+    }
 
-        Err(
-            DarkluaError::resource_not_found(&normalized_path).context(format!(
-                "tried `{}`",
-                path_iterator::find_require_paths(
-                    &normalized_path,
-                    self.path_require_mode.module_folder_name(),
-                )
-                .map(|potential_path| potential_path.display().to_string())
-                .collect::<Vec<_>>()
-                .join("`, `")
-            )),
-        )
+    /// Returns all the paths that a require to the given path could resolve to.
+    pub(crate) fn potential_paths<'p>(
+        &'p self,
+        path: &'p Path,
+    ) -> impl Iterator<Item = PathBuf> + 'p {
+        path_iterator::find_require_paths(path, self.path_require_mode.module_folder_name())
     }
 }
 
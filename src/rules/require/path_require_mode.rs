@@ -1,10 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use crate::frontend::DarkluaResult;
-use crate::nodes::FunctionCall;
+use crate::nodes::{Arguments, FunctionCall, StringExpression};
 use crate::rules::require::match_path_require_call;
-use crate::rules::Context;
-use crate::utils::find_luau_configuration;
+use crate::rules::{Context, RequireMode};
+use crate::utils::{self, find_luau_configuration};
 use crate::DarkluaError;
 
 use std::collections::HashMap;
@@ -121,12 +121,70 @@ impl PathRequireMode {
 
     pub(crate) fn generate_require(
         &self,
-        _path: &Path,
-        _current_mode: &crate::rules::RequireMode,
-        _context: &Context<'_, '_, '_>,
-    ) -> Result<Option<crate::nodes::Arguments>, crate::DarkluaError> {
-        Err(DarkluaError::custom("unsupported target require mode")
-            .context("path require mode cannot be used"))
+        require_path: &Path,
+        _current_mode: &RequireMode,
+        context: &Context,
+    ) -> DarkluaResult<Option<Arguments>> {
+        let source_path = utils::normalize_path(context.current_path());
+        let require_path = utils::normalize_path(require_path);
+        log::trace!(
+            "generate path require for `{}` from `{}`",
+            require_path.display(),
+            source_path.display(),
+        );
+
+        let module_path = if self.is_module_folder_name(&require_path) {
+            require_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default()
+        } else {
+            remove_lua_extension(&require_path)
+        };
+
+        let source_parent = source_path.parent().unwrap_or_else(|| Path::new(""));
+
+        let relative_path = pathdiff::diff_paths(&module_path, source_parent).ok_or_else(|| {
+            DarkluaError::custom(format!(
+                "unable to make path `{}` relative to `{}`",
+                require_path.display(),
+                source_path.display(),
+            ))
+        })?;
+
+        let mut components = relative_path
+            .components()
+            .map(|component| utils::convert_os_string(component.as_os_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if components.is_empty() {
+            // the required module is the folder containing the source file
+            components.push(&self.module_folder_name);
+        }
+
+        if components.first() != Some(&"..") {
+            components.insert(0, ".");
+        }
+
+        Ok(Some(Arguments::default().with_argument(
+            StringExpression::from_value(components.join("/")),
+        )))
+    }
+}
+
+// removes the `.lua` or `.luau` extension, unless the remaining path would have an extension
+// (in that case, the require would not find the file without its full name)
+fn remove_lua_extension(path: &Path) -> PathBuf {
+    match path.extension().and_then(OsStr::to_str) {
+        Some("lua") | Some("luau") => {
+            let without_extension = path.with_extension("");
+            if without_extension.extension().is_some() {
+                path.to_path_buf()
+            } else {
+                without_extension
+            }
+        }
+        _ => path.to_path_buf(),
     }
 }
 
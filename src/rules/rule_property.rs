@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use super::{require::PathRequireMode, RequireMode, RobloxRequireMode, RuleConfigurationError};
+use super::{
+    require::{ModuleRequireMode, PathRequireMode},
+    RequireMode, RobloxRequireMode, RuleConfigurationError,
+};
 
 pub type RuleProperties = HashMap<String, RulePropertyValue>;
 
@@ -133,6 +136,11 @@ impl From<&RequireMode> for RulePropertyValue {
                     return Self::from("roblox");
                 }
             }
+            RequireMode::Module(mode) => {
+                if mode == &ModuleRequireMode::default() {
+                    return Self::from("module");
+                }
+            }
         }
 
         Self::RequireMode(value.clone())
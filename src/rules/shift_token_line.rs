@@ -78,6 +78,14 @@ impl NodeProcessor for ShiftTokenLineProcessor {
         repeat.shift_token_line(self.shift_amount);
     }
 
+    fn process_goto_statement(&mut self, goto: &mut GotoStatement) {
+        goto.shift_token_line(self.shift_amount);
+    }
+
+    fn process_label_statement(&mut self, label: &mut LabelStatement) {
+        label.shift_token_line(self.shift_amount);
+    }
+
     fn process_while_statement(&mut self, statement: &mut WhileStatement) {
         statement.shift_token_line(self.shift_amount);
     }
@@ -1,6 +1,6 @@
 use crate::nodes::{
     Arguments, Block, Expression, FunctionCall, FunctionReturnType, LastStatement, Prefix,
-    Statement, Token, Type, Variable,
+    Statement, TableEntry, Token, Type, TypedIdentifier, Variable,
 };
 
 pub(crate) fn block_total(block: &Block) -> usize {
@@ -21,6 +21,23 @@ pub(crate) fn statement_first(statement: &Statement) -> usize {
         .unwrap_or(0)
 }
 
+pub(crate) fn statement_line(statement: &Statement) -> Option<usize> {
+    first_statement_token(statement).and_then(Token::get_line_number)
+}
+
+pub(crate) fn last_statement_line(statement: &LastStatement) -> Option<usize> {
+    first_last_statement_token(statement).and_then(Token::get_line_number)
+}
+
+pub(crate) fn first_last_statement_token(statement: &LastStatement) -> Option<&Token> {
+    match statement {
+        LastStatement::Break(token) | LastStatement::Continue(token) => token.as_ref(),
+        LastStatement::Return(return_statement) => {
+            return_statement.get_tokens().map(|tokens| &tokens.r#return)
+        }
+    }
+}
+
 fn get_token_line(token: &Token) -> Option<usize> {
     token
         .iter_trailing_trivia()
@@ -55,7 +72,7 @@ fn last_block_token(block: &Block) -> Option<&Token> {
         })
 }
 
-fn last_statement_token(statement: &Statement) -> Option<&Token> {
+pub(crate) fn last_statement_token(statement: &Statement) -> Option<&Token> {
     match statement {
         Statement::Assign(assign) => assign.last_value().and_then(last_expression_token),
         Statement::Do(do_statement) => do_statement.get_tokens().map(|tokens| &tokens.end),
@@ -63,11 +80,21 @@ fn last_statement_token(statement: &Statement) -> Option<&Token> {
         Statement::CompoundAssign(assign) => last_expression_token(assign.get_value()),
         Statement::Function(function) => function.get_tokens().map(|tokens| &tokens.end),
         Statement::GenericFor(generic_for) => generic_for.get_tokens().map(|tokens| &tokens.end),
+        Statement::Goto(goto) => goto.get_label().get_token(),
         Statement::If(if_statement) => if_statement.get_tokens().map(|tokens| &tokens.end),
+        Statement::Label(label) => label.get_tokens().map(|tokens| &tokens.right_colons),
         Statement::LocalAssign(local_assign) => local_assign
             .iter_values()
             .last()
             .and_then(last_expression_token)
+            .or_else(|| {
+                local_assign
+                    .iter_attributes()
+                    .last()
+                    .flatten()
+                    .and_then(|attribute| attribute.get_tokens())
+                    .map(|tokens| &tokens.closing_bracket)
+            })
             .or_else(|| {
                 local_assign
                     .iter_variables()
@@ -92,7 +119,7 @@ fn last_statement_token(statement: &Statement) -> Option<&Token> {
     }
 }
 
-fn last_last_statement_token(last: &LastStatement) -> Option<&Token> {
+pub(crate) fn last_last_statement_token(last: &LastStatement) -> Option<&Token> {
     match last {
         LastStatement::Break(token) | LastStatement::Continue(token) => token.as_ref(),
         LastStatement::Return(return_statement) => return_statement
@@ -103,7 +130,7 @@ fn last_last_statement_token(last: &LastStatement) -> Option<&Token> {
     }
 }
 
-fn last_expression_token(expression: &Expression) -> Option<&Token> {
+pub(crate) fn last_expression_token(expression: &Expression) -> Option<&Token> {
     match expression {
         Expression::Binary(binary) => last_expression_token(binary.right()),
         Expression::Call(call) => last_call_token(call),
@@ -172,6 +199,13 @@ fn last_type_token(r#type: &Type) -> Option<&Token> {
     }
 }
 
+pub(crate) fn last_typed_identifier_token(identifier: &TypedIdentifier) -> Option<&Token> {
+    match identifier.get_type() {
+        Some(r#type) => last_type_token(r#type),
+        None => identifier.get_token(),
+    }
+}
+
 fn last_call_token(call: &FunctionCall) -> Option<&Token> {
     match call.get_arguments() {
         Arguments::Tuple(tuple) => tuple.get_tokens().map(|tokens| &tokens.closing_parenthese),
@@ -180,7 +214,7 @@ fn last_call_token(call: &FunctionCall) -> Option<&Token> {
     }
 }
 
-fn first_statement_token(statement: &Statement) -> Option<&Token> {
+pub(crate) fn first_statement_token(statement: &Statement) -> Option<&Token> {
     match statement {
         Statement::Assign(assign) => assign
             .iter_variables()
@@ -191,7 +225,9 @@ fn first_statement_token(statement: &Statement) -> Option<&Token> {
         Statement::CompoundAssign(assign) => first_variable_token(assign.get_variable()),
         Statement::Function(function) => function.get_tokens().map(|tokens| &tokens.function),
         Statement::GenericFor(generic_for) => generic_for.get_tokens().map(|tokens| &tokens.r#for),
+        Statement::Goto(goto) => goto.get_tokens().map(|tokens| &tokens.goto),
         Statement::If(if_statement) => if_statement.get_tokens().map(|tokens| &tokens.r#if),
+        Statement::Label(label) => label.get_tokens().map(|tokens| &tokens.left_colons),
         Statement::LocalAssign(local_assign) => {
             local_assign.get_tokens().map(|tokens| &tokens.local)
         }
@@ -212,15 +248,58 @@ fn first_statement_token(statement: &Statement) -> Option<&Token> {
                 }
             })
         }
-        Statement::ExportTypeFunction(export_type_function) => {
-            export_type_function.get_tokens().map(|tokens| &tokens.export)
-        }
+        Statement::ExportTypeFunction(export_type_function) => export_type_function
+            .get_tokens()
+            .map(|tokens| &tokens.export),
         Statement::TypeFunction(type_function) => {
             type_function.get_tokens().map(|tokens| &tokens.r#type)
         }
     }
 }
 
+pub(crate) fn first_table_entry_token(entry: &TableEntry) -> Option<&Token> {
+    match entry {
+        TableEntry::Field(field) => field.get_field().get_token(),
+        TableEntry::Index(index) => index.get_tokens().map(|tokens| &tokens.opening_bracket),
+        TableEntry::Value(value) => first_expression_token(value),
+    }
+}
+
+pub(crate) fn last_table_entry_token(entry: &TableEntry) -> Option<&Token> {
+    match entry {
+        TableEntry::Field(field) => last_expression_token(field.get_value()),
+        TableEntry::Index(index) => last_expression_token(index.get_value()),
+        TableEntry::Value(value) => last_expression_token(value),
+    }
+}
+
+pub(crate) fn first_expression_token(expression: &Expression) -> Option<&Token> {
+    match expression {
+        Expression::Binary(binary) => first_expression_token(binary.left()),
+        Expression::Call(call) => first_prefix_token(call.get_prefix()),
+        Expression::Field(field) => first_prefix_token(field.get_prefix()),
+        Expression::Function(function) => function.get_tokens().map(|tokens| &tokens.function),
+        Expression::Identifier(identifier) => identifier.get_token(),
+        Expression::If(if_expression) => if_expression.get_tokens().map(|tokens| &tokens.r#if),
+        Expression::Index(index) => first_prefix_token(index.get_prefix()),
+        Expression::Number(number) => number.get_token(),
+        Expression::Parenthese(parentheses) => parentheses
+            .get_tokens()
+            .map(|tokens| &tokens.left_parenthese),
+        Expression::String(string) => string.get_token(),
+        Expression::InterpolatedString(string) => {
+            string.get_tokens().map(|tokens| &tokens.opening_tick)
+        }
+        Expression::Table(table) => table.get_tokens().map(|tokens| &tokens.opening_brace),
+        Expression::Nil(token)
+        | Expression::False(token)
+        | Expression::True(token)
+        | Expression::VariableArguments(token) => token.as_ref(),
+        Expression::Unary(unary) => unary.get_token(),
+        Expression::TypeCast(type_cast) => first_expression_token(type_cast.get_expression()),
+    }
+}
+
 fn first_variable_token(variable: &Variable) -> Option<&Token> {
     match variable {
         Variable::Identifier(identifier) => identifier.get_token(),
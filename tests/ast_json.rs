@@ -0,0 +1,122 @@
+use darklua_core::{
+    ast_from_json, ast_to_json,
+    generator::{DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, TokenBasedLuaGenerator},
+    nodes::Block,
+    Parser,
+};
+use std::time::Duration;
+
+mod ast_fuzzer;
+mod utils;
+
+use ast_fuzzer::*;
+
+fn round_trip(block: &Block, code: &str) -> Block {
+    let json = ast_to_json(block, code).expect("unable to serialize block");
+    ast_from_json(&json).unwrap_or_else(|err| panic!("unable to deserialize block: {}", err))
+}
+
+fn run_for_minimum_time<F: Fn()>(func: F) {
+    let millis = option_env!("FUZZ_DURATION_MILLISECONDS")
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(1500);
+
+    utils::run_for_minimum_time(Duration::from_millis(millis), func);
+}
+
+fn fuzz_round_trip(budget: fn() -> FuzzBudget) {
+    // serializing deeply nested blocks needs more than the default stack size of the
+    // test threads in debug builds
+    std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(move || {
+            run_for_minimum_time(|| {
+                let block = AstFuzzer::new(budget()).fuzz_block();
+
+                pretty_assertions::assert_eq!(round_trip(&block, ""), block);
+            })
+        })
+        .expect("unable to spawn fuzz thread")
+        .join()
+        .unwrap_or_else(|err| std::panic::resume_unwind(err));
+}
+
+#[cfg(not(coverage))]
+#[test]
+fn fuzz_small_block() {
+    fuzz_round_trip(|| FuzzBudget::new(20, 40));
+}
+
+#[cfg(not(coverage))]
+#[test]
+fn fuzz_medium_block_with_types() {
+    fuzz_round_trip(|| FuzzBudget::new(100, 200).with_types(40));
+}
+
+#[test]
+fn tokens_round_trip_generates_the_same_code() {
+    let code = include_str!("./test_cases/spaces_and_comments.lua");
+    let block = Parser::default().preserve_tokens().parse(code).unwrap();
+
+    let mut generator = TokenBasedLuaGenerator::new(code);
+    generator.write_block(&block);
+    let expected = generator.into_string();
+
+    let block = round_trip(&block, code);
+
+    let mut generator = TokenBasedLuaGenerator::new("");
+    generator.write_block(&block);
+
+    pretty_assertions::assert_eq!(generator.into_string(), expected);
+}
+
+#[test]
+fn imported_block_is_accepted_by_generators() {
+    let code = "local function add(a: number, b: number): number\n    return a + b\nend\n";
+    let block = Parser::default().preserve_tokens().parse(code).unwrap();
+
+    let block = round_trip(&block, code);
+
+    let mut dense = DenseLuaGenerator::default();
+    dense.write_block(&block);
+    pretty_assertions::assert_eq!(
+        dense.into_string(),
+        "local function add(a:number,b:number):number return a+b end"
+    );
+
+    let mut readable = ReadableLuaGenerator::default();
+    readable.write_block(&block);
+    pretty_assertions::assert_eq!(readable.into_string(), code);
+}
+
+#[test]
+fn import_handwritten_json() {
+    let json = r#"{
+        "version": 1,
+        "block": {
+            "statements": [
+                {
+                    "local_assign": {
+                        "variables": [{ "name": { "name": "answer" } }],
+                        "attributes": [null],
+                        "values": [{ "number": { "decimal": { "float": 42.0 } } }]
+                    }
+                }
+            ],
+            "last_statement": {
+                "return": {
+                    "expressions": [{ "identifier": { "name": "answer" } }]
+                }
+            }
+        }
+    }"#;
+
+    let block = ast_from_json(json).unwrap();
+
+    let mut generator = ReadableLuaGenerator::default();
+    generator.write_block(&block);
+    pretty_assertions::assert_eq!(
+        generator.into_string(),
+        "local answer = 42\n\nreturn answer\n"
+    );
+}
@@ -637,5 +637,1328 @@ data:
 
             process_main_with_error(&resources, "two_different_direct_cycles");
         }
+
+        #[test]
+        fn simple_direct_cycle_with_line_numbers() {
+            let resources = memory_resources!(
+                "src/value1.lua" => "local value2 = require('./value2')\nreturn value2",
+                "src/value2.lua" => "\n\nreturn require('./value1')",
+                "src/main.lua" => "local value = require('./value1.lua')",
+                ".darklua.json" => DARKLUA_BUNDLE_ONLY_RETAIN_LINES_CONFIG,
+            );
+
+            process_main_with_error(&resources, "simple_direct_cycle_with_line_numbers");
+        }
+
+        #[test]
+        fn allowed_cycle_with_deferred_require() {
+            let resources = memory_resources!(
+                "src/value1.lua" => "local value2 = require('./value2')\nreturn { name = 'value1', getOther = function() return value2.name end }",
+                "src/value2.lua" => "return { name = 'value2', getOther = function() return require('./value1').name end }",
+                "src/main.lua" => "local value = require('./value1.lua')",
+                ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"allow_cycles\": true } }",
+            );
+
+            process_main(
+                &resources,
+                "cyclic_requires_allowed_cycle_with_deferred_require",
+            );
+        }
+
+        #[test]
+        fn allowed_cycle_with_eager_initialization() {
+            let resources = memory_resources!(
+                "src/value1.lua" => "return require('./value2')",
+                "src/value2.lua" => "return function() return require('./value1') end",
+                "src/main.lua" => "local value = require('./value1.lua')",
+                ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"allow_cycles\": true, \"initialization\": \"eager\" } }",
+            );
+
+            process_main_with_error(&resources, "allowed_cycle_with_eager_initialization");
+        }
+    }
+}
+
+mod roblox_require_mode {
+    use super::*;
+
+    const DARKLUA_BUNDLE_ROBLOX_CONFIG: &str = "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": { \"name\": \"roblox\", \"rojo_sourcemap\": \"sourcemap.json\" } } }";
+
+    const SOURCEMAP: &str = r#"{
+        "name": "Project",
+        "className": "ModuleScript",
+        "filePaths": ["src/init.lua", "default.project.json"],
+        "children": [
+            {
+                "name": "main",
+                "className": "ModuleScript",
+                "filePaths": ["src/main.lua"]
+            },
+            {
+                "name": "value",
+                "className": "ModuleScript",
+                "filePaths": ["src/value.lua"]
+            },
+            {
+                "name": "Util",
+                "className": "Folder",
+                "children": [
+                    {
+                        "name": "format",
+                        "className": "ModuleScript",
+                        "filePaths": ["src/Util/format.lua"]
+                    },
+                    {
+                        "name": "data",
+                        "className": "ModuleScript",
+                        "filePaths": ["src/Util/data.json"]
+                    }
+                ]
+            }
+        ]
+    }"#;
+
+    fn process_main(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_roblox_{}", snapshot_name), main);
+    }
+
+    fn process_main_with_errors(resources: &Resources, snapshot_name: &'static str) {
+        let errors = process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap_err();
+
+        let error_display: Vec<_> = errors.into_iter().map(|err| err.to_string()).collect();
+
+        let mut settings = insta::Settings::clone_current();
+        settings.add_filter("\\\\", "/");
+        settings.bind(|| {
+            insta::assert_snapshot!(
+                format!("bundle_roblox_{}", snapshot_name),
+                error_display.join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn require_sibling_module() {
+        let resources = memory_resources!(
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local value = require(script.Parent.value)",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => DARKLUA_BUNDLE_ROBLOX_CONFIG,
+        );
+
+        process_main(&resources, "require_sibling_module");
+    }
+
+    #[test]
+    fn require_sibling_module_with_index() {
+        let resources = memory_resources!(
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local value = require(script.Parent['value'])",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => DARKLUA_BUNDLE_ROBLOX_CONFIG,
+        );
+
+        // the bundled code should be the same as requiring with a field
+        process_main(&resources, "require_sibling_module");
+    }
+
+    #[test]
+    fn require_nested_modules() {
+        let resources = memory_resources!(
+            "src/init.lua" => "return { name = 'project' }",
+            "src/Util/format.lua" => "local project = require(script.Parent.Parent)\nreturn function(value)\n\treturn project.name .. ': ' .. tostring(value)\nend",
+            "src/Util/data.json" => "{ \"value\": 10 }",
+            "src/main.lua" => "local format = require(script.Parent.Util.format)\nlocal data = require(script.Parent.Util.data)\nprint(format(data.value))",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => DARKLUA_BUNDLE_ROBLOX_CONFIG,
+        );
+
+        process_main(&resources, "require_nested_modules");
+    }
+
+    #[test]
+    fn require_sibling_module_with_wait_for_child() {
+        let resources = memory_resources!(
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local value = require(script.Parent:WaitForChild('value'))",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => DARKLUA_BUNDLE_ROBLOX_CONFIG,
+        );
+
+        process_main(&resources, "require_sibling_module");
+    }
+
+    #[test]
+    fn skip_service_require_not_in_sourcemap() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local Players = require(game:GetService('ReplicatedStorage').Packages.Players)",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => DARKLUA_BUNDLE_ROBLOX_CONFIG,
+        );
+
+        process_main(&resources, "skip_service_require_not_in_sourcemap");
+    }
+
+    #[test]
+    fn skip_require_that_is_not_an_instance_path() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local value = require(getModule())",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => DARKLUA_BUNDLE_ROBLOX_CONFIG,
+        );
+
+        process_main(&resources, "skip_require_that_is_not_an_instance_path");
+    }
+
+    #[test]
+    fn skip_excluded_module() {
+        let resources = memory_resources!(
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local value = require(script.Parent.value)",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": { \"name\": \"roblox\", \"rojo_sourcemap\": \"sourcemap.json\" }, \"excludes\": [\"src/value.lua\"] } }",
+        );
+
+        process_main(&resources, "skip_excluded_module");
+    }
+
+    #[test]
+    fn require_unknown_instance_errors() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local value = require(script.Parent.Unknown)",
+            "sourcemap.json" => SOURCEMAP,
+            ".darklua.json" => DARKLUA_BUNDLE_ROBLOX_CONFIG,
+        );
+
+        process_main_with_errors(&resources, "require_unknown_instance_errors");
+    }
+
+    #[test]
+    fn bundle_without_sourcemap_errors() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local value = require(script.Parent.value)",
+            ".darklua.json" => "{ \"rules\": [], \"bundle\": { \"require_mode\": \"roblox\" } }",
+        );
+
+        process_main_with_errors(&resources, "bundle_without_sourcemap_errors");
+    }
+}
+
+mod module_require_mode {
+    use super::*;
+
+    const DARKLUA_BUNDLE_MODULE_CONFIG: &str =
+        "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"module\" } }";
+
+    fn process_main(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_module_{}", snapshot_name), main);
+    }
+
+    fn process_main_with_errors(resources: &Resources, snapshot_name: &'static str) {
+        let errors = process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap_err();
+
+        let error_display: Vec<_> = errors.into_iter().map(|err| err.to_string()).collect();
+
+        let mut settings = insta::Settings::clone_current();
+        settings.add_filter("\\\\", "/");
+        settings.bind(|| {
+            insta::assert_snapshot!(
+                format!("bundle_module_{}", snapshot_name),
+                error_display.join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn require_module() {
+        let resources = memory_resources!(
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local value = require('src.value')",
+            ".darklua.json" => DARKLUA_BUNDLE_MODULE_CONFIG,
+        );
+
+        process_main(&resources, "require_module");
+    }
+
+    #[test]
+    fn require_nested_modules() {
+        let resources = memory_resources!(
+            "lib/init.lua" => "return { name = 'lib' }",
+            "lib/util/format.lua" => "local lib = require('lib')\nreturn function(value)\n\treturn lib.name .. ': ' .. tostring(value)\nend",
+            "src/main.lua" => "local format = require('lib.util.format')\nprint(format(true))",
+            ".darklua.json" => DARKLUA_BUNDLE_MODULE_CONFIG,
+        );
+
+        process_main(&resources, "require_nested_modules");
+    }
+
+    #[test]
+    fn require_module_with_custom_package_path() {
+        let resources = memory_resources!(
+            "vendor/json/init.luau" => "return { encode = function() end }",
+            "src/main.lua" => "local json = require('json')",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": { \"name\": \"module\", \"package_path\": [\"./src/?.lua\", \"./vendor/?/init.luau\"] } } }",
+        );
+
+        process_main(&resources, "require_module_with_custom_package_path");
+    }
+
+    #[test]
+    fn skip_excluded_module() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local http = require('socket.http')",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"module\", \"excludes\": [\"socket.*\"] } }",
+        );
+
+        process_main(&resources, "skip_excluded_module");
+    }
+
+    #[test]
+    fn skip_module_with_excluded_file_path() {
+        let resources = memory_resources!(
+            "vendor/json/init.lua" => "return { encode = function() end }",
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local json = require('json')\nlocal value = require('value')",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": { \"name\": \"module\", \"package_path\": [\"./src/?.lua\", \"./vendor/?/init.lua\"] }, \"excludes\": [\"vendor/**\"] } }",
+        );
+
+        process_main(&resources, "skip_module_with_excluded_file_path");
+    }
+
+    #[test]
+    fn require_unknown_module_errors() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local value = require('src.unknown')",
+            ".darklua.json" => DARKLUA_BUNDLE_MODULE_CONFIG,
+        );
+
+        process_main_with_errors(&resources, "require_unknown_module_errors");
+    }
+}
+
+mod tree_shake {
+    use super::*;
+
+    const DARKLUA_BUNDLE_TREE_SHAKE_CONFIG: &str = "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"tree_shake\": true } }";
+
+    fn process_main(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_tree_shake_{}", snapshot_name), main);
+    }
+
+    #[test]
+    fn remove_unused_fields_of_table_constructor() {
+        let resources = memory_resources!(
+            "src/util.lua" => "return { a = function() return 'a' end, b = function() return 'b' end, c = 1 }",
+            "src/main.lua" => "local util = require('./util')\nprint(util.a())",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(&resources, "remove_unused_fields_of_table_constructor");
+    }
+
+    #[test]
+    fn remove_unused_fields_of_local_table() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local Util = {}\nlocal function helper()\n\treturn 'helper'\nend\nlocal function unused()\n\treturn 'unused'\nend\nfunction Util.a()\n\treturn helper()\nend\nfunction Util.b()\n\treturn unused()\nend\nUtil.c = 10\nreturn Util",
+            "src/main.lua" => "print(require('./util').a())",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(&resources, "remove_unused_fields_of_local_table");
+    }
+
+    #[test]
+    fn keep_fields_used_by_other_kept_fields() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local Util = { prefix = '> ' }\nfunction Util.format(value)\n\treturn Util.prefix .. Util.toString(value)\nend\nfunction Util.toString(value)\n\treturn tostring(value)\nend\nfunction Util.unused()\nend\nreturn Util",
+            "src/main.lua" => "local Util = require('./util')\nprint(Util.format(true))",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(&resources, "keep_fields_used_by_other_kept_fields");
+    }
+
+    #[test]
+    fn keep_fields_used_from_multiple_modules() {
+        let resources = memory_resources!(
+            "src/util.lua" => "return { a = 1, b = 2, c = 3 }",
+            "src/value.lua" => "local util = require('./util')\nreturn util.b",
+            "src/main.lua" => "local util = require('./util')\nprint(util.a, require('./value'))",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(&resources, "keep_fields_used_from_multiple_modules");
+    }
+
+    #[test]
+    fn remove_unused_fields_with_retain_lines_generator() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local function helper() return 'helper' end\nreturn {\n\ta = 1,\n\tb = helper,\n\tc = 3,\n}",
+            "src/main.lua" => "local util = require('./util')\nprint(util.a, util.c)",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"retain_lines\", \"bundle\": { \"require_mode\": \"path\", \"tree_shake\": true } }",
+        );
+
+        process_main(
+            &resources,
+            "remove_unused_fields_with_retain_lines_generator",
+        );
+    }
+
+    #[test]
+    fn keep_entries_with_side_effects() {
+        let resources = memory_resources!(
+            "src/util.lua" => "return { a = 1, b = print('loaded') }",
+            "src/main.lua" => "print(require('./util').a)",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(&resources, "keep_entries_with_side_effects");
+    }
+
+    #[test]
+    fn keep_all_fields_when_module_value_escapes() {
+        let resources = memory_resources!(
+            "src/util.lua" => "return { a = 1, b = 2 }",
+            "src/main.lua" => "local util = require('./util')\nprint(util.a, util)",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(&resources, "keep_all_fields_when_module_value_escapes");
+    }
+
+    #[test]
+    fn keep_all_fields_when_module_method_is_called() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local Util = { name = 'util' }\nfunction Util:getName()\n\treturn self.name\nend\nfunction Util.unused()\nend\nreturn Util",
+            "src/main.lua" => "local util = require('./util')\nprint(util:getName())",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(&resources, "keep_all_fields_when_module_method_is_called");
+    }
+
+    #[test]
+    fn keep_all_fields_when_local_table_is_used_by_module() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local Util = {}\nfunction Util.a()\n\treturn 'a'\nend\nfunction Util.b()\n\treturn 'b'\nend\nsetmetatable(Util, {})\nreturn Util",
+            "src/main.lua" => "print(require('./util').a())",
+            ".darklua.json" => DARKLUA_BUNDLE_TREE_SHAKE_CONFIG,
+        );
+
+        process_main(
+            &resources,
+            "keep_all_fields_when_local_table_is_used_by_module",
+        );
+    }
+}
+
+mod initialization {
+    use super::*;
+
+    const DARKLUA_BUNDLE_EAGER_CONFIG: &str = "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"initialization\": \"eager\" } }";
+
+    fn process_main(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_initialization_{}", snapshot_name), main);
+    }
+
+    fn process_main_with_errors(resources: &Resources, snapshot_name: &'static str) {
+        let errors = process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap_err();
+
+        let error_display: Vec<_> = errors.into_iter().map(|err| err.to_string()).collect();
+
+        let mut settings = insta::Settings::clone_current();
+        settings.add_filter("\\\\", "/");
+        settings.bind(|| {
+            insta::assert_snapshot!(
+                format!("bundle_initialization_{}", snapshot_name),
+                error_display.join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn lazy_initialization_is_the_default() {
+        let resources = memory_resources!(
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local value = require('./value')",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"initialization\": \"lazy\" } }",
+        );
+
+        process_main(&resources, "lazy_initialization_is_the_default");
+    }
+
+    #[test]
+    fn eager_module_returning_value() {
+        let resources = memory_resources!(
+            "src/value.lua" => "return true",
+            "src/main.lua" => "local value = require('./value')\nprint(value)",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main(&resources, "eager_module_returning_value");
+    }
+
+    #[test]
+    fn eager_module_with_statements() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local Util = {}\nfunction Util.greet(name)\n\treturn 'hello ' .. name\nend\nreturn Util",
+            "src/main.lua" => "local Util = require('./util')\nprint(Util.greet('world'))",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main(&resources, "eager_module_with_statements");
+    }
+
+    #[test]
+    fn eager_nested_dependencies_in_dependency_order() {
+        let resources = memory_resources!(
+            "src/a.lua" => "local b = require('./b')\nreturn { b = b }",
+            "src/b.lua" => "local c = require('./c')\nreturn c + 1",
+            "src/c.lua" => "return 1",
+            "src/main.lua" => "local a = require('./a')\nlocal c = require('./c')\nprint(a.b, c)",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main(&resources, "eager_nested_dependencies_in_dependency_order");
+    }
+
+    #[test]
+    fn eager_require_used_as_prefix() {
+        let resources = memory_resources!(
+            "src/util.lua" => "return { run = function() end }",
+            "src/main.lua" => "require('./util').run()",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main(&resources, "eager_require_used_as_prefix");
+    }
+
+    #[test]
+    fn eager_require_as_statement_is_removed() {
+        let resources = memory_resources!(
+            "src/setup.lua" => "print('setup')\nreturn nil",
+            "src/main.lua" => "require('./setup')\nprint('main')",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main(&resources, "eager_require_as_statement_is_removed");
+    }
+
+    #[test]
+    fn eager_require_data_file() {
+        let resources = memory_resources!(
+            "src/data.json" => "{ \"value\": 1 }",
+            "src/main.lua" => "local data = require('./data.json')",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main(&resources, "eager_require_data_file");
+    }
+
+    #[test]
+    fn eager_module_with_nested_return_is_wrapped_in_function() {
+        let resources = memory_resources!(
+            "src/value.lua" => "if os then\n\treturn 'os'\nend\nreturn 'no os'",
+            "src/main.lua" => "local value = require('./value')",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main(
+            &resources,
+            "eager_module_with_nested_return_is_wrapped_in_function",
+        );
+    }
+
+    #[test]
+    fn eager_with_tree_shake() {
+        let resources = memory_resources!(
+            "src/util.lua" => "return { a = function() return 'a' end, b = function() return 'b' end }",
+            "src/main.lua" => "local util = require('./util')\nprint(util.a())",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"tree_shake\": true, \"initialization\": \"eager\" } }",
+        );
+
+        process_main(&resources, "eager_with_tree_shake");
+    }
+
+    #[test]
+    fn eager_with_retain_lines_generator() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local Util = {}\n\nfunction Util.run()\n\treturn 1\nend\n\nreturn Util",
+            "src/main.lua" => "local Util = require('./util')\n\nprint(Util.run())",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"retain_lines\", \"bundle\": { \"require_mode\": \"path\", \"initialization\": \"eager\" } }",
+        );
+
+        process_main(&resources, "eager_with_retain_lines_generator");
+    }
+
+    #[test]
+    fn eager_cycle_is_an_error() {
+        let resources = memory_resources!(
+            "src/value1.lua" => "return require('./value2')",
+            "src/value2.lua" => "return require('./value1')",
+            "src/main.lua" => "local value = require('./value1')",
+            ".darklua.json" => DARKLUA_BUNDLE_EAGER_CONFIG,
+        );
+
+        process_main_with_errors(&resources, "eager_cycle_is_an_error");
+    }
+}
+
+mod manifest {
+    use super::*;
+
+    const DARKLUA_BUNDLE_MANIFEST_CONFIG: &str = "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"manifest\": \"bundle-manifest.json\", \"excludes\": [\"@lune/**\"] } }";
+
+    fn process_main_manifest(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let manifest = resources.get("bundle-manifest.json").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_manifest_{}", snapshot_name), manifest);
+    }
+
+    #[test]
+    fn manifest_without_requires() {
+        let resources = memory_resources!(
+            "src/main.lua" => "print('hello')",
+            ".darklua.json" => DARKLUA_BUNDLE_MANIFEST_CONFIG,
+        );
+
+        process_main_manifest(&resources, "manifest_without_requires");
+    }
+
+    #[test]
+    fn manifest_with_nested_modules() {
+        let resources = memory_resources!(
+            "src/format.lua" => "return function(value) return '<' .. value .. '>' end",
+            "src/util.lua" => "local format = require('./format')\nreturn { format = format }",
+            "src/main.lua" => "local util = require('./util')\nlocal format = require('./format')\nprint(util.format('a'), format('b'))",
+            ".darklua.json" => DARKLUA_BUNDLE_MANIFEST_CONFIG,
+        );
+
+        process_main_manifest(&resources, "manifest_with_nested_modules");
+    }
+
+    #[test]
+    fn manifest_with_data_file() {
+        let resources = memory_resources!(
+            "src/data.json" => "{ \"value\": 1 }",
+            "src/main.lua" => "local data = require('./data.json')",
+            ".darklua.json" => DARKLUA_BUNDLE_MANIFEST_CONFIG,
+        );
+
+        process_main_manifest(&resources, "manifest_with_data_file");
+    }
+
+    #[test]
+    fn manifest_with_excluded_requires() {
+        let resources = memory_resources!(
+            "src/util.lua" => "local fs = require('@lune/fs')\nreturn { fs = fs }",
+            "src/main.lua" => "local util = require('./util')\nlocal process = require('@lune/process')",
+            ".darklua.json" => DARKLUA_BUNDLE_MANIFEST_CONFIG,
+        );
+
+        process_main_manifest(&resources, "manifest_with_excluded_requires");
+    }
+
+    #[test]
+    fn manifest_reports_size_after_tree_shaking() {
+        let resources = memory_resources!(
+            "src/util.lua" => "return { a = function() return 'a' end, b = function() return 'b' end }",
+            "src/main.lua" => "local util = require('./util')\nprint(util.a())",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"manifest\": \"bundle-manifest.json\", \"tree_shake\": true } }",
+        );
+
+        process_main_manifest(&resources, "manifest_reports_size_after_tree_shaking");
+    }
+
+    #[test]
+    fn manifest_is_not_written_when_bundle_fails() {
+        let resources = memory_resources!(
+            "src/value1.lua" => "return require('./value2')",
+            "src/value2.lua" => "return require('./value1')",
+            "src/main.lua" => "local value = require('./value1')",
+            ".darklua.json" => DARKLUA_BUNDLE_MANIFEST_CONFIG,
+        );
+
+        process(
+            &resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap_err();
+
+        assert!(resources.get("bundle-manifest.json").is_err());
+    }
+
+    #[test]
+    fn manifest_for_each_entry() {
+        let resources = memory_resources!(
+            "src/shared/util.lua" => "return { value = 1 }",
+            "src/client.lua" => "local util = require('./shared/util')\nprint('client', util.value)",
+            "src/server.lua" => "local util = require('./shared/util')\nprint('server', util.value)",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"manifest\": \"build/{entry}.manifest.json\" } }",
+        );
+
+        process(&resources, Options::new("src").with_output("out"))
+            .unwrap()
+            .result()
+            .unwrap();
+
+        insta::assert_snapshot!(
+            "bundle_manifest_for_client_entry",
+            resources.get("build/src/client.manifest.json").unwrap()
+        );
+        insta::assert_snapshot!(
+            "bundle_manifest_for_server_entry",
+            resources.get("build/src/server.manifest.json").unwrap()
+        );
+    }
+
+    #[test]
+    fn manifest_shared_by_multiple_entries_errors() {
+        let resources = memory_resources!(
+            "src/client.lua" => "print('client')",
+            "src/server.lua" => "print('server')",
+            ".darklua.json" => DARKLUA_BUNDLE_MANIFEST_CONFIG,
+        );
+
+        let errors = process(&resources, Options::new("src").with_output("out"))
+            .unwrap()
+            .result()
+            .unwrap_err();
+
+        pretty_assertions::assert_eq!(errors.len(), 1);
+        let error = errors[0].to_string().replace('\\', "/");
+        // the entries can be processed in any order
+        assert!(
+            error.contains("unable to write bundle manifest `bundle-manifest.json` for `src/")
+                && error.ends_with(
+                    "(use `{entry}` in the manifest path to write one manifest per entry)"
+                ),
+            "unexpected error: {}",
+            error
+        );
+    }
+}
+
+mod resource_loaders {
+    use super::*;
+
+    fn bundle_config_with_loaders(loaders: &str) -> String {
+        format!(
+            "{{ \"rules\": [], \"generator\": \"readable\", \"bundle\": {{ \"require_mode\": \"path\", \"loaders\": {} }} }}",
+            loaders
+        )
+    }
+
+    fn process_main(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_resource_loaders_{}", snapshot_name), main);
+    }
+
+    fn process_main_with_errors(resources: &Resources, snapshot_name: &'static str) {
+        let errors = process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap_err();
+
+        let error_display: Vec<_> = errors.into_iter().map(|err| err.to_string()).collect();
+
+        let mut settings = insta::Settings::clone_current();
+        settings.add_filter("\\\\", "/");
+        settings.bind(|| {
+            insta::assert_snapshot!(
+                format!("bundle_resource_loaders_{}", snapshot_name),
+                error_display.join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn require_csv_file() {
+        let resources = memory_resources!(
+            "src/strings.csv" => "key,en,fr\ngreeting,Hello,Bonjour\nfarewell,\"Bye, see you\",\"Au revoir\"\n",
+            "src/main.lua" => "local strings = require('./strings.csv')",
+            ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+        );
+
+        process_main(&resources, "require_csv_file");
+    }
+
+    #[test]
+    fn require_tsv_file() {
+        let resources = memory_resources!(
+            "src/strings.tsv" => "key\ten-US\ngreeting\tHello, world\n",
+            "src/main.lua" => "local strings = require('./strings.tsv')",
+            ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+        );
+
+        process_main(&resources, "require_tsv_file");
+    }
+
+    #[test]
+    fn require_csv_file_with_invalid_row() {
+        let resources = memory_resources!(
+            "src/strings.csv" => "key,en\nfarewell,\"Bye\nnow\"\n\ngreeting,Hello,Bonjour\n",
+            "src/main.lua" => "local strings = require('./strings.csv')",
+            ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+        );
+
+        process_main_with_errors(&resources, "require_csv_file_with_invalid_row");
+    }
+
+    #[test]
+    fn require_file_with_custom_extension() {
+        let resources = memory_resources!(
+            "src/strings.loc" => "key,en\ngreeting,Hello\n",
+            "src/main.lua" => "local strings = require('./strings.loc')",
+            ".darklua.json" => bundle_config_with_loaders("{ \"loc\": \"csv\" }"),
+        );
+
+        process_main(&resources, "require_file_with_custom_extension");
+    }
+
+    #[test]
+    fn require_file_with_compound_extension() {
+        let resources = memory_resources!(
+            "src/template.lua.txt" => "return {}",
+            "src/data.json" => "{ \"value\": true }",
+            "src/main.lua" => "local template = require('./template.lua.txt')\nlocal data = require('./data.json')",
+            ".darklua.json" => bundle_config_with_loaders("{ \"lua.txt\": \"lua\" }"),
+        );
+
+        process_main(&resources, "require_file_with_compound_extension");
+    }
+
+    #[test]
+    fn override_default_loader() {
+        let resources = memory_resources!(
+            "src/data.json" => "{ \"value\": true }",
+            "src/main.lua" => "local data = require('./data.json')",
+            ".darklua.json" => bundle_config_with_loaders("{ \"json\": \"text\" }"),
+        );
+
+        process_main(&resources, "override_default_loader");
+    }
+
+    #[test]
+    fn require_file_without_loader() {
+        let resources = memory_resources!(
+            "src/image.png" => "",
+            "src/main.lua" => "local image = require('./image.png')",
+            ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+        );
+
+        process_main_with_errors(&resources, "require_file_without_loader");
+    }
+}
+
+mod deduplicate {
+    use super::*;
+
+    const DARKLUA_BUNDLE_DEDUPLICATE_CONFIG: &str = "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"deduplicate\": true } }";
+
+    fn process_main(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_deduplicate_{}", snapshot_name), main);
+    }
+
+    #[test]
+    fn merge_identical_modules() {
+        let resources = memory_resources!(
+            "packages/a/vendor/lib.lua" => "return { version = '1.0.0' }",
+            "packages/b/vendor/lib.lua" => "return { version = '1.0.0' }",
+            "src/main.lua" => "local libA = require('../packages/a/vendor/lib')\nlocal libB = require('../packages/b/vendor/lib')",
+            ".darklua.json" => DARKLUA_BUNDLE_DEDUPLICATE_CONFIG,
+        );
+
+        process_main(&resources, "merge_identical_modules");
+    }
+
+    #[test]
+    fn keep_different_modules() {
+        let resources = memory_resources!(
+            "packages/a/vendor/lib.lua" => "return { version = '1.0.0' }",
+            "packages/b/vendor/lib.lua" => "return { version = '2.0.0' }",
+            "src/main.lua" => "local libA = require('../packages/a/vendor/lib')\nlocal libB = require('../packages/b/vendor/lib')",
+            ".darklua.json" => DARKLUA_BUNDLE_DEDUPLICATE_CONFIG,
+        );
+
+        process_main(&resources, "keep_different_modules");
+    }
+
+    #[test]
+    fn merge_identical_modules_with_identical_dependencies() {
+        let resources = memory_resources!(
+            "packages/a/vendor/util.lua" => "return function() end",
+            "packages/a/vendor/lib.lua" => "local util = require('./util')\nreturn { util = util }",
+            "packages/b/vendor/util.lua" => "return function() end",
+            "packages/b/vendor/lib.lua" => "local util = require('./util')\nreturn { util = util }",
+            "src/main.lua" => "local libA = require('../packages/a/vendor/lib')\nlocal libB = require('../packages/b/vendor/lib')",
+            ".darklua.json" => DARKLUA_BUNDLE_DEDUPLICATE_CONFIG,
+        );
+
+        process_main(
+            &resources,
+            "merge_identical_modules_with_identical_dependencies",
+        );
+    }
+
+    #[test]
+    fn keep_identical_modules_with_different_dependencies() {
+        let resources = memory_resources!(
+            "packages/a/vendor/util.lua" => "return 'a'",
+            "packages/a/vendor/lib.lua" => "local util = require('./util')\nreturn { util = util }",
+            "packages/b/vendor/util.lua" => "return 'b'",
+            "packages/b/vendor/lib.lua" => "local util = require('./util')\nreturn { util = util }",
+            "src/main.lua" => "local libA = require('../packages/a/vendor/lib')\nlocal libB = require('../packages/b/vendor/lib')",
+            ".darklua.json" => DARKLUA_BUNDLE_DEDUPLICATE_CONFIG,
+        );
+
+        process_main(
+            &resources,
+            "keep_identical_modules_with_different_dependencies",
+        );
+    }
+
+    #[test]
+    fn merge_modules_with_different_formatting() {
+        let resources = memory_resources!(
+            "packages/a/lib.lua" => "local Lib = {}\n\n-- comment\nfunction Lib.run() return 1 end\n\nreturn Lib",
+            "packages/b/lib.lua" => "local Lib = {}\nfunction Lib.run()\n\treturn 1\nend\nreturn Lib\n",
+            "src/main.lua" => "local libA = require('../packages/a/lib')\nlocal libB = require('../packages/b/lib')",
+            ".darklua.json" => DARKLUA_BUNDLE_DEDUPLICATE_CONFIG,
+        );
+
+        process_main(&resources, "merge_modules_with_different_formatting");
+    }
+
+    #[test]
+    fn merge_identical_modules_with_exported_types() {
+        let resources = memory_resources!(
+            "packages/a/lib.luau" => "export type Value = { name: string }\nreturn {}",
+            "packages/b/lib.luau" => "export type Value = { name: string }\nreturn {}",
+            "src/main.lua" => "local libA = require('../packages/a/lib')\nlocal libB = require('../packages/b/lib')\nlocal value: libA.Value & libB.Value = nil",
+            ".darklua.json" => DARKLUA_BUNDLE_DEDUPLICATE_CONFIG,
+        );
+
+        process_main(&resources, "merge_identical_modules_with_exported_types");
+    }
+
+    #[test]
+    fn manifest_lists_merged_modules() {
+        let resources = memory_resources!(
+            "packages/a/lib.lua" => "return true",
+            "packages/b/lib.lua" => "return true",
+            "src/main.lua" => "local libA = require('../packages/a/lib')\nlocal libB = require('../packages/b/lib')",
+            ".darklua.json" => "{ \"rules\": [], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\", \"deduplicate\": true, \"manifest\": \"manifest.json\" } }",
+        );
+
+        process(
+            &resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        insta::assert_snapshot!(
+            "bundle_deduplicate_manifest_lists_merged_modules",
+            resources.get("manifest.json").unwrap()
+        );
+    }
+}
+
+mod shared_chunk {
+    use super::*;
+
+    const DARKLUA_BUNDLE_SHARED_CHUNK_CONFIG: &str = r#"{
+        "rules": [],
+        "generator": "readable",
+        "bundle": {
+            "require_mode": "path",
+            "shared_chunk": {
+                "entries": ["src/client.lua", "src/server.lua"],
+                "output": "out/shared.lua",
+                "require": "require('./shared')",
+            },
+        },
+    }"#;
+
+    fn process_entries(resources: &Resources, snapshot_name: &'static str) {
+        process(resources, Options::new("src").with_output("out"))
+            .unwrap()
+            .result()
+            .unwrap();
+
+        for file in ["client", "server", "shared"] {
+            let code = resources.get(format!("out/{}.lua", file)).unwrap();
+
+            insta::assert_snapshot!(
+                format!("bundle_shared_chunk_{}_{}", snapshot_name, file),
+                code
+            );
+        }
+    }
+
+    fn process_entries_with_errors(resources: &Resources, snapshot_name: &'static str) {
+        let errors = process(resources, Options::new("src").with_output("out"))
+            .unwrap()
+            .result()
+            .unwrap_err();
+
+        let error_display: Vec<_> = errors.into_iter().map(|err| err.to_string()).collect();
+
+        let mut settings = insta::Settings::clone_current();
+        settings.add_filter("\\\\", "/");
+        settings.bind(|| {
+            insta::assert_snapshot!(
+                format!("bundle_shared_chunk_{}", snapshot_name),
+                error_display.join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn module_used_by_both_entries() {
+        let resources = memory_resources!(
+            "modules/format.lua" => "return function(value) return '[' .. value .. ']' end",
+            "modules/client_only.lua" => "return 'client'",
+            "modules/server_only.lua" => "return 'server'",
+            "src/client.lua" => "local format = require('../modules/format')\nlocal name = require('../modules/client_only')\nprint(format(name))",
+            "src/server.lua" => "local format = require('../modules/format')\nlocal name = require('../modules/server_only')\nprint(format(name))",
+            ".darklua.json" => DARKLUA_BUNDLE_SHARED_CHUNK_CONFIG,
+        );
+
+        process_entries(&resources, "module_used_by_both_entries");
+    }
+
+    #[test]
+    fn shared_module_dependencies() {
+        let resources = memory_resources!(
+            "modules/constants.lua" => "return { prefix = '>' }",
+            "modules/format.lua" => "local constants = require('./constants')\nreturn function(value) return constants.prefix .. value end",
+            "src/client.lua" => "local format = require('../modules/format')\nprint(format('client'))",
+            "src/server.lua" => "local format = require('../modules/format')\nprint(format('server'))",
+            ".darklua.json" => DARKLUA_BUNDLE_SHARED_CHUNK_CONFIG,
+        );
+
+        process_entries(&resources, "shared_module_dependencies");
+    }
+
+    #[test]
+    fn entry_without_shared_modules() {
+        let resources = memory_resources!(
+            "modules/format.lua" => "return function(value) return value end",
+            "src/client.lua" => "local format = require('../modules/format')\nprint(format('client'))",
+            "src/server.lua" => "print('server')",
+            ".darklua.json" => DARKLUA_BUNDLE_SHARED_CHUNK_CONFIG,
+        );
+
+        process_entries(&resources, "entry_without_shared_modules");
+    }
+
+    #[test]
+    fn file_that_is_not_an_entry_inlines_shared_modules() {
+        let resources = memory_resources!(
+            "modules/format.lua" => "return function(value) return value end",
+            "src/client.lua" => "local format = require('../modules/format')\nprint(format('client'))",
+            "src/server.lua" => "local format = require('../modules/format')\nprint(format('server'))",
+            "src/tool.lua" => "local format = require('../modules/format')\nprint(format('tool'))",
+            ".darklua.json" => DARKLUA_BUNDLE_SHARED_CHUNK_CONFIG,
+        );
+
+        process_entries(
+            &resources,
+            "file_that_is_not_an_entry_inlines_shared_modules",
+        );
+
+        insta::assert_snapshot!(
+            "bundle_shared_chunk_file_that_is_not_an_entry_inlines_shared_modules_tool",
+            resources.get("out/tool.lua").unwrap()
+        );
+    }
+
+    #[test]
+    fn eager_initialization_of_entries() {
+        let resources = memory_resources!(
+            "modules/format.lua" => "return function(value) return value end",
+            "modules/client_only.lua" => "local format = require('./format')\nreturn format('client')",
+            "src/client.lua" => "local name = require('../modules/client_only')\nprint(name)",
+            "src/server.lua" => "local format = require('../modules/format')\nprint(format('server'))",
+            ".darklua.json" => r#"{
+                "rules": [],
+                "generator": "readable",
+                "bundle": {
+                    "require_mode": "path",
+                    "initialization": "eager",
+                    "shared_chunk": {
+                        "entries": ["src/client.lua", "src/server.lua"],
+                        "output": "out/shared.lua",
+                        "require": "require('./shared')",
+                    },
+                },
+            }"#,
+        );
+
+        process_entries(&resources, "eager_initialization_of_entries");
+    }
+
+    #[test]
+    fn rules_are_applied_to_shared_chunk() {
+        let resources = memory_resources!(
+            "modules/format.lua" => "return function(value: string): string return value end",
+            "src/client.lua" => "local format = require('../modules/format')\nprint(format('client'))",
+            "src/server.lua" => "local format = require('../modules/format')\nprint(format('server'))",
+            ".darklua.json" => r#"{
+                "rules": ["remove_types"],
+                "generator": "readable",
+                "bundle": {
+                    "require_mode": "path",
+                    "shared_chunk": {
+                        "entries": ["src/client.lua", "src/server.lua"],
+                        "output": "out/shared.lua",
+                        "require": "require('./shared')",
+                    },
+                },
+            }"#,
+        );
+
+        process_entries(&resources, "rules_are_applied_to_shared_chunk");
+    }
+
+    #[test]
+    fn invalid_shared_chunk_require() {
+        let resources = memory_resources!(
+            "modules/format.lua" => "return function(value) return value end",
+            "src/client.lua" => "local format = require('../modules/format')",
+            "src/server.lua" => "local format = require('../modules/format')",
+            ".darklua.json" => r#"{
+                "rules": [],
+                "generator": "readable",
+                "bundle": {
+                    "require_mode": "path",
+                    "shared_chunk": {
+                        "entries": ["src/client.lua", "src/server.lua"],
+                        "output": "out/shared.lua",
+                        "require": "local shared = 1",
+                    },
+                },
+            }"#,
+        );
+
+        process_entries_with_errors(&resources, "invalid_shared_chunk_require");
+    }
+}
+
+mod source_map {
+    use super::*;
+
+    fn process_main_source_map(
+        resources: &Resources,
+        generator: &'static str,
+        snapshot_name: &'static str,
+    ) {
+        resources
+            .write(
+                ".darklua.json",
+                &format!(
+                    "{{ \"rules\": [], \"generator\": {{ \"name\": \"{}\", \"source_map\": true }}, \"bundle\": {{ \"require_mode\": \"path\" }} }}",
+                    generator
+                ),
+            )
+            .unwrap();
+
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out/main.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let code = resources.get("out/main.lua").unwrap();
+        let source_map = resources.get("out/main.lua.map").unwrap();
+
+        insta::assert_snapshot!(format!("bundle_source_map_{}_code", snapshot_name), code);
+        insta::assert_snapshot!(format!("bundle_source_map_{}", snapshot_name), source_map);
+    }
+
+    #[test]
+    fn readable_bundle_maps_modules_to_their_files() {
+        let resources = memory_resources!(
+            "src/format.lua" => "local prefix = '<'\n\nreturn function(value)\n    return prefix .. value\nend",
+            "src/util.lua" => "local format = require('./format')\nreturn { format = format }",
+            "src/main.lua" => "local util = require('./util')\n\nprint(util.format('a'))",
+        );
+
+        process_main_source_map(&resources, "readable", "readable_nested_modules");
+    }
+
+    #[test]
+    fn dense_bundle_maps_modules_to_their_files() {
+        let resources = memory_resources!(
+            "src/format.lua" => "local prefix = '<'\n\nreturn function(value)\n    return prefix .. value\nend",
+            "src/util.lua" => "local format = require('./format')\nreturn { format = format }",
+            "src/main.lua" => "local util = require('./util')\n\nprint(util.format('a'))",
+        );
+
+        process_main_source_map(&resources, "dense", "dense_nested_modules");
+    }
+
+    #[test]
+    fn bundle_without_requires() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local value = 'a'\nprint(value)",
+        );
+
+        process_main_source_map(&resources, "readable", "without_requires");
+    }
+}
+
+mod conditional_compilation {
+    use super::*;
+
+    const DARKLUA_BUNDLE_CONDITIONAL_COMPILATION_CONFIG: &str = "{ \"rules\": [{ \"rule\": \"conditional_compilation\", \"defines\": [\"PLATFORM=lune\"] }], \"generator\": \"readable\", \"bundle\": { \"require_mode\": \"path\" } }";
+
+    fn process_main(resources: &Resources, snapshot_name: &'static str) {
+        process(
+            resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        insta::assert_snapshot!(
+            format!("bundle_conditional_compilation_{}", snapshot_name),
+            main
+        );
+    }
+
+    #[test]
+    fn remove_inactive_statements_of_bundled_module() {
+        let resources = memory_resources!(
+            "src/platform.lua" => "--#if PLATFORM == 'roblox'\nlocal name = 'roblox'\n--#else\nlocal name = 'other'\n--#endif\nreturn name",
+            "src/main.lua" => "local platform = require('./platform')\nprint(platform)",
+            ".darklua.json" => DARKLUA_BUNDLE_CONDITIONAL_COMPILATION_CONFIG,
+        );
+
+        process_main(&resources, "remove_inactive_statements_of_bundled_module");
+    }
+
+    #[test]
+    fn skip_require_in_inactive_branch() {
+        let resources = memory_resources!(
+            "src/lune_only.lua" => "return 'lune'",
+            "src/main.lua" => "--#if PLATFORM == 'roblox'\nlocal m = require('./roblox_only')\n--#else\nlocal m = require('./lune_only')\n--#endif\nprint(m)",
+            ".darklua.json" => DARKLUA_BUNDLE_CONDITIONAL_COMPILATION_CONFIG,
+        );
+
+        process_main(&resources, "skip_require_in_inactive_branch");
+    }
+
+    #[test]
+    fn skip_require_in_inactive_branch_of_bundled_module() {
+        let resources = memory_resources!(
+            "src/lune_only.lua" => "return 'lune'",
+            "src/platform.lua" => "--#if PLATFORM == 'roblox'\nlocal m = require('./roblox_only')\n--#else\nlocal m = require('./lune_only')\n--#endif\nreturn m",
+            "src/main.lua" => "local platform = require('./platform')\nprint(platform)",
+            ".darklua.json" => DARKLUA_BUNDLE_CONDITIONAL_COMPILATION_CONFIG,
+        );
+
+        process_main(
+            &resources,
+            "skip_require_in_inactive_branch_of_bundled_module",
+        );
     }
 }
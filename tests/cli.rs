@@ -146,6 +146,11 @@ impl Context {
         self
     }
 
+    pub fn expect_failure(mut self) -> Self {
+        self.command.assert().failure();
+        self
+    }
+
     pub fn replace_snapshot_content(
         mut self,
         matcher: impl Into<String>,
@@ -204,6 +209,14 @@ fn snapshot_convert_help_command() {
         .snapshot_command("convert_help_command");
 }
 
+#[test]
+fn snapshot_format_help_command() {
+    Context::default()
+        .arg("format")
+        .arg("--help")
+        .snapshot_command("format_help_command");
+}
+
 #[test]
 fn run_minify_command() {
     Context::default()
@@ -382,3 +395,265 @@ fn run_convert_command_errors_when_unrecognized_extension() {
         .replace_duration_labels()
         .snapshot_command("run_convert_command_errors_when_unrecognized_extension");
 }
+
+#[test]
+fn run_format_command() {
+    Context::default()
+        .write_file(
+            "src/init.lua",
+            "-- entry point\nlocal value=1+1 return {value=value}\n",
+        )
+        .arg("format")
+        .arg("src")
+        .replace_duration_labels()
+        .snapshot_command("run_format_command")
+        .snapshot_file("run_format_command_init", "src/init.lua");
+}
+
+#[test]
+fn run_format_with_column_span_command() {
+    Context::default()
+        .write_file(
+            "src/init.lua",
+            "return {first=true,second=false,third=nil}\n",
+        )
+        .arg("format")
+        .arg("src")
+        .arg("--column-span")
+        .arg("20")
+        .replace_duration_labels()
+        .snapshot_command("run_format_with_column_span_command")
+        .snapshot_file("run_format_with_column_span_command_init", "src/init.lua");
+}
+
+#[test]
+fn run_format_command_with_readable_style_from_config() {
+    Context::default()
+        .write_file(
+            ".darklua.json",
+            r#"{ "generator": { "name": "readable", "style": { "quote_style": "double", "indent_style": "tabs" } } }"#,
+        )
+        .write_file("src/init.lua", "if a then print('a') end\n")
+        .arg("format")
+        .arg("src")
+        .replace_duration_labels()
+        .expect_success()
+        .snapshot_file("run_format_command_with_readable_style_from_config_init", "src/init.lua");
+}
+
+#[test]
+fn run_format_command_with_custom_config() {
+    Context::default()
+        .write_file(
+            "format.json",
+            r#"{ "generator": { "name": "readable", "column_span": 20, "style": { "trailing_commas": false } } }"#,
+        )
+        .write_file("src/init.lua", "return {first=true,second=false}\n")
+        .arg("format")
+        .arg("src")
+        .arg("--config")
+        .arg("format.json")
+        .expect_success()
+        .snapshot_file("run_format_command_with_custom_config_init", "src/init.lua");
+}
+
+#[test]
+fn run_format_check_command_on_unformatted_files() {
+    Context::default()
+        .write_file("src/init.lua", "return 1+1\n")
+        .write_file("src/formatted.lua", "return 1 + 1\n")
+        .arg("format")
+        .arg("src")
+        .arg("--check")
+        .replace_backslashes()
+        .expect_failure()
+        .snapshot_command("run_format_check_command_on_unformatted_files")
+        .snapshot_file(
+            "run_format_check_command_on_unformatted_files_init",
+            "src/init.lua",
+        );
+}
+
+#[test]
+fn run_format_check_command_on_formatted_files() {
+    Context::default()
+        .write_file("src/init.lua", "-- entry point\nreturn 1 + 1\n")
+        .arg("format")
+        .arg("src")
+        .arg("--check")
+        .expect_success()
+        .snapshot_command("run_format_check_command_on_formatted_files");
+}
+
+#[test]
+fn run_format_command_on_current_directory() {
+    Context::default()
+        .write_file("src/init.lua", "local value=1+1 return {value=value}\n")
+        .arg("format")
+        .arg(".")
+        .replace_duration_labels()
+        .expect_success()
+        .snapshot_command("run_format_command_on_current_directory")
+        .snapshot_file(
+            "run_format_command_on_current_directory_init",
+            "src/init.lua",
+        );
+}
+
+#[test]
+fn run_format_check_command_on_current_directory() {
+    Context::default()
+        .write_file("src/init.lua", "return 1+1\n")
+        .arg("format")
+        .arg(".")
+        .arg("--check")
+        .replace_backslashes()
+        .expect_failure()
+        .snapshot_command("run_format_check_command_on_current_directory");
+}
+
+#[test]
+fn run_format_command_keeps_comments_around_blocks_and_arguments() {
+    Context::default()
+        .write_file(
+            "src/init.lua",
+            "if a then -- after then\n  call(a, -- arg comment\n  b)\n  -- before end\nend\n",
+        )
+        .arg("format")
+        .arg("src")
+        .expect_success()
+        .snapshot_file(
+            "run_format_command_keeps_comments_around_blocks_and_arguments_init",
+            "src/init.lua",
+        );
+}
+
+#[test]
+fn run_format_command_errors_when_comments_would_be_lost() {
+    Context::default()
+        .write_file("src/init.lua", "local a = 1 + -- inside\n  2\nreturn a\n")
+        .arg("format")
+        .arg("src")
+        .replace_backslashes()
+        .expect_failure()
+        .snapshot_command("run_format_command_errors_when_comments_would_be_lost")
+        .snapshot_file(
+            "run_format_command_errors_when_comments_would_be_lost_init",
+            "src/init.lua",
+        );
+}
+
+#[test]
+fn run_format_check_command_errors_when_comments_would_be_lost() {
+    Context::default()
+        .write_file("src/init.lua", "return 1 + --[[ one ]] 1\n")
+        .arg("format")
+        .arg("src")
+        .arg("--check")
+        .replace_backslashes()
+        .expect_failure()
+        .snapshot_command("run_format_check_command_errors_when_comments_would_be_lost");
+}
+
+#[test]
+fn snapshot_ast_help_command() {
+    Context::default()
+        .arg("ast")
+        .arg("--help")
+        .snapshot_command("ast_help_command");
+}
+
+#[test]
+fn run_ast_export_command() {
+    Context::default()
+        .write_file("src/init.lua", "local value = 1\nreturn value\n")
+        .arg("ast")
+        .arg("export")
+        .arg("src/init.lua")
+        .replace_duration_labels()
+        .expect_success()
+        .snapshot_command("run_ast_export_command");
+}
+
+#[test]
+fn run_ast_export_command_with_tokens() {
+    Context::default()
+        .write_file("src/init.lua", "-- comment\nreturn true\n")
+        .arg("ast")
+        .arg("export")
+        .arg("src/init.lua")
+        .arg("ast.json")
+        .arg("--tokens")
+        .replace_duration_labels()
+        .expect_success()
+        .snapshot_command("run_ast_export_command_with_tokens")
+        .snapshot_file("run_ast_export_command_with_tokens_json", "ast.json");
+}
+
+#[test]
+fn run_ast_import_command() {
+    Context::default()
+        .write_file(
+            "ast.json",
+            r#"{"version":1,"block":{"statements":[],"last_statement":{"return":{"expressions":[{"true":null}]}}}}"#,
+        )
+        .arg("ast")
+        .arg("import")
+        .arg("ast.json")
+        .arg("out.lua")
+        .replace_duration_labels()
+        .expect_success()
+        .snapshot_command("run_ast_import_command")
+        .snapshot_file("run_ast_import_command_out", "out.lua");
+}
+
+#[test]
+fn run_ast_import_command_with_dense_format() {
+    Context::default()
+        .write_file(
+            "ast.json",
+            r#"{"version":1,"block":{"statements":[{"local_assign":{"variables":[{"name":{"name":"a"}}],"attributes":[null],"values":[{"true":null}]}}],"last_statement":{"return":{"expressions":[{"identifier":{"name":"a"}}]}}}}"#,
+        )
+        .arg("ast")
+        .arg("import")
+        .arg("ast.json")
+        .arg("out.lua")
+        .arg("--format")
+        .arg("dense")
+        .expect_success()
+        .snapshot_file("run_ast_import_command_with_dense_format_out", "out.lua");
+}
+
+#[test]
+fn run_ast_import_command_errors_on_invalid_json() {
+    Context::default()
+        .write_file(
+            "ast.json",
+            r#"{"version":1,"block":{"statements":[{"oops":{}}]}}"#,
+        )
+        .arg("ast")
+        .arg("import")
+        .arg("ast.json")
+        .replace_duration_labels()
+        .expect_failure()
+        .snapshot_command("run_ast_import_command_errors_on_invalid_json");
+}
+
+#[test]
+fn run_ast_import_command_errors_on_deeply_nested_json() {
+    let depth = 100_000;
+    let json = format!(
+        r#"{{"version":1,"block":{{"statements":[],"last_statement":{{"return":{{"expressions":[{}{}{}]}}}}}}}}"#,
+        r#"{"parenthese":{"expression":"#.repeat(depth),
+        r#"{"true":null}"#,
+        "}}".repeat(depth)
+    );
+
+    Context::default()
+        .write_file("ast.json", &json)
+        .arg("ast")
+        .arg("import")
+        .arg("ast.json")
+        .expect_failure()
+        .snapshot_command("run_ast_import_command_errors_on_deeply_nested_json");
+}
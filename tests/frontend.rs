@@ -172,6 +172,249 @@ fn use_default_json5_config_in_place() {
     assert_eq!(resources.get("src/test.lua").unwrap(), "return 'Hello'");
 }
 
+#[test]
+fn target_accepts_code_once_rules_removed_unsupported_syntax() {
+    let resources = memory_resources!(
+        "src/test.lua" => "local a: number = 1\na += 1\nreturn a",
+        ".darklua.json" => "{ target: 'lua51', generator: 'dense', rules: ['remove_types', 'remove_compound_assignment'] }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(
+        resources.get("src/test.lua").unwrap(),
+        "local a=1 a=a+1 return a"
+    );
+}
+
+#[test]
+fn conditional_compilation_with_dense_generator() {
+    let resources = memory_resources!(
+        "src/test.lua" => "--#if DEBUG\nprint('debug')\n--#else\nprint('release')\n--#endif\n",
+        ".darklua.json" => "{ generator: 'dense', rules: [{ rule: 'conditional_compilation', defines: ['DEBUG'] }] }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), "print('debug')");
+}
+
+fn process_project(resources: &Resources) {
+    process(
+        resources,
+        Options::new("project").with_configuration_at("project/.darklua.json"),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+}
+
+#[test]
+fn apply_rules_only_to_included_paths() {
+    let resources = memory_resources!(
+        "project/src/main.lua" => "local value: number = 1\nreturn value",
+        "project/vendor/module.lua" => "local value: number = 1\nreturn value",
+        "project/.darklua.json" => "{ generator: 'dense', rules: [{ rule: 'remove_types', include: ['src/**'] }] }",
+    );
+
+    process_project(&resources);
+
+    assert_eq!(
+        resources.get("project/src/main.lua").unwrap(),
+        "local value=1 return value"
+    );
+    assert_eq!(
+        resources.get("project/vendor/module.lua").unwrap(),
+        "local value:number=1 return value"
+    );
+}
+
+#[test]
+fn skip_rules_for_excluded_paths() {
+    let resources = memory_resources!(
+        "project/src/main.lua" => "local value = 1\nreturn value",
+        "project/vendor/module.lua" => "local value = 1\nreturn value",
+        "project/.darklua.json" => "{ generator: 'dense', rules: [{ rule: 'rename_variables', exclude: ['vendor/**'] }] }",
+    );
+
+    process_project(&resources);
+
+    assert_eq!(
+        resources.get("project/src/main.lua").unwrap(),
+        "local a=1 return a"
+    );
+    assert_eq!(
+        resources.get("project/vendor/module.lua").unwrap(),
+        "local value=1 return value"
+    );
+}
+
+#[test]
+fn skip_only_filtered_rules() {
+    let resources = memory_resources!(
+        "project/src/main.lua" => "local value: number = 1\nreturn value",
+        "project/vendor/module.lua" => "local value: number = 1\nreturn value",
+        "project/.darklua.json" => "{ generator: 'dense', rules: ['remove_types', { rule: 'rename_variables', include: ['src/**'], exclude: ['src/generated/**'] }] }",
+    );
+
+    process_project(&resources);
+
+    assert_eq!(
+        resources.get("project/src/main.lua").unwrap(),
+        "local a=1 return a"
+    );
+    assert_eq!(
+        resources.get("project/vendor/module.lua").unwrap(),
+        "local value=1 return value"
+    );
+}
+
+mod ignore_directives {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn process_with_rules(code: &'static str, rules: &'static str) -> String {
+        let resources = memory_resources!(
+            "src/test.lua" => code,
+            ".darklua.json" => rules,
+        );
+
+        process(&resources, Options::new("src"))
+            .unwrap()
+            .result()
+            .unwrap();
+
+        resources.get("src/test.lua").unwrap()
+    }
+
+    #[test]
+    fn ignore_next_line_for_a_rule() {
+        assert_eq!(
+            process_with_rules(
+                "local a = 1 + 1\n-- darklua-ignore-next-line compute_expression\nlocal b = 2 + 2\nreturn a, b",
+                "{ generator: 'dense', rules: ['compute_expression'] }",
+            ),
+            "local a=2 local b=2+2 return a,b"
+        );
+    }
+
+    #[test]
+    fn ignore_next_line_for_all_rules() {
+        assert_eq!(
+            process_with_rules(
+                "-- darklua-ignore-next-line\nlocal a = 1 + 1\nlocal b = 2 + 2\nreturn a, b",
+                "{ generator: 'dense', rules: ['compute_expression'] }",
+            ),
+            "local a=1+1 local b=4 return a,b"
+        );
+    }
+
+    #[test]
+    fn ignore_next_line_does_not_apply_to_other_rules() {
+        assert_eq!(
+            process_with_rules(
+                "-- darklua-ignore-next-line remove_types\nlocal a = 1 + 1\nreturn a",
+                "{ generator: 'dense', rules: ['compute_expression'] }",
+            ),
+            "local a=2 return a"
+        );
+    }
+
+    #[test]
+    fn disable_and_enable_region() {
+        assert_eq!(
+            process_with_rules(
+                "local a = 1 + 1\n-- darklua-disable compute_expression\nlocal b = 2 + 2\nlocal c = 3 + 3\n-- darklua-enable compute_expression\nreturn a, b, c, 4 + 4",
+                "{ generator: 'dense', rules: ['compute_expression'] }",
+            ),
+            "local a=2 local b=2+2 local c=3+3 return a,b,c,8"
+        );
+    }
+
+    #[test]
+    fn ignore_last_statement() {
+        assert_eq!(
+            process_with_rules(
+                "local a = 1 + 1\n-- darklua-ignore-next-line\nreturn a + 1",
+                "{ generator: 'dense', rules: ['compute_expression'] }",
+            ),
+            "local a=2 return a+1"
+        );
+    }
+
+    #[test]
+    fn ignore_statement_in_nested_block() {
+        assert_eq!(
+            process_with_rules(
+                "local function fn()\n\tlocal a = 1 + 1\n\t-- darklua-ignore-next-line\n\tlocal b = 2 + 2\n\treturn a + b\nend\nreturn fn",
+                "{ generator: 'dense', rules: ['compute_expression'] }",
+            ),
+            "local function fn()local a=2 local b=2+2 return a+b end return fn"
+        );
+    }
+
+    #[test]
+    fn rename_variables_keeps_ignored_declaration() {
+        assert_eq!(
+            process_with_rules(
+                "local value = 1\n-- darklua-ignore-next-line rename_variables\nlocal important = value\nreturn important + value",
+                "{ generator: 'dense', rules: ['rename_variables'] }",
+            ),
+            "local a=1 local important=a return important+a"
+        );
+    }
+
+    #[test]
+    fn remove_unused_variable_keeps_disabled_declarations() {
+        assert_eq!(
+            process_with_rules(
+                "local a = 1\n-- darklua-disable remove_unused_variable\nlocal b = 2\n-- darklua-enable\nlocal c = a\nreturn nil",
+                "{ generator: 'dense', rules: ['remove_unused_variable'] }",
+            ),
+            "local b=2 return nil"
+        );
+    }
+
+    #[test]
+    fn remove_unused_variable_keeps_variables_used_in_ignored_statement() {
+        assert_eq!(
+            process_with_rules(
+                "local a = 1\n-- darklua-ignore-next-line remove_unused_variable\nlocal b = a\nreturn nil",
+                "{ generator: 'dense', rules: ['remove_unused_variable'] }",
+            ),
+            "local a=1 local b=a return nil"
+        );
+    }
+
+    #[test]
+    fn ignored_assignment_keeps_variable_mutable() {
+        assert_eq!(
+            process_with_rules(
+                "local DEBUG = false\n-- darklua-ignore-next-line\nDEBUG = true\nif DEBUG then print('debug') end",
+                "{ generator: 'dense', rules: ['propagate_constants', 'remove_unused_if_branch'] }",
+            ),
+            "local DEBUG=false DEBUG=true if DEBUG then print('debug')end"
+        );
+    }
+
+    #[test]
+    fn ignored_closure_keeps_captured_variable_mutable() {
+        assert_eq!(
+            process_with_rules(
+                "local count = 0\n-- darklua-ignore-next-line\nonEvent(function() count = count + 1 end)\nreturn count",
+                "{ generator: 'dense', rules: ['propagate_constants'] }",
+            ),
+            "local count=0 onEvent(function()count=count+1 end)return count"
+        );
+    }
+}
+
 mod errors {
     use std::path::{Path, PathBuf};
 
@@ -273,4 +516,247 @@ mod errors {
             Options::new("src"),
         );
     }
+
+    #[test]
+    fn snapshot_unsupported_target() {
+        let resources = memory_resources!(
+            "src/init.lua" => "local value = 0\nwhile true do\n\tvalue += 1\n\tif value > 10 then\n\t\tcontinue\n\tend\nend\nreturn if value then `{value}` else nil\n",
+            ".darklua.json" => "{ target: 'lua51', rules: [] }",
+        );
+
+        assert_errors("unsupported_target", &resources, Options::new("src"));
+    }
+}
+
+mod source_map {
+    use super::*;
+
+    fn process_with_generator(code: &'static str, generator: &'static str) -> (String, String) {
+        let resources = memory_resources!(
+            "src/test.lua" => code,
+            ".darklua.json" => generator,
+        );
+
+        process(
+            &resources,
+            Options::new("src/test.lua").with_output("out/test.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        (
+            resources.get("out/test.lua").unwrap(),
+            resources.get("out/test.lua.map").unwrap(),
+        )
+    }
+
+    #[test]
+    fn dense_generator_writes_source_map() {
+        let (code, source_map) = process_with_generator(
+            "local value = 1\n\nlocal function print_value()\n    print(value)\nend\nreturn print_value",
+            "{ rules: [], generator: { name: 'dense', source_map: true } }",
+        );
+
+        insta::assert_snapshot!("dense_generator_source_map_code", code);
+        insta::assert_snapshot!("dense_generator_source_map", source_map);
+    }
+
+    #[test]
+    fn readable_generator_writes_source_map() {
+        let (code, source_map) = process_with_generator(
+            "local value = 1\n\nlocal function print_value()\n    print(value)\nend\nreturn print_value",
+            "{ rules: [], generator: { name: 'readable', source_map: true } }",
+        );
+
+        insta::assert_snapshot!("readable_generator_source_map_code", code);
+        insta::assert_snapshot!("readable_generator_source_map", source_map);
+    }
+
+    #[test]
+    fn dense_generator_maps_wrapped_lines() {
+        let (code, source_map) = process_with_generator(
+            "local a, b, c = 'first', 'second', 'third'\nprint(a, b, c)",
+            "{ rules: [], generator: { name: 'dense', column_span: 20, source_map: true } }",
+        );
+
+        insta::assert_snapshot!("dense_generator_wrapped_lines_source_map_code", code);
+        insta::assert_snapshot!("dense_generator_wrapped_lines_source_map", source_map);
+    }
+
+    #[test]
+    fn source_map_is_not_written_by_default() {
+        let resources = memory_resources!(
+            "src/test.lua" => ANY_CODE,
+            ".darklua.json" => "{ generator: 'dense' }",
+        );
+
+        process(
+            &resources,
+            Options::new("src/test.lua").with_output("out/test.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        assert!(resources.get("out/test.lua.map").is_err());
+    }
+}
+
+mod preserve_comments {
+    use super::*;
+
+    fn process_with_comments(code: &'static str, rules: &'static str) -> String {
+        let resources = memory_resources!(
+            "src/test.lua" => code,
+            ".darklua.json" => rules,
+        );
+
+        process(&resources, Options::new("src/test.lua"))
+            .unwrap()
+            .result()
+            .unwrap();
+
+        resources.get("src/test.lua").unwrap()
+    }
+
+    macro_rules! test_comments {
+        ($($name:ident ($code:literal)),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    let output = process_with_comments(
+                        $code,
+                        "{ rules: [], generator: { name: 'readable', preserve_comments: true } }",
+                    );
+
+                    insta::assert_snapshot!(
+                        concat!("readable_preserve_comments_", stringify!($name)),
+                        output
+                    );
+                }
+            )*
+        };
+    }
+
+    test_comments!(
+        leading_statement_comment("-- print a value\nprint('a')"),
+        trailing_statement_comment("local a = 1 -- the value\nreturn a"),
+        trailing_comment_after_semicolon("print('a'); -- first\nprint('b')"),
+        comments_in_nested_block("local function f()\n        -- indented comment\n  return 1 -- one\nend"),
+        comment_before_return("local a = 1\n\n--[[ multiline\ncomment ]]\nreturn a"),
+        comment_at_end_of_file("print('a')\n-- end of file"),
+        comments_in_table("local t = { -- config\n  -- first entry\n  a = 1, -- one\n  [\"b\"] = 2,\n  3 -- three\n  -- end of table\n}"),
+        small_table_with_comment("local t = { 1, 2 } -- small"),
+        nested_table_comments("return {\n  -- inner\n  inner = {\n    -- value\n    value = true,\n  },\n}"),
+        comments_around_if_branches("if a then -- after then\n  print(1)\n  -- before elseif\nelseif b then -- after elseif\n  -- only comment\nelse -- after else\n  print(2)\n  -- before end\nend"),
+        comments_in_empty_do("do -- after do\nend"),
+        comments_around_loops("while x do -- after do\nend\nfor i = 1, 10 do\n  -- inside\nend\nrepeat -- after repeat\n  x = x + 1\n  -- before until\nuntil x > 10"),
+        comments_in_function_body("local function f() -- after parameters\n  return 1\n  -- before end\nend"),
+        comments_in_call_arguments("call(a, -- first argument\n  -- second argument\n  b)"),
+        comments_in_function_parameters("local function f(a, -- first\n  b, ... -- variadic\n) end"),
+    );
+
+    #[test]
+    fn comments_are_dropped_by_default() {
+        let output = process_with_comments(
+            "-- print a value\nprint('a') -- trailing",
+            "{ rules: [], generator: 'readable' }",
+        );
+
+        pretty_assertions::assert_eq!(output, "print('a')\n");
+    }
+
+    #[test]
+    fn comments_are_kept_after_rules() {
+        let output = process_with_comments(
+            "-- a typed value\nlocal value: number = 1 -- one\n\n-- the result\nreturn value",
+            "{ rules: ['remove_types'], generator: { name: 'readable', preserve_comments: true } }",
+        );
+
+        insta::assert_snapshot!("readable_preserve_comments_after_rules", output);
+    }
+}
+
+mod readable_style {
+    use super::*;
+
+    fn process_with_style(code: &'static str, style: &'static str) -> String {
+        let resources = memory_resources!(
+            "src/test.lua" => code,
+        );
+        resources
+            .write(
+                ".darklua.json",
+                &format!(
+                    "{{ rules: [], generator: {{ name: 'readable', style: {} }} }}",
+                    style
+                ),
+            )
+            .unwrap();
+
+        process(&resources, Options::new("src/test.lua"))
+            .unwrap()
+            .result()
+            .unwrap();
+
+        resources.get("src/test.lua").unwrap()
+    }
+
+    macro_rules! test_style {
+        ($($name:ident ($code:literal, $style:literal)),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    let output = process_with_style($code, $style);
+
+                    insta::assert_snapshot!(
+                        concat!("readable_style_", stringify!($name)),
+                        output
+                    );
+                }
+            )*
+        };
+    }
+
+    const NESTED_CODE: &str = "local function f()\n  if true then\n    return { a = 1, b = 2, c = { 1, 2, 3, 4 } }\n  end\nend";
+
+    test_style!(
+        indent_with_tabs(
+            "local function f()\n  if true then\n    return { a = 1, b = 2 }\n  end\nend",
+            "{ indent_style: 'tabs' }"
+        ),
+        indent_width_two(
+            "local function f()\n  if true then\n    return { a = 1, b = 2 }\n  end\nend",
+            "{ indent_width: 2 }"
+        ),
+        double_quotes("print('a', \"b\", 'it\\'s', 'say \"hi\"')", "{ quote_style: 'double' }"),
+        prefer_double_quotes("print('a', \"b\", 'it\\'s', 'say \"hi\"')", "{ quote_style: 'prefer_double' }"),
+        single_quotes("print('a', \"b\", 'it\\'s', 'say \"hi\"')", "{ quote_style: 'single' }"),
+        without_trailing_commas("local t = { a = 1, b = 2, c = { 1, 2, 3, 4 } }", "{ trailing_commas: false }"),
+        always_call_parentheses("require 'module'\nprint { value = true }", "{ call_parentheses: 'always' }"),
+        omit_call_parentheses("require('module')\nprint({ value = true })\nprint('a', 'b')", "{ call_parentheses: 'omit' }"),
+        function_blank_lines(
+            "local a = 1\nlocal function f() end\nlocal function g()\n  local function nested() end\n  local function nested2() end\nend\nfunction h() end\nreturn f",
+            "{ function_blank_lines: 2 }"
+        ),
+    );
+
+    #[test]
+    fn default_style_is_unchanged() {
+        let resources = memory_resources!(
+            "src/test.lua" => NESTED_CODE,
+            ".darklua.json" => "{ rules: [], generator: 'readable' }",
+        );
+
+        process(&resources, Options::new("src/test.lua"))
+            .unwrap()
+            .result()
+            .unwrap();
+
+        pretty_assertions::assert_eq!(
+            resources.get("src/test.lua").unwrap(),
+            process_with_style(NESTED_CODE, "{}")
+        );
+    }
 }
@@ -0,0 +1,110 @@
+use darklua_core::rules::{ConditionalCompilation, Rule};
+
+test_rule_with_tokens!(
+    conditional_compilation,
+    ConditionalCompilation::default()
+        .with_define("DEBUG")
+        .with_value("PLATFORM", "roblox"),
+    keep_active_statement("--#if DEBUG\nprint('debug')\n--#endif\n") => "\nprint('debug')\n\n",
+    remove_inactive_statement("--#if not DEBUG\nprint('release')\n--#endif\n") => "\n",
+    remove_undefined_variable("--#if TEST\nprint('test')\n--#endif\nprint('end')") => "\n\n\nprint('end')",
+    keep_else_branch("--#if TEST\nlocal a = 1\n--#else\nlocal a = 2\n--#endif\nreturn a") => "\n\n\nlocal a = 2\n\nreturn a",
+    keep_elseif_branch("--#if PLATFORM == 'lune'\nlocal fs = require('@lune/fs')\n--#elseif PLATFORM == 'roblox'\nlocal fs = require(script.fs)\n--#else\nlocal fs = nil\n--#endif\nreturn fs")
+        => "\n\n\nlocal fs = require(script.fs)\n\n\n\nreturn fs",
+    remove_nested_directive("--#if TEST\n--#if DEBUG\nprint('debug')\n--#endif\nprint('test')\n--#endif\nprint('end')") => "\n\n\n\n\n\nprint('end')",
+    remove_inside_function("local function fn()\n\t--#if TEST\n\tprint('test')\n\t--#endif\n\treturn 1\nend") => "local function fn()\n\t\n\t\n\nreturn 1\nend",
+    remove_return_statement("local a = 1\n--#if TEST\nreturn a\n--#endif\n") => "local a = 1\n\n",
+    remove_type_declaration("--#if TEST\ntype Value = string\n--#else\ntype Value = number\n--#endif\n") => "\n\n\ntype Value = number\n\n",
+    keep_unknown_directive_comment("--#region\nprint('hello')") => "--#region\nprint('hello')",
+    remove_statements_before_endif_on_same_line(
+        "--#if TEST\nprint('test') print('also') --#endif\nprint('end')"
+    ) => "\n\nprint('end')",
+    keep_statement_before_if_on_same_line("print('start') --#if TEST\nprint('test')\n--#endif\n")
+        => "print('start') \n\n",
+    remove_inside_else_block("if value then\n\tprint('a')\nelse\n\t--#if TEST\n\tprint('b')\n\t--#endif\nend")
+        => "if value then\n\tprint('a')\nelse\n\t\n\n\nend",
+    remove_inside_function_expression("local fn = function()\n\t--#if TEST\n\tprint('test')\n\t--#endif\nend")
+        => "local fn = function()\n\t\n\n\nend",
+);
+
+test_rule_without_effects!(
+    ConditionalCompilation::default(),
+    no_directives("print('hello')"),
+);
+
+fn process_code(code: &str) -> Result<(), String> {
+    let mut block = darklua_core::Parser::default()
+        .preserve_tokens()
+        .parse(code)
+        .unwrap();
+    let resources = darklua_core::Resources::from_memory();
+    let context =
+        darklua_core::rules::ContextBuilder::new("src/test.lua", &resources, code).build();
+
+    ConditionalCompilation::default().process(&mut block, &context)
+}
+
+#[test]
+fn missing_endif_error() {
+    pretty_assertions::assert_eq!(
+        process_code("print('a')\n--#if DEBUG\nprint('b')"),
+        Err("missing `--#endif` for the `--#if` directive at line 2".to_owned())
+    );
+}
+
+#[test]
+fn endif_without_if_error() {
+    pretty_assertions::assert_eq!(
+        process_code("--#endif\nprint('a')"),
+        Err("unexpected `--#endif` without a matching `--#if` at line 1".to_owned())
+    );
+}
+
+#[test]
+fn else_after_else_error() {
+    pretty_assertions::assert_eq!(
+        process_code("--#if DEBUG\nlocal a\n--#else\nlocal b\n--#else\nlocal c\n--#endif\n"),
+        Err("unexpected second `--#else` at line 5".to_owned())
+    );
+}
+
+#[test]
+fn invalid_condition_error() {
+    pretty_assertions::assert_eq!(
+        process_code("--#if DEBUG.value\nprint('a')\n--#endif\n"),
+        Err("unable to evaluate condition `DEBUG.value`".to_owned())
+    );
+}
+
+#[test]
+fn directive_inside_table_error() {
+    pretty_assertions::assert_eq!(
+        process_code("local t = {\n--#if DEBUG\ndebug = true,\n--#endif\n}"),
+        Err("unexpected `--#if` inside a statement at line 2 (directives must be placed between statements)".to_owned())
+    );
+}
+
+#[test]
+fn directive_inside_if_condition_error() {
+    pretty_assertions::assert_eq!(
+        process_code("if value\n--#if DEBUG\nor debug\n--#endif\nthen end"),
+        Err("unexpected `--#if` inside a statement at line 2 (directives must be placed between statements)".to_owned())
+    );
+}
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'conditional_compilation',
+        defines: ['DEBUG', 'PLATFORM=roblox'],
+        env: ['DARKLUA_CONDITIONAL_COMPILATION_TEST_VARIABLE'],
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'conditional_compilation'").unwrap();
+}
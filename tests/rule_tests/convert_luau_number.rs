@@ -0,0 +1,53 @@
+use darklua_core::rules::{ConvertLuauNumber, Rule};
+
+test_rule!(
+    convert_luau_number,
+    ConvertLuauNumber::default(),
+    binary_number("return 0b101") => "return 5",
+    binary_number_uppercase("return 0B1111") => "return 15",
+    binary_number_with_separators("return 0b1111_0000") => "return 240",
+    largest_exact_binary_number(
+        "return 0b100000000000000000000000000000000000000000000000000000"
+    ) => "return 9007199254740992",
+    large_binary_number(
+        "return 0b1111111111111111111111111111111111111111111111111111111111111111"
+    ) => "return 0xffffffffffffffff",
+    decimal_number_with_separators("return 1_000_000") => "return 1000000",
+    hex_number_with_separators("return 0xFFFF_FFFF") => "return 0xFFFFFFFF",
+);
+
+test_rule_with_tokens!(
+    convert_luau_number_with_tokens,
+    ConvertLuauNumber::default(),
+    binary_number("return 0b101") => "return 5",
+    binary_number_with_comments("return --[[ mask ]] 0b1010 -- ten") => "return --[[ mask ]] 10 -- ten",
+    large_binary_number(
+        "return 0b100000000000000000000000000000000000000000000000000001"
+    ) => "return 0x20000000000001",
+    decimal_number_with_separators("local value = 1_000_000") => "local value = 1000000",
+    float_number_with_separators("local value = 1_000.000_5") => "local value = 1000.0005",
+    large_hex_number_with_separators("return 0xFFFF_FFFF_FFFF_FFFF") => "return 0xFFFFFFFFFFFFFFFF",
+    number_without_separators("return 0xFF + 10") => "return 0xFF + 10",
+);
+
+test_rule_without_effects!(
+    ConvertLuauNumber::default(),
+    decimal_number("return 1000"),
+    hex_number("return 0xABC"),
+    float_number("return 0.5e10"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_luau_number',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'convert_luau_number'").unwrap();
+}
@@ -470,3 +470,341 @@ mod sourcemap {
         );
     }
 }
+
+mod roblox_to_path {
+    use super::*;
+
+    const CONVERT_ROBLOX_TO_PATH_CONFIG: &str = r#"{
+        generator: 'retain_lines',
+        rules: [
+            {
+                rule: 'convert_require',
+                current: { name: 'roblox', rojo_sourcemap: './sourcemap.json' },
+                target: 'path',
+            }
+        ]
+    }"#;
+
+    fn get_resources(datamodel_case: bool, file_name: &str, content: &str) -> Resources {
+        let resources = memory_resources!(
+            "src/init.lua" => "return nil",
+            "src/a.lua" => "return nil",
+            "src/b.lua" => "return nil",
+            "src/c.lua" => "return nil",
+            "src/d/init.lua" => "return nil",
+            "src/d/d1.lua" => "return nil",
+            "src/d/d2.lua" => "return nil",
+            "Packages/Package1/init.lua" => "return nil",
+            "Packages/Package1/value.lua" => "return nil",
+            "main.server.lua" => "return nil",
+            ".darklua.json" => CONVERT_ROBLOX_TO_PATH_CONFIG,
+            "sourcemap.json" => if datamodel_case {
+                include_str!("../test_cases/sourcemap/place-sourcemap.json")
+            } else {
+                include_str!("../test_cases/sourcemap/sourcemap.json")
+            },
+        );
+        resources.write(file_name, content).unwrap();
+        resources
+    }
+
+    fn expect_conversion(file_name: &str, content: &str, expect_content: &str) {
+        for datamodel_case in [false, true].iter() {
+            expect_file_process(
+                &get_resources(*datamodel_case, file_name, content),
+                file_name,
+                expect_content,
+            );
+        }
+    }
+
+    #[test]
+    fn convert_sibling_module() {
+        expect_conversion(
+            "src/b.lua",
+            "local a = require(script.Parent.a)",
+            "local a = require('./a')",
+        );
+    }
+
+    #[test]
+    fn convert_sibling_module_with_find_first_child() {
+        expect_conversion(
+            "src/b.lua",
+            "local a = require(script.Parent:FindFirstChild('a'))",
+            "local a = require('./a')",
+        );
+    }
+
+    #[test]
+    fn convert_sibling_module_with_wait_for_child() {
+        expect_conversion(
+            "src/b.lua",
+            "local a = require(script.Parent:WaitForChild('a'))",
+            "local a = require('./a')",
+        );
+    }
+
+    #[test]
+    fn convert_sibling_module_with_index() {
+        expect_conversion(
+            "src/b.lua",
+            "local a = require(script.Parent['a'])",
+            "local a = require('./a')",
+        );
+    }
+
+    #[test]
+    fn convert_child_module_from_init_module() {
+        expect_conversion(
+            "src/d/init.lua",
+            "local d1 = require(script.d1)",
+            "local d1 = require('./d1')",
+        );
+    }
+
+    #[test]
+    fn convert_init_module_from_child_module() {
+        expect_conversion(
+            "src/d/d1.lua",
+            "local d = require(script.Parent)",
+            "local d = require('./init')",
+        );
+    }
+
+    #[test]
+    fn convert_nested_init_module_from_parent_init_module() {
+        expect_conversion(
+            "src/init.lua",
+            "local d = require(script.d)",
+            "local d = require('./d')",
+        );
+    }
+
+    #[test]
+    fn convert_module_from_nested_module() {
+        expect_conversion(
+            "src/d/d2.lua",
+            "local a = require(script.Parent.Parent.a)",
+            "local a = require('../a')",
+        );
+    }
+
+    #[test]
+    fn convert_root_module_from_nested_module() {
+        expect_conversion(
+            "src/d/d2.lua",
+            "local project = require(script.Parent.Parent)",
+            "local project = require('..')",
+        );
+    }
+
+    #[test]
+    fn convert_package_module_from_nested_module() {
+        expect_conversion(
+            "src/d/d1.lua",
+            "local value = require(script.Parent.Parent.Packages.Package1.value)",
+            "local value = require('../../Packages/Package1/value')",
+        );
+    }
+
+    #[test]
+    fn keep_require_not_found_in_sourcemap() {
+        expect_conversion(
+            "src/b.lua",
+            "local a = require(script.Parent.Unknown)",
+            "local a = require(script.Parent.Unknown)",
+        );
+    }
+
+    #[test]
+    fn keep_recursive_find_first_child_require() {
+        expect_conversion(
+            "src/b.lua",
+            "local a = require(script.Parent:FindFirstChild('a', true))",
+            "local a = require(script.Parent:FindFirstChild('a', true))",
+        );
+    }
+
+    #[test]
+    fn in_datamodel_convert_module_from_service() {
+        expect_file_process(
+            &get_resources(
+                true,
+                "main.server.lua",
+                "local a = require(game:GetService('ReplicatedStorage').Project.a)",
+            ),
+            "main.server.lua",
+            "local a = require('./src/a')",
+        );
+    }
+
+    #[test]
+    fn in_datamodel_convert_module_from_service_with_wait_for_child() {
+        expect_file_process(
+            &get_resources(
+                true,
+                "main.server.lua",
+                "local d = require(game:GetService('ReplicatedStorage'):WaitForChild('Project'):WaitForChild('d'))",
+            ),
+            "main.server.lua",
+            "local d = require('./src/d')",
+        );
+    }
+
+    #[test]
+    fn in_datamodel_convert_module_from_script_to_service() {
+        expect_file_process(
+            &get_resources(
+                true,
+                "src/d/d1.lua",
+                "local value = require(game.ReplicatedStorage.Project.Packages.Package1)",
+            ),
+            "src/d/d1.lua",
+            "local value = require('../../Packages/Package1')",
+        );
+    }
+
+    #[test]
+    fn keep_service_require_without_datamodel_sourcemap() {
+        expect_file_process(
+            &get_resources(
+                false,
+                "src/b.lua",
+                "local a = require(game:GetService('ReplicatedStorage').Project.a)",
+            ),
+            "src/b.lua",
+            "local a = require(game:GetService('ReplicatedStorage').Project.a)",
+        );
+    }
+}
+
+mod module_require_mode {
+    use super::*;
+
+    fn convert_config(current: &str, target: &str) -> String {
+        format!(
+            "{{ rules: [{{ rule: 'convert_require', current: {}, target: {} }}], generator: 'retain_lines' }}",
+            current, target
+        )
+    }
+
+    #[test]
+    fn convert_module_to_sibling_path() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local util = require('src.util')",
+            "src/util.lua" => "return nil",
+            ".darklua.json" => convert_config("'module'", "'path'"),
+        );
+        expect_file_process(&resources, "src/main.lua", "local util = require('./util')");
+    }
+
+    #[test]
+    fn convert_module_to_init_module_path() {
+        let resources = memory_resources!(
+            "main.lua" => "local lib = require('lib')",
+            "lib/init.lua" => "return nil",
+            ".darklua.json" => convert_config("'module'", "'path'"),
+        );
+        expect_file_process(&resources, "main.lua", "local lib = require('./lib')");
+    }
+
+    #[test]
+    fn convert_module_with_custom_package_path_to_path() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local json = require('json')",
+            "vendor/json.lua" => "return nil",
+            ".darklua.json" => convert_config(
+                "{ name: 'module', package_path: ['./src/?.lua', './vendor/?.lua'] }",
+                "'path'",
+            ),
+        );
+        expect_file_process(
+            &resources,
+            "src/main.lua",
+            "local json = require('../vendor/json')",
+        );
+    }
+
+    #[test]
+    fn keep_unknown_module() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local socket = require('socket.http')",
+            ".darklua.json" => convert_config("'module'", "'path'"),
+        );
+        expect_file_process(
+            &resources,
+            "src/main.lua",
+            "local socket = require('socket.http')",
+        );
+    }
+
+    #[test]
+    fn convert_sibling_path_to_module() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local util = require('./util')",
+            "src/util.lua" => "return nil",
+            ".darklua.json" => convert_config("'path'", "'module'"),
+        );
+        expect_file_process(
+            &resources,
+            "src/main.lua",
+            "local util = require('src.util')",
+        );
+    }
+
+    #[test]
+    fn convert_init_module_path_to_module() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local lib = require('../lib')",
+            "lib/init.lua" => "return nil",
+            ".darklua.json" => convert_config("'path'", "'module'"),
+        );
+        expect_file_process(&resources, "src/main.lua", "local lib = require('lib')");
+    }
+
+    #[test]
+    fn convert_path_to_module_with_custom_package_path() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local format = require('./util/format')",
+            "src/util/format.luau" => "return nil",
+            ".darklua.json" => convert_config(
+                "'path'",
+                "{ name: 'module', package_path: ['./src/?.luau', './src/?/init.luau'] }",
+            ),
+        );
+        expect_file_process(
+            &resources,
+            "src/main.lua",
+            "local format = require('util.format')",
+        );
+    }
+
+    #[test]
+    fn keep_path_outside_of_package_path() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local format = require('./format.luau')",
+            "src/format.luau" => "return nil",
+            ".darklua.json" => convert_config("'path'", "'module'"),
+        );
+        expect_file_process(
+            &resources,
+            "src/main.lua",
+            "local format = require('./format.luau')",
+        );
+    }
+
+    #[test]
+    fn convert_module_to_roblox() {
+        let resources = memory_resources!(
+            "src/main.lua" => "local util = require('src.util')",
+            "src/util.lua" => "return nil",
+            ".darklua.json" => convert_config("'module'", "'roblox'"),
+        );
+        expect_file_process(
+            &resources,
+            "src/main.lua",
+            "local util = require(script.Parent:FindFirstChild('util'))",
+        );
+    }
+}
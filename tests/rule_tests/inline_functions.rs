@@ -0,0 +1,88 @@
+use darklua_core::rules::{InlineFunctions, Rule};
+
+test_rule!(
+    inline_functions,
+    InlineFunctions::default(),
+    call_statement_without_parameters("local function log() print('hello') end log()")
+        => "local function log() print('hello') end do print('hello') end",
+    call_statement_with_parameters("local function log(message) print(message) end log('hello')")
+        => "local function log(message) print(message) end do local message = 'hello' print(message) end",
+    call_statement_with_missing_argument("local function log(a, b) print(a, b) end log(1)")
+        => "local function log(a, b) print(a, b) end do local a, b = 1 print(a, b) end",
+    call_statement_with_extra_argument("local function log() print('hello') end log(getValue())")
+        => "local function log() print('hello') end do getValue() print('hello') end",
+    call_statement_with_string_argument("local function log(message) print(message) end log 'hello'")
+        => "local function log(message) print(message) end do local message = 'hello' print(message) end",
+    call_statement_discards_return_value("local function add(a, b) return a + b end add(1, 2)")
+        => "local function add(a, b) return a + b end do local a, b = 1, 2 local _ = a + b end",
+    call_statement_keeps_return_side_effects("local function run(callback) return callback() end run(f)")
+        => "local function run(callback) return callback() end do local callback = f callback() end",
+    call_expression_with_negative_number(
+        "local function square(n) return n ^ 2 end return square(-2)"
+    ) => "local function square(n) return n ^ 2 end return ((-2) ^ 2)",
+    call_expression_with_literal("local function double(n) return n * 2 end return double(4)")
+        => "local function double(n) return n * 2 end return (4 * 2)",
+    call_expression_with_identifier("local function double(n) return n * 2 end local value = 1 return double(value)")
+        => "local function double(n) return n * 2 end local value = 1 return (value * 2)",
+    call_expression_with_missing_argument("local function isNil(value) return value == nil end return isNil()")
+        => "local function isNil(value) return value == nil end return (nil == nil)",
+    call_expression_with_call_in_body("local function clamp(n) return math.max(0, n) end return clamp(-2)")
+        => "local function clamp(n) return math.max(0, n) end return math.max(0, (-2))",
+    call_expression_with_field("local function getName(object) return object.Name end local part = {} return getName(part)")
+        => "local function getName(object) return object.Name end local part = {} return part.Name",
+    call_expression_with_method("local function upper(text) return text:upper() end return upper('abc')")
+        => "local function upper(text) return text:upper() end return ('abc'):upper()",
+    call_expression_with_shadowed_parameter(
+        "local function wrap(n) return function(n) return n end end return wrap(1)"
+    ) => "local function wrap(n) return function(n) return n end end return function(n) return n end",
+    call_expression_with_upvalue("local offset = 1 local function shift(n) return n + offset end return shift(2)")
+        => "local offset = 1 local function shift(n) return n + offset end return (2 + offset)",
+    nested_call_statement("local function log(message) print(message) end local function run() log('a') end")
+        => "local function log(message) print(message) end local function run() do local message = 'a' print(message) end end",
+);
+
+test_rule_without_effects!(
+    InlineFunctions::default(),
+    recursive_function("local function loop(n) if n > 0 then loop(n - 1) end end loop(10)"),
+    variadic_function("local function log(...) print(...) end log(1, 2)"),
+    function_used_as_value("local function log() print('hello') end log() callbacks.log = log"),
+    function_reassigned("local function log() print('hello') end log() log = nil"),
+    function_with_early_return("local function log(value) if value then return end print(value) end log(1)"),
+    function_called_as_method("local function log() end log:method()"),
+    expression_with_statements("local function double(n) local value = n * 2 return value end return double(2)"),
+    expression_with_multiple_values("local function pair(a) return a, a end return pair(2)"),
+    expression_with_call_argument("local function double(n) return n * 2 end return double(f())"),
+    expression_with_identifier_argument_and_call(
+        "local function add(n) return n + f() end local value = 1 return add(value)"
+    ),
+    upvalue_shadowed_at_call_site(
+        "local offset = 1 local function log() print(offset) end do local offset = 2 log() end"
+    ),
+    global_shadowed_at_call_site(
+        "local function log(value) print(value) end do local print = warn log(1) end"
+    ),
+    argument_captured_by_function(
+        "local function wrap(n) return function(value) return value + n end end local value = 1 return wrap(value)"
+    ),
+    argument_moved_into_returned_function(
+        "local function make(x) return function() return x end end local i = 0 local f = make(i) i = 5 return f()"
+    ),
+    large_function(
+        "local function run() a() b() c() d() e() f() g() h() i() end run()"
+    ),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'inline_functions',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'inline_functions'").unwrap();
+}
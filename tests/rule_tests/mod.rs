@@ -417,12 +417,16 @@ macro_rules! test_rule_snapshot {
 
 mod append_text_comment;
 mod compute_expression;
+mod conditional_compilation;
 mod convert_index_to_field;
+mod convert_luau_number;
 mod convert_require;
 mod filter_early_return;
 mod group_local_assignment;
 mod inject_value;
+mod inline_functions;
 mod no_local_function;
+mod propagate_constants;
 mod remove_assertions;
 mod remove_call_parens;
 mod remove_comments;
@@ -431,6 +435,8 @@ mod remove_continue;
 mod remove_debug_profiling;
 mod remove_empty_do;
 mod remove_floor_division;
+mod remove_function_calls;
+mod remove_generalized_iteration;
 mod remove_if_expression;
 mod remove_interpolated_string;
 mod remove_method_definition;
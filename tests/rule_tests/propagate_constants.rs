@@ -0,0 +1,58 @@
+use darklua_core::rules::{PropagateConstants, Rule};
+
+test_rule!(
+    propagate_constants,
+    PropagateConstants::default(),
+    boolean_constant("local DEBUG = false if DEBUG then print('debug') end")
+        => "local DEBUG = false if false then print('debug') end",
+    nil_constant("local value = nil return value") => "local value = nil return nil",
+    number_constant("local size = 10 return size") => "local size = 10 return 10",
+    string_constant("local name = 'darklua' print(name)") => "local name = 'darklua' print('darklua')",
+    computed_constant("local size = 2 * 8 return size") => "local size = 2 * 8 return 16",
+    fold_binary_expression("local size = 10 return size * 2") => "local size = 10 return 20",
+    fold_unary_expression("local DEBUG = false return not DEBUG") => "local DEBUG = false return true",
+    fold_and_expression("local DEBUG = false return DEBUG and 'debug' or 'release'")
+        => "local DEBUG = false return 'release'",
+    constant_used_in_function("local DEBUG = true local function log() if DEBUG then end end")
+        => "local DEBUG = true local function log() if true then end end",
+    constant_used_as_prefix("local name = 'abc' return name:upper()")
+        => "local name = 'abc' return ('abc'):upper()",
+    constant_alias("local DEBUG = false local VERBOSE = DEBUG return VERBOSE")
+        => "local DEBUG = false local VERBOSE = false return false",
+    shadowed_constant("local value = 1 do local value = f() print(value) end return value")
+        => "local value = 1 do local value = f() print(value) end return 1",
+    constant_shadowed_by_parameter("local value = 1 local function f(value) return value end")
+        => "local value = 1 local function f(value) return value end",
+    keep_other_expressions("local value = 1 return 1 + 2, value + 1")
+        => "local value = 1 return 1 + 2, 2",
+);
+
+test_rule_without_effects!(
+    PropagateConstants::default(),
+    reassigned_variable("local value = 1 value = 2 return value"),
+    reassigned_variable_in_function(
+        "local value = 1 local function f() value = 2 end return value"
+    ),
+    compound_assigned_variable("local value = 1 value += 2 return value"),
+    reassigned_with_function_statement("local value = 1 function value() end return value"),
+    variable_with_unknown_value("local value = f() return value"),
+    variable_with_table("local value = {} return value"),
+    variable_without_value("local value return value"),
+    global_variable("return value"),
+    reassigned_in_multiple_assignment("local a, b = 1, 2 a, b = b, a return a, b"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'propagate_constants',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'propagate_constants'").unwrap();
+}
@@ -0,0 +1,56 @@
+use darklua_core::rules::{RemoveFunctionCalls, Rule};
+
+fn new_rule() -> RemoveFunctionCalls {
+    RemoveFunctionCalls::new(["print", "Logger.debug", "Debug:trace"]).unwrap()
+}
+
+test_rule!(
+    remove_function_calls,
+    new_rule(),
+    remove_print("print('hello')") => "do end",
+    remove_field_function("Logger.debug('value', 1)") => "do end",
+    remove_method("Debug:trace('value')") => "do end",
+    remove_print_with_maybe_side_effects("print(getValue())") => "getValue()",
+    remove_print_in_expression("local value = print('hello')") => "local value = nil",
+    keep_other_field_function("Logger.info('value')") => "Logger.info('value')",
+    keep_field_called_as_method("Logger:debug('value')") => "Logger:debug('value')",
+    keep_method_called_as_field("Debug.trace('value')") => "Debug.trace('value')",
+);
+
+test_rule!(
+    remove_function_calls_without_side_effects,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_function_calls',
+        functions: ['print', 'Logger.debug'],
+        preserve_arguments_side_effects: false,
+    }"#,
+    )
+    .unwrap(),
+    remove_print("print('hello')") => "do end",
+    remove_print_with_maybe_side_effects("print(getValue())") => "do end",
+    remove_field_function_with_maybe_side_effects("Logger.debug(getValue())") => "do end",
+);
+
+test_rule_without_effects!(
+    new_rule(),
+    print_identifier_used("local print = nil print('hello')"),
+    logger_identifier_used("local Logger = {} Logger.debug('hello')"),
+    debug_identifier_used("local function fn(Debug) Debug:trace('hello') end"),
+);
+
+test_rule_without_effects!(
+    RemoveFunctionCalls::default(),
+    empty_function_list("print('hello')"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_function_calls',
+        functions: ['print'],
+    }"#,
+    )
+    .unwrap();
+}
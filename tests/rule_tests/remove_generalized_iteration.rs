@@ -0,0 +1,59 @@
+use darklua_core::rules::{RemoveGeneralizedIteration, Rule};
+
+test_rule!(
+    remove_generalized_iteration,
+    RemoveGeneralizedIteration::default(),
+    generic_for_with_call("for key, value in pairs(object) do end") => "for key, value in pairs(object) do end",
+    generic_for_with_iterator_and_state("for key, value in next, object do end") => "for key, value in next, object do end",
+    generic_for_with_variable_arguments("for key, value in ... do end") => "for key, value in ... do end",
+    generic_for_with_function("for key in function() end do end") => "for key in function() end do end",
+    generic_for_with_table("for key, value in {} do end") => "for key, value in next, {} do end",
+);
+
+test_rule_snapshot!(
+    remove_generalized_iteration,
+    RemoveGeneralizedIteration::default(),
+    generic_for_with_identifier("for key, value in object do end"),
+    generic_for_with_field("for key, value in self.items do end"),
+    generic_for_with_parenthese_call("for key, value in (getItems()) do end"),
+    generic_for_with_table_shadowing_next("local next for key, value in { 1 } do end"),
+    generic_for_with_local_named_like_helper(
+        "local __DARKLUA_ITER = {} for key, value in __DARKLUA_ITER do end"
+    ),
+    generic_for_with_global_named_like_helper(
+        "for key, value in object do end print(__DARKLUA_ITER, __DARKLUA_ITER0)"
+    ),
+    nested_generic_for(
+        r#"
+    for _, list in lists do
+        for _, value in list do
+            print(value)
+        end
+    end
+    "#
+    ),
+    generic_for_in_function(
+        r#"
+    local function printAll(object)
+        for key, value in object do
+            print(key, value)
+        end
+    end
+    "#
+    ),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_generalized_iteration',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'remove_generalized_iteration'").unwrap();
+}